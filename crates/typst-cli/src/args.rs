@@ -48,7 +48,11 @@ pub enum Command {
 
     /// Watches an input file and recompiles on changes
     #[command(visible_alias = "w")]
-    Watch(CompileCommand),
+    Watch(WatchCommand),
+
+    /// Compiles all documents listed in a build file
+    #[command(visible_alias = "b")]
+    Build(BuildCommand),
 
     /// Initializes a new project from a template
     Init(InitCommand),
@@ -56,6 +60,10 @@ pub enum Command {
     /// Processes an input file to extract provided metadata
     Query(QueryCommand),
 
+    /// Evaluates an input file under a debugger, speaking the Debug Adapter
+    /// Protocol on stdin and stdout
+    Debug(DebugCommand),
+
     /// Lists all discovered fonts in system and custom font paths
     Fonts(FontsCommand),
 
@@ -123,12 +131,90 @@ pub struct CompileCommand {
     #[arg(long = "timings", value_name = "OUTPUT_JSON")]
     pub timings: Option<Option<PathBuf>>,
 
+    /// Profiles the Typst functions, show rules, and context expressions of
+    /// the document (experimental)
+    ///
+    /// Prints the items that took the most time. If a path is given, also
+    /// writes the time spent in each stack of items in the folded format
+    /// understood by flame graph tools such as https://www.speedscope.app.
+    #[arg(long = "profile", value_name = "OUTPUT_FOLDED")]
+    pub profile: Option<Option<PathBuf>>,
+
     /// One (or multiple comma-separated) PDF standards that Typst will enforce
     /// conformance with.
     #[arg(long = "pdf-standard", value_delimiter = ',')]
     pub pdf_standard: Vec<PdfStandard>,
 }
 
+/// Watches an input file and recompiles on changes
+#[derive(Debug, Clone, Parser)]
+pub struct WatchCommand {
+    /// Arguments for compilation
+    #[clap(flatten)]
+    pub compile: CompileCommand,
+
+    /// Serves a live preview of the document on localhost, optionally on a
+    /// specific port (defaults to 3000)
+    ///
+    /// The preview page is updated whenever the document is recompiled. Only
+    /// pages that changed are sent to the browser. Clicking into a page jumps
+    /// to the corresponding source location.
+    #[arg(long = "preview", value_name = "PORT")]
+    pub preview: Option<Option<u16>>,
+}
+
+/// Compiles all documents listed in a build file
+#[derive(Debug, Clone, Parser)]
+pub struct BuildCommand {
+    /// Path to the build file
+    ///
+    /// The build file is a TOML file with one `[[target]]` table per document.
+    /// Each target has an `input` path and supports the keys `name`, `output`,
+    /// `format`, `root`, `inputs`, `font-paths`, `ignore-system-fonts`,
+    /// `pages`, `ppi`, `pdf-standard` and `make-deps`, named after the
+    /// corresponding arguments of `typst compile`. A `[defaults]` table with
+    /// the same keys applies to all targets. Relative paths are resolved
+    /// relative to the build file.
+    #[clap(default_value = "typst-build.toml", value_hint = ValueHint::FilePath)]
+    pub file: PathBuf,
+
+    /// Only builds the targets with the given names
+    #[clap(long = "target", value_name = "NAME", value_delimiter = ',')]
+    pub targets: Vec<String>,
+
+    /// Compiles the targets in parallel instead of one after another
+    #[clap(long)]
+    pub parallel: bool,
+
+    /// The documents' creation date formatted as a UNIX timestamp.
+    ///
+    /// For more information, see <https://reproducible-builds.org/specs/source-date-epoch/>.
+    #[clap(
+        long = "creation-timestamp",
+        env = "SOURCE_DATE_EPOCH",
+        value_name = "UNIX_TIMESTAMP",
+        value_parser = parse_source_date_epoch,
+    )]
+    pub creation_timestamp: Option<DateTime<Utc>>,
+
+    /// The format to emit diagnostics in
+    #[clap(
+        long,
+        default_value_t = DiagnosticFormat::Human,
+        value_parser = clap::value_parser!(DiagnosticFormat)
+    )]
+    pub diagnostic_format: DiagnosticFormat,
+
+    /// Arguments related to storage of packages in the system
+    #[clap(flatten)]
+    pub package_storage_args: PackageStorageArgs,
+
+    /// Number of parallel jobs spawned during compilation,
+    /// defaults to number of CPUs. Setting it to 1 disables parallelism.
+    #[clap(long, short)]
+    pub jobs: Option<usize>,
+}
+
 /// A PDF standard.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
 #[allow(non_camel_case_types)]
@@ -178,6 +264,16 @@ pub struct QueryCommand {
     #[clap(long = "one", default_value = "false")]
     pub one: bool,
 
+    /// Adds where each element ended up in the document and where it was
+    /// created in the source
+    ///
+    /// Each result is then a dictionary with the element (or the selected
+    /// field) under `value`, its one-based `page`, its `position` on the page
+    /// in points, the `page-numbering` of that page and its `source` file and
+    /// line range.
+    #[clap(long = "locations")]
+    pub locations: bool,
+
     /// The format to serialize in
     #[clap(long = "format", default_value = "json")]
     pub format: SerializationFormat,
@@ -185,6 +281,13 @@ pub struct QueryCommand {
     /// Whether to pretty-print the serialized output
     #[clap(long)]
     pub pretty: bool,
+
+    /// Watches the input file and reruns the query on changes
+    ///
+    /// Each changed result is written to stdout as a single line of JSON.
+    /// Diagnostics are written to stderr.
+    #[clap(long, conflicts_with = "pretty")]
+    pub watch: bool,
 }
 
 // Output file format for query command
@@ -194,6 +297,14 @@ pub enum SerializationFormat {
     Yaml,
 }
 
+/// Evaluates an input file under a debugger
+#[derive(Debug, Clone, Parser)]
+pub struct DebugCommand {
+    /// Shared arguments
+    #[clap(flatten)]
+    pub common: SharedArgs,
+}
+
 /// Common arguments of compile, watch, and query.
 #[derive(Debug, Clone, Args)]
 pub struct SharedArgs {
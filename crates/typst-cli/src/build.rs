@@ -0,0 +1,373 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use codespan_reporting::term;
+use codespan_reporting::term::termcolor::{ColorSpec, WriteColor};
+use ecow::{eco_format, EcoVec};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::Deserialize;
+use typst::diag::{bail, At, SourceDiagnostic, SourceResult, StrResult, Warned};
+use typst::syntax::Span;
+use typst::utils::format_duration;
+use typst_kit::package::PackageStorage;
+
+use crate::args::{
+    BuildCommand, CompileCommand, DiagnosticFormat, FontArgs, Input, Output,
+    OutputFormat, PageRangeArgument, PdfStandard, SharedArgs,
+};
+use crate::compile::{export, print_diagnostics, write_make_deps};
+use crate::world::{SharedFonts, SystemWorld};
+use crate::{package, print_error, set_failed, terminal};
+
+/// Execute a build command.
+pub fn build(command: &BuildCommand) -> StrResult<()> {
+    let file = BuildFile::load(&command.file)?;
+    let dir = command.file.parent().unwrap_or(Path::new(""));
+
+    // Set up the thread pool.
+    if let Some(jobs) = command.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .use_current_thread()
+            .build_global()
+            .ok();
+    }
+
+    // Fonts and packages are shared between all targets. Fonts are searched
+    // only once for each distinct combination of font arguments.
+    let package_storage = Arc::new(package::storage(&command.package_storage_args));
+    let mut fonts = HashMap::new();
+
+    let mut targets = vec![];
+    for (i, config) in file.targets.into_iter().enumerate() {
+        let config = config.with_defaults(&file.defaults);
+        let name = config.name(i);
+        if !command.targets.is_empty() && !command.targets.contains(&name) {
+            continue;
+        }
+
+        let compile = config.into_command(&name, dir, command)?;
+        let args = &compile.common.font_args;
+        let shared = fonts
+            .entry((args.font_paths.clone(), args.ignore_system_fonts))
+            .or_insert_with(|| SharedFonts::search(args))
+            .clone();
+
+        targets.push(Target { name, command: compile, fonts: shared });
+    }
+
+    for name in &command.targets {
+        if !targets.iter().any(|target| &target.name == name) {
+            bail!("build file contains no target named {name}");
+        }
+    }
+
+    if targets.is_empty() {
+        bail!("build file contains no targets");
+    }
+
+    let format = command.diagnostic_format;
+    if command.parallel {
+        let built: Vec<_> = targets
+            .into_par_iter()
+            .map(|target| {
+                let built = target.build(&package_storage);
+                (target, built)
+            })
+            .collect();
+
+        for (target, built) in built {
+            target.report(built, format)?;
+        }
+    } else {
+        for target in targets {
+            let built = target.build(&package_storage);
+            target.report(built, format)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A document to compile.
+struct Target {
+    /// The target's name, used in status messages.
+    name: String,
+    /// The arguments to compile it with.
+    command: CompileCommand,
+    /// The fonts available to it.
+    fonts: SharedFonts,
+}
+
+impl Target {
+    /// Compile and export the document.
+    fn build(&self, package_storage: &Arc<PackageStorage>) -> StrResult<Built> {
+        let start = Instant::now();
+        let mut world = SystemWorld::with_resources(
+            &self.command.common,
+            self.fonts.clone(),
+            Arc::clone(package_storage),
+        )?;
+
+        let Warned { output, warnings } = typst::compile(&world);
+        let result = output
+            .and_then(|document| export(&mut world, &document, &self.command, false))
+            .and_then(|()| {
+                write_make_deps(&mut world, &self.command).at(Span::detached())
+            });
+
+        Ok(Built { world, warnings, result, duration: start.elapsed() })
+    }
+
+    /// Print a status line and the diagnostics for a built target.
+    fn report(&self, built: StrResult<Built>, format: DiagnosticFormat) -> StrResult<()> {
+        let styles = term::Styles::default();
+        let print_status = |color: &ColorSpec, message: &str| {
+            self.print_status(color, message)
+                .map_err(|err| eco_format!("failed to print status ({err})"))
+        };
+
+        let built = match built {
+            Ok(built) => built,
+            Err(message) => {
+                set_failed();
+                print_status(&styles.header_error, "failed")?;
+                print_error(&message).unwrap();
+                return Ok(());
+            }
+        };
+
+        let duration = format_duration(built.duration);
+        let errors = match &built.result {
+            Ok(()) if built.warnings.is_empty() => {
+                let output = self.command.output();
+                print_status(
+                    &styles.header_note,
+                    &format!("compiled successfully in {duration} to {output}"),
+                )?;
+                &[][..]
+            }
+            Ok(()) => {
+                print_status(
+                    &styles.header_warning,
+                    &format!("compiled with warnings in {duration}"),
+                )?;
+                &[][..]
+            }
+            Err(errors) => {
+                set_failed();
+                print_status(&styles.header_error, "compiled with errors")?;
+                errors.as_slice()
+            }
+        };
+
+        print_diagnostics(&built.world, errors, &built.warnings, format)
+            .map_err(|err| eco_format!("failed to print diagnostics ({err})"))
+    }
+
+    /// Print a line that starts with the target's name.
+    fn print_status(&self, color: &ColorSpec, message: &str) -> io::Result<()> {
+        let mut out = terminal::out();
+        out.set_color(color)?;
+        write!(out, "{}", self.name)?;
+        out.reset()?;
+        writeln!(out, " {message}")
+    }
+}
+
+/// The outcome of compiling a target.
+struct Built {
+    /// The world the target was compiled in.
+    world: SystemWorld,
+    /// Warnings that occurred during compilation.
+    warnings: EcoVec<SourceDiagnostic>,
+    /// Whether compilation and export succeeded.
+    result: SourceResult<()>,
+    /// How long it took.
+    duration: Duration,
+}
+
+/// A build file, listing the documents to compile.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BuildFile {
+    /// Settings that apply to all targets, unless a target overrides them.
+    #[serde(default)]
+    defaults: TargetConfig,
+    /// The documents to compile.
+    #[serde(default, rename = "target")]
+    targets: Vec<TargetConfig>,
+}
+
+impl BuildFile {
+    /// Read and parse a build file.
+    fn load(path: &Path) -> StrResult<Self> {
+        let text = fs::read_to_string(path).map_err(|err| {
+            eco_format!("failed to read build file {} ({err})", path.display())
+        })?;
+        toml::from_str(&text).map_err(|err| {
+            eco_format!("failed to parse build file {} ({err})", path.display())
+        })
+    }
+}
+
+/// The settings for one document in a build file. Apart from `name`, the keys
+/// correspond to the arguments of `typst compile`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct TargetConfig {
+    /// The name used to refer to the target.
+    name: Option<String>,
+    /// Path to the input Typst file.
+    input: Option<PathBuf>,
+    /// Path to the output file.
+    output: Option<PathBuf>,
+    /// The format of the output file.
+    format: Option<String>,
+    /// The project root.
+    root: Option<PathBuf>,
+    /// Key-value pairs visible through `sys.inputs`.
+    inputs: BTreeMap<String, String>,
+    /// Additional directories that are searched for fonts.
+    font_paths: Vec<PathBuf>,
+    /// Whether to skip searching for system fonts.
+    ignore_system_fonts: Option<bool>,
+    /// Which pages to export, as comma-separated page ranges.
+    pages: Option<String>,
+    /// The PPI to use for PNG export.
+    ppi: Option<f32>,
+    /// The PDF standards to conform with.
+    pdf_standard: Vec<String>,
+    /// Where to write a Makefile rule describing the compilation.
+    make_deps: Option<PathBuf>,
+}
+
+impl TargetConfig {
+    /// Fill in settings the target doesn't specify from the defaults.
+    ///
+    /// Inputs and font paths of the target are added to those of the
+    /// defaults instead of replacing them.
+    fn with_defaults(self, defaults: &TargetConfig) -> Self {
+        let defaults = defaults.clone();
+
+        let mut inputs = defaults.inputs;
+        inputs.extend(self.inputs);
+
+        let mut font_paths = defaults.font_paths;
+        font_paths.extend(self.font_paths);
+
+        Self {
+            name: self.name,
+            input: self.input.or(defaults.input),
+            output: self.output.or(defaults.output),
+            format: self.format.or(defaults.format),
+            root: self.root.or(defaults.root),
+            inputs,
+            font_paths,
+            ignore_system_fonts: self
+                .ignore_system_fonts
+                .or(defaults.ignore_system_fonts),
+            pages: self.pages.or(defaults.pages),
+            ppi: self.ppi.or(defaults.ppi),
+            pdf_standard: if self.pdf_standard.is_empty() {
+                defaults.pdf_standard
+            } else {
+                self.pdf_standard
+            },
+            make_deps: self.make_deps.or(defaults.make_deps),
+        }
+    }
+
+    /// The target's name: either the configured one, the input path or its
+    /// position in the build file.
+    fn name(&self, index: usize) -> String {
+        match (&self.name, &self.input) {
+            (Some(name), _) => name.clone(),
+            (None, Some(input)) => input.display().to_string(),
+            (None, None) => format!("target {}", index + 1),
+        }
+    }
+
+    /// Turn the settings into the arguments of a compilation. Relative paths
+    /// are resolved relative to `dir`.
+    fn into_command(
+        self,
+        name: &str,
+        dir: &Path,
+        build: &BuildCommand,
+    ) -> StrResult<CompileCommand> {
+        let Some(input) = self.input else {
+            bail!("target {name} has no input");
+        };
+
+        let format = self
+            .format
+            .map(|format| {
+                OutputFormat::from_str(&format, true)
+                    .map_err(|_| eco_format!("target {name} has unknown format {format}"))
+            })
+            .transpose()?;
+
+        let pages = self
+            .pages
+            .map(|pages| {
+                pages
+                    .split(',')
+                    .map(str::parse::<PageRangeArgument>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| eco_format!("target {name} has invalid pages ({err})"))
+            })
+            .transpose()?;
+
+        let pdf_standard = self
+            .pdf_standard
+            .iter()
+            .map(|standard| {
+                PdfStandard::from_str(standard, true).map_err(|_| {
+                    eco_format!("target {name} has unknown PDF standard {standard}")
+                })
+            })
+            .collect::<StrResult<Vec<_>>>()?;
+
+        let command = CompileCommand {
+            common: SharedArgs {
+                input: Input::Path(dir.join(input)),
+                root: self.root.map(|root| dir.join(root)),
+                inputs: self.inputs.into_iter().collect(),
+                font_args: FontArgs {
+                    font_paths: self
+                        .font_paths
+                        .iter()
+                        .map(|path| dir.join(path))
+                        .collect(),
+                    ignore_system_fonts: self.ignore_system_fonts.unwrap_or(false),
+                },
+                creation_timestamp: build.creation_timestamp,
+                diagnostic_format: build.diagnostic_format,
+                package_storage_args: build.package_storage_args.clone(),
+                jobs: None,
+            },
+            output: self.output.map(|output| Output::Path(dir.join(output))),
+            pages,
+            make_deps: self.make_deps.map(|path| dir.join(path)),
+            format,
+            open: None,
+            ppi: self.ppi.unwrap_or(144.0),
+            timings: None,
+            profile: None,
+            pdf_standard,
+        };
+
+        // Only meant for input validation
+        command
+            .output_format()
+            .map_err(|err| eco_format!("target {name}: {err}"))?;
+
+        Ok(command)
+    }
+}
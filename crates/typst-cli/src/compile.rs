@@ -5,11 +5,11 @@ use std::path::{Path, PathBuf};
 use chrono::{Datelike, Timelike};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term;
-use ecow::{eco_format, EcoString};
+use ecow::{eco_format, EcoString, EcoVec};
 use parking_lot::RwLock;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use typst::diag::{
-    bail, At, Severity, SourceDiagnostic, SourceResult, StrResult, Warned,
+    bail, At, Recovered, Severity, SourceDiagnostic, SourceResult, StrResult, Warned,
 };
 use typst::foundations::{Datetime, Smart};
 use typst::layout::{Frame, Page, PageRanges};
@@ -22,6 +22,7 @@ use crate::args::{
     CompileCommand, DiagnosticFormat, Input, Output, OutputFormat, PageRangeArgument,
     PdfStandard,
 };
+use crate::server::PreviewServer;
 use crate::timings::Timer;
 use crate::watch::Status;
 use crate::world::SystemWorld;
@@ -101,26 +102,52 @@ pub fn compile(mut timer: Timer, mut command: CompileCommand) -> StrResult<()> {
 
     let mut world =
         SystemWorld::new(&command.common).map_err(|err| eco_format!("{err}"))?;
-    timer.record(&mut world, |world| compile_once(world, &mut command, false))??;
+    timer
+        .record(&mut world, |world| compile_once(world, &mut command, false, None))??;
     Ok(())
 }
 
 /// Compile a single time.
 ///
 /// Returns whether it compiled without errors.
+///
+/// When watching, compilation recovers from errors where possible. The
+/// document is then only exported if there were no errors at all, but a given
+/// preview server is still updated with it, showing placeholders where the
+/// errors occurred.
 #[typst_macros::time(name = "compile once")]
 pub fn compile_once(
     world: &mut SystemWorld,
     command: &mut CompileCommand,
     watching: bool,
+    preview: Option<&PreviewServer>,
 ) -> StrResult<()> {
     let start = std::time::Instant::now();
     if watching {
-        Status::Compiling.print(command).unwrap();
+        Status::Compiling.print(command, preview).unwrap();
     }
 
-    let Warned { output, warnings } = typst::compile(world);
-    let result = output.and_then(|document| export(world, &document, command, watching));
+    let Warned { output, warnings } = if watching {
+        typst::compile_recovering(world)
+    } else {
+        let Warned { output, warnings } = typst::compile(world);
+        let output = output.map(|output| Recovered { output, errors: EcoVec::new() });
+        Warned { output, warnings }
+    };
+
+    let result = output.and_then(|Recovered { output: document, errors }| {
+        if errors.is_empty() {
+            export(world, &document, command, watching)?;
+        }
+        if let Some(preview) = preview {
+            preview.update(world, document);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    });
 
     match result {
         // Export the PDF / PNG.
@@ -129,9 +156,9 @@ pub fn compile_once(
 
             if watching {
                 if warnings.is_empty() {
-                    Status::Success(duration).print(command).unwrap();
+                    Status::Success(duration).print(command, preview).unwrap();
                 } else {
-                    Status::PartialSuccess(duration).print(command).unwrap();
+                    Status::PartialSuccess(duration).print(command, preview).unwrap();
                 }
             }
 
@@ -152,7 +179,11 @@ pub fn compile_once(
             set_failed();
 
             if watching {
-                Status::Error.print(command).unwrap();
+                Status::Error.print(command, preview).unwrap();
+            }
+
+            if let Some(preview) = preview {
+                preview.fail(&errors);
             }
 
             print_diagnostics(
@@ -169,7 +200,7 @@ pub fn compile_once(
 }
 
 /// Export into the target format.
-fn export(
+pub fn export(
     world: &mut SystemWorld,
     document: &Document,
     command: &CompileCommand,
@@ -404,12 +435,20 @@ impl ExportCache {
 
         cache.with_upgraded(|cache| std::mem::replace(&mut cache[i], hash) == hash)
     }
+
+    /// Forgets the hashes of all frames from index `len` onwards.
+    pub fn truncate(&self, len: usize) {
+        self.cache.write().truncate(len);
+    }
 }
 
 /// Writes a Makefile rule describing the relationship between the output and
 /// its dependencies to the path specified by the --make-deps argument, if it
 /// was provided.
-fn write_make_deps(world: &mut SystemWorld, command: &CompileCommand) -> StrResult<()> {
+pub fn write_make_deps(
+    world: &mut SystemWorld,
+    command: &CompileCommand,
+) -> StrResult<()> {
     let Some(ref make_deps_path) = command.make_deps else { return Ok(()) };
     let Output::Path(output_path) = command.output() else {
         bail!("failed to create make dependencies file because output was stdout")
@@ -0,0 +1,469 @@
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use ecow::{eco_format, EcoString};
+use serde_json::{json, Value as Json};
+use typst::diag::{bail, HintedStrResult, Severity, SourceDiagnostic, StrResult, Warned};
+use typst::eval::debug::{Call, Controller, Reason, Step};
+use typst::foundations::{Repr, Value};
+use typst::syntax::{ast, FileId, LinkedNode, Source, Span, SyntaxKind, VirtualPath};
+use typst::World;
+
+use crate::args::{DebugCommand, Input};
+use crate::world::SystemWorld;
+
+/// The id of the only thread reported to the client.
+const THREAD: u64 = 1;
+
+/// Execute a debug command.
+///
+/// Speaks the Debug Adapter Protocol on stdin and stdout. Evaluation starts
+/// once the client is done with configuration and runs until the document is
+/// laid out. Nothing is exported.
+pub fn debug(command: &DebugCommand) -> HintedStrResult<()> {
+    if let Input::Stdin = command.common.input {
+        bail!("cannot debug input from stdin");
+    }
+
+    let world = Arc::new(SystemWorld::new(&command.common)?);
+    let client = Arc::new(Client::default());
+    let controller = Arc::new(Controller::new({
+        let client = client.clone();
+        move |stop| {
+            let reason = match stop.reason {
+                Reason::Entry => "entry",
+                Reason::Breakpoint => "breakpoint",
+                Reason::Step => "step",
+                Reason::Pause => "pause",
+            };
+            client.event(
+                "stopped",
+                json!({
+                    "reason": reason,
+                    "threadId": THREAD,
+                    "allThreadsStopped": true,
+                }),
+            );
+        }
+    }));
+
+    let mut session = Session {
+        world,
+        client,
+        controller,
+        references: vec![],
+        started: false,
+    };
+
+    let mut stdin = io::stdin().lock();
+    while let Some(request) = read_message(&mut stdin)
+        .map_err(|err| eco_format!("failed to read request ({err})"))?
+    {
+        if !session.handle(&request) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// The state of a debugging session.
+struct Session {
+    world: Arc<SystemWorld>,
+    client: Arc<Client>,
+    controller: Arc<Controller>,
+    /// Expandable variables, referenced by their index plus one. Cleared
+    /// whenever evaluation resumes.
+    references: Vec<Vec<(EcoString, Value)>>,
+    started: bool,
+}
+
+impl Session {
+    /// Handle a request from the client. Returns whether the session should
+    /// go on.
+    fn handle(&mut self, request: &Json) -> bool {
+        let command = request["command"].as_str().unwrap_or_default();
+        let args = &request["arguments"];
+        let result = match command {
+            "initialize" => Ok(json!({ "supportsConfigurationDoneRequest": true })),
+            "launch" | "attach" => {
+                if args["stopOnEntry"].as_bool() == Some(true) {
+                    self.controller.stop_on_entry();
+                }
+                Ok(json!({}))
+            }
+            "setBreakpoints" => self.set_breakpoints(args),
+            "configurationDone" => {
+                self.start();
+                Ok(json!({}))
+            }
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD, "name": "main" }] })),
+            "stackTrace" => self.stack_trace(),
+            "scopes" => self.scopes(args),
+            "variables" => self.variables(args),
+            "continue" => {
+                self.resume(Step::Continue);
+                Ok(json!({ "allThreadsContinued": true }))
+            }
+            "next" => {
+                self.resume(Step::Over);
+                Ok(json!({}))
+            }
+            "stepIn" => {
+                self.resume(Step::Into);
+                Ok(json!({}))
+            }
+            "stepOut" => {
+                self.resume(Step::Out);
+                Ok(json!({}))
+            }
+            "pause" => {
+                self.controller.pause();
+                Ok(json!({}))
+            }
+            "disconnect" | "terminate" => {
+                // Without breakpoints, evaluation runs to the end.
+                self.controller.clear_breakpoints();
+                self.controller.resume(Step::Continue);
+                self.client.respond(request, Ok(json!({})));
+                return false;
+            }
+            _ => Err(eco_format!("unsupported request `{command}`")),
+        };
+
+        self.client.respond(request, result);
+        if command == "initialize" {
+            self.client.event("initialized", json!({}));
+        }
+
+        true
+    }
+
+    /// Start evaluation on a separate thread.
+    fn start(&mut self) {
+        if self.started {
+            return;
+        }
+
+        self.started = true;
+
+        let world = self.world.clone();
+        let client = self.client.clone();
+        let controller = self.controller.clone();
+        std::thread::spawn(move || {
+            let Warned { output, warnings } =
+                typst::compile_with_debugger(&*world, controller);
+            let errors = output.err().unwrap_or_default();
+
+            for diagnostic in warnings.iter().chain(&errors) {
+                client.event(
+                    "output",
+                    json!({
+                        "category": "stderr",
+                        "output": format_diagnostic(&world, diagnostic),
+                    }),
+                );
+            }
+
+            client.event("exited", json!({ "exitCode": i32::from(!errors.is_empty()) }));
+            client.event("terminated", json!({}));
+        });
+    }
+
+    /// Resume evaluation.
+    fn resume(&mut self, step: Step) {
+        self.references.clear();
+        self.controller.resume(step);
+    }
+
+    /// Replace the breakpoints in a file.
+    ///
+    /// A breakpoint is placed on the first statement that starts on the
+    /// requested line.
+    fn set_breakpoints(&mut self, args: &Json) -> StrResult<Json> {
+        let Some(path) = args["source"]["path"].as_str() else {
+            bail!("missing source path");
+        };
+
+        let Some(id) = file_id(&self.world, Path::new(path)) else {
+            bail!("file is not part of the project");
+        };
+
+        let source = self.world.source(id).map_err(|err| err.to_string())?;
+        let lines = args["breakpoints"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|breakpoint| breakpoint["line"].as_u64());
+
+        let mut spans = vec![];
+        let mut breakpoints = vec![];
+        for line in lines {
+            let span = usize::try_from(line)
+                .ok()
+                .and_then(|line| statement_at_line(&source, line.checked_sub(1)?));
+            breakpoints.push(match span {
+                Some(span) => {
+                    spans.push(span);
+                    json!({ "verified": true, "line": line })
+                }
+                None => json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "no statement starts on this line",
+                }),
+            });
+        }
+
+        self.controller.set_breakpoints(id, spans);
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    /// The calls in progress, innermost first.
+    fn stack_trace(&self) -> StrResult<Json> {
+        let Some(stop) = self.controller.stopped() else {
+            bail!("evaluation is not stopped");
+        };
+
+        let mut stack = stop.stack;
+        if stack.is_empty() {
+            stack.push(Call { name: None, span: stop.span });
+        }
+
+        let frames: Vec<_> = stack
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, call)| {
+                let name = call.name.as_deref().unwrap_or("(anonymous)");
+                let mut frame = json!({ "id": i, "name": name, "line": 0, "column": 0 });
+                if let Some((path, line, column)) = locate(&self.world, call.span) {
+                    frame["source"] = json!({ "path": path });
+                    frame["line"] = json!(line);
+                    frame["column"] = json!(column);
+                }
+                frame
+            })
+            .collect();
+
+        Ok(json!({ "totalFrames": frames.len(), "stackFrames": frames }))
+    }
+
+    /// The scopes of a stack frame. Variables are only available for the
+    /// innermost frame.
+    fn scopes(&mut self, args: &Json) -> StrResult<Json> {
+        let Some(stop) = self.controller.stopped() else {
+            bail!("evaluation is not stopped");
+        };
+
+        let innermost = stop.stack.len().saturating_sub(1) as u64;
+        if args["frameId"].as_u64() != Some(innermost) {
+            return Ok(json!({ "scopes": [] }));
+        }
+
+        let reference = self.reference(stop.variables);
+        Ok(json!({
+            "scopes": [{
+                "name": "Locals",
+                "variablesReference": reference,
+                "expensive": false,
+            }]
+        }))
+    }
+
+    /// The variables behind a reference.
+    fn variables(&mut self, args: &Json) -> StrResult<Json> {
+        let Some(variables) = args["variablesReference"]
+            .as_u64()
+            .and_then(|reference| reference.checked_sub(1))
+            .and_then(|index| self.references.get(index as usize))
+            .cloned()
+        else {
+            bail!("invalid variable reference");
+        };
+
+        let variables: Vec<_> = variables
+            .into_iter()
+            .map(|(name, value)| self.variable(name, value))
+            .collect();
+
+        Ok(json!({ "variables": variables }))
+    }
+
+    /// Describe a variable. Arrays and dictionaries can be expanded.
+    fn variable(&mut self, name: EcoString, value: Value) -> Json {
+        let children: Option<Vec<_>> = match &value {
+            Value::Array(array) if !array.is_empty() => Some(
+                array
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| (eco_format!("{i}"), item.clone()))
+                    .collect(),
+            ),
+            Value::Dict(dict) if !dict.is_empty() => Some(
+                dict.iter()
+                    .map(|(key, value)| (key.as_str().into(), value.clone()))
+                    .collect(),
+            ),
+            _ => None,
+        };
+
+        let reference = children.map_or(0, |children| self.reference(children));
+        json!({
+            "name": name.as_str(),
+            "value": value.repr().as_str(),
+            "type": value.ty().short_name(),
+            "variablesReference": reference,
+        })
+    }
+
+    /// Store variables for later expansion and return their reference.
+    fn reference(&mut self, variables: Vec<(EcoString, Value)>) -> usize {
+        self.references.push(variables);
+        self.references.len()
+    }
+}
+
+/// Writes messages to the client.
+#[derive(Default)]
+struct Client {
+    seq: Mutex<u64>,
+}
+
+impl Client {
+    /// Send an event.
+    fn event(&self, event: &str, body: Json) {
+        self.send(json!({ "type": "event", "event": event, "body": body }));
+    }
+
+    /// Respond to a request.
+    fn respond(&self, request: &Json, result: StrResult<Json>) {
+        let mut message = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": result.is_ok(),
+        });
+
+        match result {
+            Ok(body) => message["body"] = body,
+            Err(err) => message["message"] = json!(err.as_str()),
+        }
+
+        self.send(message);
+    }
+
+    /// Number and write a message.
+    fn send(&self, mut message: Json) {
+        // Holding the lock while writing keeps messages in order.
+        let mut seq = self.seq.lock().unwrap();
+        *seq += 1;
+        message["seq"] = json!(*seq);
+
+        // If the client went away, there is nobody to tell about it.
+        let body = message.to_string();
+        let mut stdout = io::stdout().lock();
+        write!(stdout, "Content-Length: {}\r\n\r\n{body}", body.len())
+            .and_then(|_| stdout.flush())
+            .ok();
+    }
+}
+
+/// Read a message from the client. Returns `None` once the input is closed.
+fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Json>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            if key.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    let Some(length) = length else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "missing content length"));
+    };
+
+    let mut buf = vec![0; length];
+    reader.read_exact(&mut buf)?;
+    serde_json::from_slice(&buf)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// The id of a file in the project.
+fn file_id(world: &SystemWorld, path: &Path) -> Option<FileId> {
+    let path = path.canonicalize().ok()?;
+    let vpath = VirtualPath::within_root(&path, world.root())?;
+    Some(FileId::new(None, vpath))
+}
+
+/// The path, one-based line, and one-based column of a span.
+fn locate(world: &SystemWorld, span: Span) -> Option<(PathBuf, usize, usize)> {
+    let id = span.id()?;
+    let source = world.source(id).ok()?;
+    let range = source.range(span)?;
+    let line = source.byte_to_line(range.start)?;
+    let column = source.byte_to_column(range.start)?;
+    let path = world.path(id).ok()?;
+    Some((path, line + 1, column + 1))
+}
+
+/// The span of the first statement that starts on a zero-based line.
+fn statement_at_line(source: &Source, line: usize) -> Option<Span> {
+    fn find(source: &Source, node: &LinkedNode, line: usize) -> Option<Span> {
+        let start = source.byte_to_line(node.offset())?;
+        if start > line {
+            return None;
+        }
+
+        if start == line && is_statement(node) {
+            return Some(node.span());
+        }
+
+        node.children().find_map(|child| find(source, &child, line))
+    }
+
+    find(source, &LinkedNode::new(source.root()), line)
+}
+
+/// Whether the evaluator notifies the debugger before evaluating the node.
+fn is_statement(node: &LinkedNode) -> bool {
+    let Some(expr) = node.cast::<ast::Expr>() else { return false };
+    match node.parent_kind() {
+        Some(SyntaxKind::Code) => true,
+        Some(SyntaxKind::Markup) => expr.hash(),
+        _ => false,
+    }
+}
+
+/// Format a diagnostic as a single line plus hints.
+fn format_diagnostic(world: &SystemWorld, diagnostic: &SourceDiagnostic) -> String {
+    let severity = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+
+    let mut output = String::new();
+    if let Some((path, line, column)) = locate(world, diagnostic.span) {
+        output.push_str(&format!("{}:{line}:{column}: ", path.display()));
+    }
+
+    output.push_str(&format!("{severity}: {}\n", diagnostic.message));
+    for hint in &diagnostic.hints {
+        output.push_str(&format!("  hint: {hint}\n"));
+    }
+
+    output
+}
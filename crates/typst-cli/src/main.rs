@@ -1,10 +1,13 @@
 mod args;
+mod build;
 mod compile;
+mod debug;
 mod download;
 mod fonts;
 mod init;
 mod package;
 mod query;
+mod server;
 mod terminal;
 mod timings;
 #[cfg(feature = "self-update")]
@@ -52,8 +55,10 @@ fn dispatch() -> HintedStrResult<()> {
     match &ARGS.command {
         Command::Compile(command) => crate::compile::compile(timer, command.clone())?,
         Command::Watch(command) => crate::watch::watch(timer, command.clone())?,
+        Command::Build(command) => crate::build::build(command)?,
         Command::Init(command) => crate::init::init(command)?,
         Command::Query(command) => crate::query::query(command)?,
+        Command::Debug(command) => crate::debug::debug(command)?,
         Command::Fonts(command) => crate::fonts::fonts(command),
         Command::Update(command) => crate::update::update(command)?,
     }
@@ -3,50 +3,106 @@ use ecow::{eco_format, EcoString};
 use serde::Serialize;
 use typst::diag::{bail, HintedStrResult, StrResult, Warned};
 use typst::eval::{eval_string, EvalMode};
-use typst::foundations::{Content, IntoValue, LocatableSelector, Scope};
+use typst::foundations::{
+    dict, Content, Dict, IntoValue, LocatableSelector, Scope, Value,
+};
 use typst::model::Document;
 use typst::syntax::Span;
 use typst::World;
 
 use crate::args::{QueryCommand, SerializationFormat};
 use crate::compile::print_diagnostics;
-use crate::set_failed;
+use crate::watch::{create_world, Watcher};
 use crate::world::SystemWorld;
+use crate::{print_error, set_failed};
 
 /// Execute a query command.
 pub fn query(command: &QueryCommand) -> HintedStrResult<()> {
+    if command.watch {
+        return watch(command);
+    }
+
     let mut world = SystemWorld::new(&command.common)?;
+    if let Some(serialized) = query_once(&mut world, command)? {
+        println!("{serialized}");
+    }
+
+    Ok(())
+}
+
+/// Execute a query command in watch mode, writing one line of JSON to stdout
+/// whenever the results change.
+fn watch(command: &QueryCommand) -> HintedStrResult<()> {
+    if command.format != SerializationFormat::Json {
+        bail!("watch mode only supports JSON output");
+    }
+
+    let mut watcher = Watcher::new(None)?;
+    let mut world = create_world(&mut watcher, &command.common, |err| {
+        print_error(&err.to_string()).unwrap();
+    })?;
 
+    let mut last = None;
+    loop {
+        match query_once(&mut world, command) {
+            Ok(Some(serialized)) => {
+                if last.as_ref() != Some(&serialized) {
+                    println!("{serialized}");
+                    last = Some(serialized);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => {
+                set_failed();
+                print_error(err.message()).unwrap();
+            }
+        }
+
+        // Evict the cache.
+        comemo::evict(10);
+
+        // Adjust the file watching and wait until anything relevant happens.
+        watcher.update(world.dependencies())?;
+        watcher.wait()?;
+    }
+}
+
+/// Compile the document and serialize the query results.
+///
+/// Returns `None` if compilation failed, after printing the diagnostics.
+fn query_once(
+    world: &mut SystemWorld,
+    command: &QueryCommand,
+) -> HintedStrResult<Option<String>> {
     // Reset everything and ensure that the main file is present.
     world.reset();
     world.source(world.main()).map_err(|err| err.to_string())?;
 
-    let Warned { output, warnings } = typst::compile(&world);
+    let Warned { output, warnings } = typst::compile(world);
 
     match output {
-        // Retrieve and print query results.
+        // Retrieve and serialize query results.
         Ok(document) => {
-            let data = retrieve(&world, command, &document)?;
-            let serialized = format(data, command)?;
-            println!("{serialized}");
-            print_diagnostics(&world, &[], &warnings, command.common.diagnostic_format)
+            let data = retrieve(world, command, &document)?;
+            let serialized = format(world, &document, data, command)?;
+            print_diagnostics(world, &[], &warnings, command.common.diagnostic_format)
                 .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
+            Ok(Some(serialized))
         }
 
         // Print diagnostics.
         Err(errors) => {
             set_failed();
             print_diagnostics(
-                &world,
+                world,
                 &errors,
                 &warnings,
                 command.common.diagnostic_format,
             )
             .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
+            Ok(None)
         }
     }
-
-    Ok(())
 }
 
 /// Retrieve the matches for the selector.
@@ -80,16 +136,28 @@ fn retrieve(
 }
 
 /// Format the query result in the output format.
-fn format(elements: Vec<Content>, command: &QueryCommand) -> StrResult<String> {
+fn format(
+    world: &dyn World,
+    document: &Document,
+    elements: Vec<Content>,
+    command: &QueryCommand,
+) -> StrResult<String> {
     if command.one && elements.len() != 1 {
         bail!("expected exactly one element, found {}", elements.len());
     }
 
     let mapped: Vec<_> = elements
         .into_iter()
-        .filter_map(|c| match &command.field {
-            Some(field) => c.get_by_name(field).ok(),
-            _ => Some(c.into_value()),
+        .filter_map(|c| {
+            let value = match &command.field {
+                Some(field) => c.get_by_name(field).ok()?,
+                _ => c.clone().into_value(),
+            };
+            Some(if command.locations {
+                locate(world, document, &c, value)
+            } else {
+                value
+            })
         })
         .collect();
 
@@ -103,6 +171,57 @@ fn format(elements: Vec<Content>, command: &QueryCommand) -> StrResult<String> {
     }
 }
 
+/// Wrap a query result into a dictionary that also describes where the
+/// element ended up in the document and where it was created in the source.
+fn locate(
+    world: &dyn World,
+    document: &Document,
+    element: &Content,
+    value: Value,
+) -> Value {
+    let introspector = &document.introspector;
+    let (page, position, numbering) = match element.location() {
+        Some(location) => {
+            let position = introspector.position(location);
+            (
+                Some(position.page.get()),
+                Some(dict! {
+                    "x" => position.point.x.to_pt(),
+                    "y" => position.point.y.to_pt(),
+                }),
+                introspector.page_numbering(location).cloned(),
+            )
+        }
+        None => (None, None, None),
+    };
+
+    Value::Dict(dict! {
+        "value" => value,
+        "page" => page,
+        "position" => position,
+        "page-numbering" => numbering,
+        "source" => source_lines(world, element.span()),
+    })
+}
+
+/// Determine the file and the one-based, inclusive line range of a span.
+fn source_lines(world: &dyn World, span: Span) -> Option<Dict> {
+    let id = span.id()?;
+    let source = world.source(id).ok()?;
+    let range = source.range(span)?;
+    let vpath = id.vpath();
+    let file = match id.package() {
+        Some(package) => eco_format!("{package}{}", vpath.as_rooted_path().display()),
+        None => eco_format!("{}", vpath.as_rootless_path().display()),
+    };
+
+    Some(dict! {
+        "file" => file,
+        "start-line" => source.byte_to_line(range.start)? + 1,
+        "end-line" => source.byte_to_line(range.end)? + 1,
+    })
+}
+
 /// Serialize data to the output format.
 fn serialize(
     data: &impl Serialize,
@@ -0,0 +1,633 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use codespan_reporting::files::Files;
+use ecow::{eco_format, EcoString};
+use parking_lot::Mutex;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::Serialize;
+use typst::diag::{FileError, FileResult, SourceDiagnostic, StrResult};
+use typst::foundations::{Bytes, Datetime};
+use typst::layout::{Abs, Page, Point};
+use typst::model::Document;
+use typst::syntax::{FileId, Source};
+use typst::text::{Font, FontBook};
+use typst::utils::LazyHash;
+use typst::{Library, World};
+use typst_ide::Jump;
+
+use crate::compile::ExportCache;
+use crate::world::SystemWorld;
+
+/// The port the preview server listens on if none is specified.
+const DEFAULT_PORT: u16 = 3000;
+
+/// The page served to the browser. It subscribes to the event stream and
+/// renders the pages it receives.
+const INDEX_HTML: &str = include_str!("preview.html");
+
+/// Serves a live preview of a document on localhost.
+///
+/// Pages are rendered to SVG and pushed to connected browsers through a stream
+/// of server-sent events. Only pages whose frames changed since the last
+/// compilation are rendered and sent again.
+pub struct PreviewServer {
+    /// The address the server listens on.
+    addr: SocketAddr,
+    /// State shared with the connection handlers.
+    shared: Arc<Shared>,
+}
+
+impl PreviewServer {
+    /// Start serving on the given port, or the default one.
+    ///
+    /// The world's library and fonts are kept to resolve clicks into the
+    /// preview. They don't change while watching.
+    pub fn new(port: Option<u16>, world: &SystemWorld) -> StrResult<Self> {
+        let port = port.unwrap_or(DEFAULT_PORT);
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).map_err(|err| {
+            eco_format!("failed to start preview server on port {port} ({err})")
+        })?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| eco_format!("failed to start preview server ({err})"))?;
+
+        let shared = Arc::new(Shared {
+            library: world.library().clone(),
+            book: world.book().clone(),
+            cache: ExportCache::new(),
+            state: Mutex::new(State::new()),
+        });
+
+        let handle = Arc::clone(&shared);
+        thread::spawn(move || {
+            for stream in listener.incoming().filter_map(Result::ok) {
+                let shared = Arc::clone(&handle);
+                thread::spawn(move || shared.handle(stream).ok());
+            }
+        });
+
+        Ok(Self { addr, shared })
+    }
+
+    /// The URL under which the preview is reachable.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Render the changed pages of a new document and push them to all
+    /// connected browsers.
+    ///
+    /// The sources the document was compiled from are kept, so that clicks
+    /// into it can be resolved while the next compilation is running.
+    pub fn update(&self, world: &SystemWorld, document: Document) {
+        let count = document.pages.len();
+        let cache = &self.shared.cache;
+        cache.truncate(count);
+
+        let changed: Vec<(usize, &Page)> = document
+            .pages
+            .iter()
+            .enumerate()
+            .filter(|(i, page)| !cache.is_cached(*i, &page.frame))
+            .collect();
+
+        let rendered: Vec<PageData> = changed
+            .par_iter()
+            .map(|&(i, page)| PageData::render(i, page))
+            .collect();
+
+        let sources = world
+            .sources()
+            .into_iter()
+            .filter_map(|source| {
+                Some((source.id(), (world.name(source.id()).ok()?, source)))
+            })
+            .collect();
+
+        let snapshot = Snapshot {
+            document: Arc::new(document),
+            main: world.main(),
+            sources,
+        };
+
+        let mut state = self.shared.state.lock();
+        state.pages.truncate(count);
+        for data in &rendered {
+            match state.pages.get_mut(data.index) {
+                Some(slot) => *slot = data.clone(),
+                None => state.pages.push(data.clone()),
+            }
+        }
+
+        state.error = None;
+        state.snapshot = Some(Arc::new(snapshot));
+        state.broadcast(&Event::Update { count, pages: &rendered });
+    }
+
+    /// Notify all connected browsers that the compilation failed.
+    ///
+    /// The last successfully compiled pages stay visible.
+    pub fn fail(&self, errors: &[SourceDiagnostic]) {
+        let message = match errors.first() {
+            Some(error) => error.message.clone(),
+            None => "compilation failed".into(),
+        };
+
+        let mut state = self.shared.state.lock();
+        state.broadcast(&Event::Error { message: &message });
+        state.error = Some(message);
+    }
+}
+
+/// State shared between the server and its connection handlers.
+struct Shared {
+    /// The standard library of the watched compilation.
+    library: LazyHash<Library>,
+    /// The fonts of the watched compilation.
+    book: LazyHash<FontBook>,
+    /// The hashes of the rendered pages' frames.
+    cache: ExportCache,
+    /// The results of the last compilation and the connected browsers.
+    state: Mutex<State>,
+}
+
+impl Shared {
+    /// Respond to a single request.
+    fn handle(&self, mut stream: TcpStream) -> io::Result<()> {
+        let request = Request::read(&stream)?;
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/") => {
+                respond(&mut stream, "200 OK", "text/html; charset=utf-8", INDEX_HTML)
+            }
+            ("GET", "/events") => self.subscribe(stream),
+            ("POST", "/jump") => {
+                let body = serde_json::to_string(&self.jump(&request))
+                    .map_err(io::Error::other)?;
+                respond(&mut stream, "200 OK", "application/json", &body)
+            }
+            _ => respond(&mut stream, "404 Not Found", "text/plain", "not found"),
+        }
+    }
+
+    /// Open an event stream, send the current state through it, and then keep
+    /// forwarding broadcast events until the browser goes away.
+    ///
+    /// Writing happens on the connection's own thread, so a stalled browser
+    /// never holds up the compilation or other browsers.
+    fn subscribe(&self, mut stream: TcpStream) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/event-stream\r\n\
+             Cache-Control: no-cache\r\n\
+             Connection: keep-alive\r\n\r\n"
+        )?;
+        stream.flush()?;
+
+        let (sender, receiver) = mpsc::channel();
+        {
+            let mut state = self.state.lock();
+            let count = state.pages.len();
+            sender
+                .send(Event::Update { count, pages: &state.pages }.encode())
+                .ok();
+            if let Some(message) = &state.error {
+                sender.send(Event::Error { message }.encode()).ok();
+            }
+            state.clients.push(sender);
+        }
+
+        for message in receiver {
+            stream.write_all(message.as_bytes())?;
+            stream.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Determine where a click into a page leads.
+    fn jump(&self, request: &Request) -> Option<Target> {
+        let index: usize = request.query.get("page")?.parse().ok()?;
+        let x: f64 = request.query.get("x")?.parse().ok()?;
+        let y: f64 = request.query.get("y")?.parse().ok()?;
+
+        let snapshot = self.state.lock().snapshot.clone()?;
+        let frame = &snapshot.document.pages.get(index)?.frame;
+        let click = Point::new(Abs::pt(x), Abs::pt(y));
+
+        let world = SnapshotWorld { shared: self, snapshot: &snapshot };
+        Some(
+            match typst_ide::jump_from_click(&world, &snapshot.document, frame, click)? {
+                Jump::Source(id, offset) => {
+                    let (name, source) = snapshot.sources.get(&id)?;
+                    Target::Source {
+                        file: name.clone(),
+                        line: source.byte_to_line(offset)? + 1,
+                        column: source.byte_to_column(offset)? + 1,
+                    }
+                }
+                Jump::Url(url) => Target::Url { url: url.as_str().into() },
+                Jump::Position(position) => Target::Position {
+                    page: position.page.get() - 1,
+                    x: position.point.x.to_pt(),
+                    y: position.point.y.to_pt(),
+                },
+            },
+        )
+    }
+}
+
+/// The results of the last compilation and the connected browsers.
+struct State {
+    /// The last successfully compiled document and its sources.
+    snapshot: Option<Arc<Snapshot>>,
+    /// The rendered pages of that document.
+    pages: Vec<PageData>,
+    /// The message of the last compilation's first error, if it failed.
+    error: Option<EcoString>,
+    /// The event streams of all connected browsers.
+    clients: Vec<Sender<Arc<str>>>,
+}
+
+impl State {
+    /// Create a new, empty state.
+    fn new() -> Self {
+        Self {
+            snapshot: None,
+            pages: vec![],
+            error: None,
+            clients: vec![],
+        }
+    }
+
+    /// Send an event to all connected browsers, dropping those that went away.
+    ///
+    /// This only queues the event, the browsers' connection threads do the
+    /// actual writing.
+    fn broadcast(&mut self, event: &Event) {
+        let message = event.encode();
+        self.clients
+            .retain(|sender| sender.send(Arc::clone(&message)).is_ok());
+    }
+}
+
+/// A compiled document together with the sources it was compiled from.
+struct Snapshot {
+    /// The document.
+    document: Arc<Document>,
+    /// The main source file.
+    main: FileId,
+    /// The sources and their display names.
+    sources: HashMap<FileId, (String, Source)>,
+}
+
+/// A world that serves the sources of a snapshot, which is all that is needed
+/// to resolve clicks into its document.
+struct SnapshotWorld<'a> {
+    shared: &'a Shared,
+    snapshot: &'a Snapshot,
+}
+
+impl World for SnapshotWorld<'_> {
+    fn library(&self) -> &LazyHash<Library> {
+        &self.shared.library
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        &self.shared.book
+    }
+
+    fn main(&self) -> FileId {
+        self.snapshot.main
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        match self.snapshot.sources.get(&id) {
+            Some((_, source)) => Ok(source.clone()),
+            None => Err(FileError::NotFound(id.vpath().as_rootless_path().into())),
+        }
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+    }
+
+    fn font(&self, _: usize) -> Option<Font> {
+        None
+    }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        None
+    }
+}
+
+/// A page rendered to SVG.
+#[derive(Clone, Serialize)]
+struct PageData {
+    /// The zero-based index of the page.
+    index: usize,
+    /// The width of the page in points.
+    width: f64,
+    /// The height of the page in points.
+    height: f64,
+    /// The SVG image of the page.
+    svg: EcoString,
+}
+
+impl PageData {
+    /// Render a page.
+    fn render(index: usize, page: &Page) -> Self {
+        Self {
+            index,
+            width: page.frame.width().to_pt(),
+            height: page.frame.height().to_pt(),
+            svg: typst_svg::svg(page).into(),
+        }
+    }
+}
+
+/// An event pushed to connected browsers.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum Event<'a> {
+    /// The document now has `count` pages, of which the listed ones changed.
+    Update { count: usize, pages: &'a [PageData] },
+    /// The compilation failed.
+    Error { message: &'a str },
+}
+
+impl Event<'_> {
+    /// Encode the event as a message of an event stream.
+    fn encode(&self) -> Arc<str> {
+        // Serializing cannot fail as the event contains no maps.
+        let json = serde_json::to_string(self).unwrap();
+        format!("data: {json}\n\n").into()
+    }
+}
+
+/// Where a click into the preview leads.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum Target {
+    /// A location in a source file, with one-based line and column.
+    Source { file: String, line: usize, column: usize },
+    /// An external URL.
+    Url { url: EcoString },
+    /// A point on a zero-based page, in points.
+    Position { page: usize, x: f64, y: f64 },
+}
+
+/// The parts of an HTTP request we care about.
+struct Request {
+    /// The request method.
+    method: String,
+    /// The requested path, without the query.
+    path: String,
+    /// The query parameters.
+    query: HashMap<String, String>,
+}
+
+impl Request {
+    /// Read the request line and headers from a stream.
+    fn read(stream: &TcpStream) -> io::Result<Self> {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+
+        let mut parts = line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or_default();
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let query = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (decode(key, true), decode(value, true)))
+            .collect();
+
+        // Skip the headers, we don't need any of them.
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+        }
+
+        Ok(Self { method, path: decode(path, false), query })
+    }
+}
+
+/// Decode the percent-encoded characters in a part of a URL. In the query,
+/// a plus also stands for a space.
+fn decode(text: &str, query: bool) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        match byte {
+            b'%' if rest.len() >= 2 && rest[..2].iter().all(u8::is_ascii_hexdigit) => {
+                let hex = std::str::from_utf8(&rest[..2]).unwrap();
+                bytes.push(u8::from_str_radix(hex, 16).unwrap());
+                rest = &rest[2..];
+            }
+            b'+' if query => bytes.push(b' '),
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Write a complete response and close the connection.
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    mime: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {mime}\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n\
+         {body}",
+        body.len(),
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Read;
+    use std::path::PathBuf;
+
+    use clap::Parser;
+    use typst_kit::download::Downloader;
+    use typst_kit::package::PackageStorage;
+
+    use super::*;
+    use crate::args::{CliArguments, Command};
+    use crate::world::SharedFonts;
+
+    /// A preview server for a document in a temporary directory.
+    struct Test {
+        dir: PathBuf,
+        world: SystemWorld,
+        server: PreviewServer,
+    }
+
+    impl Test {
+        /// Write the document's source and start a server for it.
+        fn new(name: &str, text: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("typst-preview-{}-{name}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let input = dir.join("main.typ");
+            fs::write(&input, text).unwrap();
+
+            let args = ["typst", "compile", "--ignore-system-fonts"];
+            let args = CliArguments::parse_from(
+                args.into_iter().map(Into::into).chain([input.into_os_string()]),
+            );
+            let Command::Compile(command) = args.command else { unreachable!() };
+
+            // Build the world without the global downloader, which would read
+            // the process's own arguments.
+            let command = &command.common;
+            let fonts = SharedFonts::search(&command.font_args);
+            let package_storage =
+                Arc::new(PackageStorage::new(None, None, Downloader::new("typst-test")));
+            let world =
+                SystemWorld::with_resources(command, fonts, package_storage).unwrap();
+            let server = PreviewServer::new(Some(0), &world).unwrap();
+            Self { dir, world, server }
+        }
+
+        /// Compile the document and push it to the server.
+        fn compile(&self) {
+            let document = typst::compile(&self.world).output.unwrap();
+            self.server.update(&self.world, document);
+        }
+
+        /// Send a request and return the connection.
+        fn request(&self, method: &str, target: &str) -> TcpStream {
+            let mut stream = TcpStream::connect(self.server.addr).unwrap();
+            write!(stream, "{method} {target} HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                .unwrap();
+            stream
+        }
+
+        /// Send a request and return the response's status line and body.
+        fn fetch(&self, method: &str, target: &str) -> (String, String) {
+            let mut response = String::new();
+            self.request(method, target).read_to_string(&mut response).unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            (head.lines().next().unwrap().into(), body.into())
+        }
+    }
+
+    impl Drop for Test {
+        fn drop(&mut self) {
+            fs::remove_dir_all(&self.dir).ok();
+        }
+    }
+
+    /// Read the next event from an event stream.
+    fn next_event(reader: &mut BufReader<TcpStream>) -> serde_json::Value {
+        loop {
+            let mut line = String::new();
+            assert_ne!(reader.read_line(&mut line).unwrap(), 0);
+            if let Some(json) = line.strip_prefix("data: ") {
+                return serde_json::from_str(json).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_preview_index() {
+        let test = Test::new("index", "Hello");
+        let (status, body) = test.fetch("GET", "/");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(body, INDEX_HTML);
+
+        let (status, _) = test.fetch("GET", "/index.html");
+        assert_eq!(status, "HTTP/1.1 404 Not Found");
+    }
+
+    #[test]
+    fn test_preview_events() {
+        let test = Test::new("events", "#pagebreak()");
+        test.compile();
+
+        // A new browser first receives all pages.
+        let mut reader = BufReader::new(test.request("GET", "/%65vents"));
+        let event = next_event(&mut reader);
+        assert_eq!(event["kind"], "update");
+        assert_eq!(event["count"], 2);
+        assert_eq!(event["pages"].as_array().unwrap().len(), 2);
+
+        // An unchanged document doesn't send any pages again.
+        test.compile();
+        let event = next_event(&mut reader);
+        assert_eq!(event["kind"], "update");
+        assert_eq!(event["count"], 2);
+        assert_eq!(event["pages"].as_array().unwrap().len(), 0);
+
+        test.server.fail(&[]);
+        let event = next_event(&mut reader);
+        assert_eq!(event["kind"], "error");
+        assert_eq!(event["message"], "compilation failed");
+    }
+
+    #[test]
+    fn test_preview_stalled_browser() {
+        let test = Test::new("stalled", "#rect(width: 100%, height: 100%)");
+
+        // A browser that never reads must not block updates.
+        let _stalled = test.request("GET", "/events");
+        for _ in 0..50 {
+            test.compile();
+            test.server.fail(&[]);
+        }
+
+        let (status, _) = test.fetch("GET", "/");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+    }
+
+    #[test]
+    fn test_preview_jump() {
+        let test = Test::new(
+            "jump",
+            "#set page(width: 20pt, height: 20pt, margin: 0pt)\n\
+             #rect(width: 100%, height: 100%)",
+        );
+
+        // Nothing to jump to before the first compilation.
+        let (_, body) = test.fetch("POST", "/jump?page=0&x=10&y=10");
+        assert_eq!(body, "null");
+
+        test.compile();
+        let (status, body) = test.fetch("POST", "/jump?page=0&x=1%30&y=10");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let target: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(target["kind"], "source");
+        assert_eq!(target["line"], 2);
+        assert_eq!(target["column"], 2);
+
+        let (_, body) = test.fetch("POST", "/jump?page=1&x=10&y=10");
+        assert_eq!(body, "null");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("/a%20b+c", false), "/a b+c");
+        assert_eq!(decode("a%20b+c", true), "a b c");
+        assert_eq!(decode("%e2%86%92%", true), "→%");
+        assert_eq!(decode("%zz%4", true), "%zz%4");
+    }
+}
@@ -1,12 +1,16 @@
+use std::cmp::Reverse;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use typst::diag::{bail, StrResult};
 use typst::syntax::Span;
 use typst::World;
+use typst_timing::{ProfileStats, Profiled};
 
 use crate::args::{CliArguments, Command};
+use crate::terminal;
 use crate::world::SystemWorld;
 
 /// Allows to record timings of function executions.
@@ -15,16 +19,22 @@ pub struct Timer {
     path: Option<PathBuf>,
     /// The current watch iteration.
     index: usize,
+    /// Whether to profile Typst code and where to save the flame graph.
+    profile: Option<Option<PathBuf>>,
 }
 
 impl Timer {
     /// Initializes the timing system and returns a timer that can be used to
     /// record timings for a specific function invocation.
     pub fn new(args: &CliArguments) -> Timer {
-        let record = match &args.command {
-            Command::Compile(command) => command.timings.clone(),
-            Command::Watch(command) => command.timings.clone(),
-            _ => None,
+        let (record, profile) = match &args.command {
+            Command::Compile(command) => {
+                (command.timings.clone(), command.profile.clone())
+            }
+            Command::Watch(command) => {
+                (command.compile.timings.clone(), command.compile.profile.clone())
+            }
+            _ => (None, None),
         };
 
         // Enable event collection.
@@ -32,17 +42,39 @@ impl Timer {
             typst_timing::enable();
         }
 
+        if profile.is_some() {
+            typst_timing::enable_profiling();
+        }
+
         let path =
             record.map(|path| path.unwrap_or_else(|| PathBuf::from("record-{n}.json")));
 
-        Timer { path, index: 0 }
+        Timer { path, index: 0, profile }
     }
 
-    /// Records all timings in `f` and writes them to disk.
+    /// Records all timings in `f` and writes them to disk. If profiling is
+    /// enabled, also reports the profile.
     pub fn record<T>(
         &mut self,
         world: &mut SystemWorld,
         f: impl FnOnce(&mut SystemWorld) -> T,
+    ) -> StrResult<T> {
+        let Some(profile) = &self.profile else {
+            return self.record_timings(world, f);
+        };
+
+        let profile = profile.clone();
+        typst_timing::clear_profile();
+        let output = self.record_timings(world, f)?;
+        report_profile(world, profile.as_deref())?;
+        Ok(output)
+    }
+
+    /// Records all timings in `f` and writes them to disk.
+    fn record_timings<T>(
+        &mut self,
+        world: &mut SystemWorld,
+        f: impl FnOnce(&mut SystemWorld) -> T,
     ) -> StrResult<T> {
         let Some(path) = &self.path else {
             return Ok(f(world));
@@ -87,3 +119,99 @@ fn resolve_span(world: &SystemWorld, span: Span) -> Option<(String, u32)> {
     let line = source.byte_to_line(range.start)?;
     Some((format!("{id:?}"), line as u32 + 1))
 }
+
+/// The number of items listed in the profile summary.
+const SUMMARY_LEN: usize = 20;
+
+/// Print the items that took the most time and optionally write a flame graph
+/// in the folded format.
+fn report_profile(world: &SystemWorld, path: Option<&Path>) -> StrResult<()> {
+    let mut summary = typst_timing::profile_summary();
+    summary.sort_by_key(|(_, stats)| Reverse(stats.own));
+
+    print_summary(world, &summary)
+        .map_err(|e| format!("failed to print profile: {e}"))?;
+
+    let Some(path) = path else { return Ok(()) };
+
+    let mut lines: Vec<_> = typst_timing::profile_stacks()
+        .into_iter()
+        .map(|(stack, time)| {
+            let frames: Vec<_> = stack
+                .iter()
+                .map(|item| label(world, item).replace(';', ","))
+                .collect();
+            format!("{} {}", frames.join(";"), time.as_micros())
+        })
+        .collect();
+    lines.sort();
+
+    let file = File::create(path).map_err(|e| format!("failed to create file: {e}"))?;
+    let mut writer = BufWriter::new(file);
+    lines
+        .iter()
+        .try_for_each(|line| writeln!(writer, "{line}"))
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("failed to write profile: {e}"))?;
+
+    Ok(())
+}
+
+/// Print the items that took the most time in their own code.
+fn print_summary(
+    world: &SystemWorld,
+    summary: &[(Profiled, ProfileStats)],
+) -> std::io::Result<()> {
+    let mut out = terminal::out();
+    writeln!(out, "{:>12} {:>12} {:>8}  item", "total", "self", "calls")?;
+    for (item, stats) in summary.iter().take(SUMMARY_LEN) {
+        writeln!(
+            out,
+            "{:>12} {:>12} {:>8}  {}",
+            format_duration(stats.total),
+            format_duration(stats.own),
+            stats.calls,
+            label(world, item),
+        )?;
+    }
+
+    if summary.len() > SUMMARY_LEN {
+        writeln!(out, "... and {} more", summary.len() - SUMMARY_LEN)?;
+    }
+
+    Ok(())
+}
+
+/// Describes a profiled item with its kind, name, and location.
+fn label(world: &SystemWorld, item: &Profiled) -> String {
+    let mut label = String::from(item.kind);
+    if let Some(name) = &item.name {
+        label.push(' ');
+        label.push_str(name);
+    }
+
+    let location = match resolve_location(world, item.span) {
+        Some((path, line)) => format!("{path}:{line}"),
+        None => "unknown".into(),
+    };
+
+    format!("{label} ({location})")
+}
+
+/// Turns a span into a (rootless path, line) pair.
+fn resolve_location(world: &SystemWorld, span: Span) -> Option<(String, usize)> {
+    let id = span.id()?;
+    let source = world.source(id).ok()?;
+    let line = source.byte_to_line(source.range(span)?.start)?;
+    let path = id.vpath().as_rootless_path().display();
+    let path = match id.package() {
+        Some(spec) => format!("{spec}/{path}"),
+        None => path.to_string(),
+    };
+    Some((path, line + 1))
+}
+
+/// Formats a duration in milliseconds.
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
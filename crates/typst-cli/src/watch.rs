@@ -13,41 +13,36 @@ use same_file::is_same_file;
 use typst::diag::{bail, StrResult};
 use typst::utils::format_duration;
 
-use crate::args::{CompileCommand, Input, Output};
+use crate::args::{CompileCommand, Input, Output, SharedArgs, WatchCommand};
 use crate::compile::compile_once;
+use crate::server::PreviewServer;
 use crate::timings::Timer;
 use crate::world::{SystemWorld, WorldCreationError};
 use crate::{print_error, terminal};
 
 /// Execute a watching compilation command.
-pub fn watch(mut timer: Timer, mut command: CompileCommand) -> StrResult<()> {
+pub fn watch(mut timer: Timer, command: WatchCommand) -> StrResult<()> {
+    let WatchCommand { compile: mut command, preview } = command;
     let Output::Path(output) = command.output() else {
         bail!("cannot write document to stdout in watch mode");
     };
 
     // Create a file system watcher.
-    let mut watcher = Watcher::new(output)?;
+    let mut watcher = Watcher::new(Some(output))?;
 
     // Create the world that serves sources, files, and fonts.
-    // Additionally, if any files do not exist, wait until they do.
-    let mut world = loop {
-        match SystemWorld::new(&command.common) {
-            Ok(world) => break world,
-            Err(
-                ref err @ (WorldCreationError::InputNotFound(ref path)
-                | WorldCreationError::RootNotFound(ref path)),
-            ) => {
-                watcher.update([path.clone()])?;
-                Status::Error.print(&command).unwrap();
-                print_error(&err.to_string()).unwrap();
-                watcher.wait()?;
-            }
-            Err(err) => return Err(err.into()),
-        }
-    };
+    let mut world = create_world(&mut watcher, &command.common, |err| {
+        Status::Error.print(&command, None).unwrap();
+        print_error(&err.to_string()).unwrap();
+    })?;
+
+    // Start the preview server, if requested.
+    let server = preview.map(|port| PreviewServer::new(port, &world)).transpose()?;
 
     // Perform initial compilation.
-    timer.record(&mut world, |world| compile_once(world, &mut command, true))??;
+    timer.record(&mut world, |world| {
+        compile_once(world, &mut command, true, server.as_ref())
+    })??;
 
     // Watch all dependencies of the initial compilation.
     watcher.update(world.dependencies())?;
@@ -61,7 +56,9 @@ pub fn watch(mut timer: Timer, mut command: CompileCommand) -> StrResult<()> {
         world.reset();
 
         // Recompile.
-        timer.record(&mut world, |world| compile_once(world, &mut command, true))??;
+        timer.record(&mut world, |world| {
+            compile_once(world, &mut command, true, server.as_ref())
+        })??;
 
         // Evict the cache.
         comemo::evict(10);
@@ -71,10 +68,35 @@ pub fn watch(mut timer: Timer, mut command: CompileCommand) -> StrResult<()> {
     }
 }
 
+/// Create the world that serves sources, files, and fonts.
+///
+/// If the input file or the root directory do not exist, the error is reported
+/// and we wait until they do.
+pub fn create_world(
+    watcher: &mut Watcher,
+    args: &SharedArgs,
+    mut report: impl FnMut(&WorldCreationError),
+) -> StrResult<SystemWorld> {
+    loop {
+        match SystemWorld::new(args) {
+            Ok(world) => return Ok(world),
+            Err(
+                ref err @ (WorldCreationError::InputNotFound(ref path)
+                | WorldCreationError::RootNotFound(ref path)),
+            ) => {
+                watcher.update([path.clone()])?;
+                report(err);
+                watcher.wait()?;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
 /// Watches file system activity.
-struct Watcher {
-    /// The output file. We ignore any events for it.
-    output: PathBuf,
+pub struct Watcher {
+    /// The output file, if any. We ignore any events for it.
+    output: Option<PathBuf>,
     /// The underlying watcher.
     watcher: RecommendedWatcher,
     /// Notify event receiver.
@@ -101,7 +123,7 @@ impl Watcher {
     const POLL_INTERVAL: Duration = Duration::from_millis(300);
 
     /// Create a new, blank watcher.
-    fn new(output: PathBuf) -> StrResult<Self> {
+    pub fn new(output: Option<PathBuf>) -> StrResult<Self> {
         // Setup file watching.
         let (tx, rx) = std::sync::mpsc::channel();
 
@@ -127,7 +149,7 @@ impl Watcher {
     ///
     /// Files that are not yet watched will be watched. Files that are already
     /// watched, but don't need to be watched anymore, will be unwatched.
-    fn update(&mut self, iter: impl IntoIterator<Item = PathBuf>) -> StrResult<()> {
+    pub fn update(&mut self, iter: impl IntoIterator<Item = PathBuf>) -> StrResult<()> {
         // Mark all files as not "seen" so that we may unwatch them if they
         // aren't in the dependency list.
         for seen in self.watched.values_mut() {
@@ -171,7 +193,7 @@ impl Watcher {
     }
 
     /// Wait until there is a change to a watched path.
-    fn wait(&mut self) -> StrResult<()> {
+    pub fn wait(&mut self) -> StrResult<()> {
         loop {
             // Wait for an initial event. If there are missing files, we need to
             // poll those regularly to check whether they are created, so we
@@ -232,12 +254,14 @@ impl Watcher {
     /// Whether a watch event is relevant for compilation.
     fn is_event_relevant(&self, event: &notify::Event) -> bool {
         // Never recompile because the output file changed.
-        if event
-            .paths
-            .iter()
-            .all(|path| is_same_file(path, &self.output).unwrap_or(false))
-        {
-            return false;
+        if let Some(output) = &self.output {
+            if event
+                .paths
+                .iter()
+                .all(|path| is_same_file(path, output).unwrap_or(false))
+            {
+                return false;
+            }
         }
 
         match &event.kind {
@@ -267,7 +291,11 @@ pub enum Status {
 
 impl Status {
     /// Clear the terminal and render the status message.
-    pub fn print(&self, command: &CompileCommand) -> io::Result<()> {
+    pub fn print(
+        &self,
+        command: &CompileCommand,
+        preview: Option<&PreviewServer>,
+    ) -> io::Result<()> {
         let output = command.output();
         let timestamp = chrono::offset::Local::now().format("%H:%M:%S");
         let color = self.color();
@@ -288,6 +316,13 @@ impl Status {
         out.reset()?;
         writeln!(out, " {output}")?;
 
+        if let Some(preview) = preview {
+            out.set_color(&color)?;
+            write!(out, "serving at")?;
+            out.reset()?;
+            writeln!(out, " {}", preview.url())?;
+        }
+
         writeln!(out)?;
         writeln!(out, "[{timestamp}] {}", self.message())?;
         writeln!(out)?;
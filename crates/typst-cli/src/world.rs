@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use std::{fmt, fs, io, mem};
 
 use chrono::{DateTime, Datelike, FixedOffset, Local, Utc};
@@ -18,7 +18,7 @@ use typst_kit::fonts::{FontSlot, Fonts};
 use typst_kit::package::PackageStorage;
 use typst_timing::{timed, TimingScope};
 
-use crate::args::{Input, SharedArgs};
+use crate::args::{FontArgs, Input, SharedArgs};
 use crate::compile::ExportCache;
 use crate::download::PrintDownload;
 use crate::package;
@@ -38,14 +38,12 @@ pub struct SystemWorld {
     main: FileId,
     /// Typst's standard library.
     library: LazyHash<Library>,
-    /// Metadata about discovered fonts.
-    book: LazyHash<FontBook>,
-    /// Locations of and storage for lazily loaded fonts.
-    fonts: Vec<FontSlot>,
+    /// The discovered fonts.
+    fonts: SharedFonts,
     /// Maps file ids to source files and buffers.
     slots: Mutex<HashMap<FileId, FileSlot>>,
     /// Holds information about where packages are stored.
-    package_storage: PackageStorage,
+    package_storage: Arc<PackageStorage>,
     /// The current datetime if requested. This is stored here to ensure it is
     /// always the same within one compilation.
     /// Reset between compilations if not [`Now::Fixed`].
@@ -67,6 +65,21 @@ impl SystemWorld {
                 .ok();
         }
 
+        let fonts = SharedFonts::search(&command.font_args);
+        let package_storage = Arc::new(package::storage(&command.package_storage_args));
+        Self::with_resources(command, fonts, package_storage)
+    }
+
+    /// Create a new system world that uses already discovered fonts and an
+    /// existing package storage, which may be shared with other worlds.
+    ///
+    /// The font arguments and package storage arguments in `command` are
+    /// ignored.
+    pub fn with_resources(
+        command: &SharedArgs,
+        fonts: SharedFonts,
+        package_storage: Arc<PackageStorage>,
+    ) -> Result<Self, WorldCreationError> {
         // Resolve the system-global input path.
         let input = match &command.input {
             Input::Stdin => None,
@@ -116,10 +129,6 @@ impl SystemWorld {
             Library::builder().with_inputs(inputs).build()
         };
 
-        let fonts = Fonts::searcher()
-            .include_system_fonts(!command.font_args.ignore_system_fonts)
-            .search_with(&command.font_args.font_paths);
-
         let now = match command.creation_timestamp {
             Some(time) => Now::Fixed(time),
             None => Now::System(OnceLock::new()),
@@ -130,10 +139,9 @@ impl SystemWorld {
             root,
             main,
             library: LazyHash::new(library),
-            book: LazyHash::new(fonts.book),
-            fonts: fonts.fonts,
+            fonts,
             slots: Mutex::new(HashMap::new()),
-            package_storage: package::storage(&command.package_storage_args),
+            package_storage,
             now,
             export_cache: ExportCache::new(),
         })
@@ -165,6 +173,16 @@ impl SystemWorld {
             })
     }
 
+    /// Return all source files the last compilation depended on.
+    pub fn sources(&self) -> Vec<Source> {
+        self.slots
+            .lock()
+            .values()
+            .filter(|slot| slot.source.accessed())
+            .filter_map(|slot| slot.source.data.clone()?.ok())
+            .collect()
+    }
+
     /// Reset the compilation state in preparation of a new compilation.
     pub fn reset(&mut self) {
         for slot in self.slots.get_mut().values_mut() {
@@ -181,6 +199,11 @@ impl SystemWorld {
         self.source(id).expect("file id does not point to any source file")
     }
 
+    /// The path of a file on the system.
+    pub fn path(&self, id: FileId) -> FileResult<PathBuf> {
+        system_path(&self.root, id, &self.package_storage)
+    }
+
     /// Gets access to the export cache.
     pub fn export_cache(&self) -> &ExportCache {
         &self.export_cache
@@ -193,7 +216,7 @@ impl World for SystemWorld {
     }
 
     fn book(&self) -> &LazyHash<FontBook> {
-        &self.book
+        &self.fonts.book
     }
 
     fn main(&self) -> FileId {
@@ -209,7 +232,7 @@ impl World for SystemWorld {
     }
 
     fn font(&self, index: usize) -> Option<Font> {
-        self.fonts[index].get()
+        self.fonts.slots[index].get()
     }
 
     fn today(&self, offset: Option<i64>) -> Option<Datetime> {
@@ -246,6 +269,29 @@ impl SystemWorld {
     }
 }
 
+/// Discovered fonts, which can be shared between multiple worlds.
+#[derive(Clone)]
+pub struct SharedFonts {
+    /// Metadata about the fonts.
+    book: Arc<LazyHash<FontBook>>,
+    /// Locations of and storage for lazily loaded fonts.
+    slots: Arc<Vec<FontSlot>>,
+}
+
+impl SharedFonts {
+    /// Search for fonts as configured by the font arguments.
+    pub fn search(args: &FontArgs) -> Self {
+        let fonts = Fonts::searcher()
+            .include_system_fonts(!args.ignore_system_fonts)
+            .search_with(&args.font_paths);
+
+        Self {
+            book: Arc::new(LazyHash::new(fonts.book)),
+            slots: Arc::new(fonts.fonts),
+        }
+    }
+}
+
 /// Holds the processed data for a file ID.
 ///
 /// Both fields can be populated if the file is both imported and read().
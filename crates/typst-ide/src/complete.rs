@@ -5,8 +5,8 @@ use ecow::{eco_format, EcoString};
 use if_chain::if_chain;
 use serde::{Deserialize, Serialize};
 use typst::foundations::{
-    fields_on, format_str, repr, AutoValue, CastInfo, Func, Label, NoneValue, Repr,
-    Scope, StyleChain, Styles, Type, Value,
+    fields_on, format_str, repr, AutoValue, CastInfo, DeclaredType, Func, Label,
+    NoneValue, Repr, Scope, StyleChain, Styles, Type, Value,
 };
 use typst::model::Document;
 use typst::syntax::{
@@ -650,7 +650,7 @@ fn show_rule_recipe_completions(ctx: &mut CompletionContext) {
 /// Complete call and set rule parameters.
 fn complete_params(ctx: &mut CompletionContext) -> bool {
     // Ensure that we are in a function call or set rule's argument list.
-    let (callee, set, args) = if_chain! {
+    let (callee, set, args, call) = if_chain! {
         if let Some(parent) = ctx.leaf.parent();
         if let Some(parent) = match parent.kind() {
             SyntaxKind::Named => parent.parent(),
@@ -666,7 +666,7 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
             _ => None,
         };
         then {
-            (callee, set, args)
+            (callee, set, args, grand.clone())
         } else {
             return false;
         }
@@ -692,7 +692,7 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
                 ctx.from = ctx.cursor.min(next.offset());
             }
 
-            named_param_value_completions(ctx, callee, &param);
+            named_param_value_completions(ctx, callee, &call, &param);
             return true;
         }
     }
@@ -706,7 +706,7 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
                 ctx.from = ctx.cursor.min(next.offset());
             }
 
-            param_completions(ctx, callee, set, args);
+            param_completions(ctx, callee, &call, set, args);
             return true;
         }
     }
@@ -718,12 +718,10 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
 fn param_completions<'a>(
     ctx: &mut CompletionContext<'a>,
     callee: ast::Expr<'a>,
+    call: &LinkedNode,
     set: bool,
     args: ast::Args<'a>,
 ) {
-    let Some(func) = resolve_global_callee(ctx, callee) else { return };
-    let Some(params) = func.params() else { return };
-
     // Exclude named arguments which are already present.
     let exclude: Vec<_> = args
         .items()
@@ -733,6 +731,14 @@ fn param_completions<'a>(
         })
         .collect();
 
+    let Some(func) = resolve_global_callee(ctx, callee) else {
+        if let Some(func) = resolve_closure(ctx, call) {
+            closure_param_completions(ctx, &func, &exclude);
+        }
+        return;
+    };
+
+    let Some(params) = func.params() else { return };
     for param in params {
         if exclude.iter().any(|ident| ident.as_str() == param.name) {
             continue;
@@ -761,13 +767,67 @@ fn param_completions<'a>(
     }
 }
 
+/// Add completions for the parameters of a user-defined closure, based on
+/// their type annotations.
+fn closure_param_completions(
+    ctx: &mut CompletionContext,
+    func: &Func,
+    exclude: &[ast::Ident],
+) {
+    for (param, types) in closure_params(func) {
+        match param {
+            ast::Param::Named(named) => {
+                let name = named.name();
+                if exclude.iter().any(|ident| ident.as_str() == name.as_str()) {
+                    continue;
+                }
+
+                ctx.completions.push(Completion {
+                    kind: CompletionKind::Param,
+                    label: name.get().clone(),
+                    apply: Some(eco_format!("{}: ${{}}", name.as_str())),
+                    detail: (!types.is_empty()).then(|| {
+                        let names: Vec<_> =
+                            types.iter().map(|ty| eco_format!("{ty}")).collect();
+                        eco_format!(
+                            "A value of type {}.",
+                            repr::separated_list(&names, "or")
+                        )
+                    }),
+                });
+            }
+            _ => ctx.cast_completions(&declared_cast(types)),
+        }
+    }
+
+    if ctx.before.ends_with(',') {
+        ctx.enrich(" ", "");
+    }
+}
+
 /// Add completions for the values of a named function parameter.
 fn named_param_value_completions<'a>(
     ctx: &mut CompletionContext<'a>,
     callee: ast::Expr<'a>,
+    call: &LinkedNode,
     name: &str,
 ) {
-    let Some(func) = resolve_global_callee(ctx, callee) else { return };
+    let Some(func) = resolve_global_callee(ctx, callee) else {
+        // Complete the values of an annotated closure parameter.
+        if let Some(func) = resolve_closure(ctx, call) {
+            if let Some((_, types)) = closure_params(&func).find(|(param, _)| {
+                matches!(param, ast::Param::Named(named) if named.name().as_str() == name)
+            }) {
+                ctx.cast_completions(&declared_cast(types));
+            }
+
+            if ctx.before.ends_with(':') {
+                ctx.enrich(" ", "");
+            }
+        }
+        return;
+    };
+
     let Some(param) = func.param(name) else { return };
     if !param.named {
         return;
@@ -807,6 +867,50 @@ fn resolve_global_callee<'a>(
     }
 }
 
+/// Resolve the callee of a function call to a user-defined closure.
+fn resolve_closure(ctx: &CompletionContext, call: &LinkedNode) -> Option<Func> {
+    if call.kind() != SyntaxKind::FuncCall {
+        return None;
+    }
+
+    let callee = call.children().next()?;
+    analyze_expr(ctx.world, &callee)
+        .into_iter()
+        .find_map(|(value, _)| match value {
+            Value::Func(func) if func.closure().is_some() => Some(func),
+            _ => None,
+        })
+}
+
+/// The parameters of a closure other than the argument sink, together with
+/// their declared types.
+fn closure_params(
+    func: &Func,
+) -> impl Iterator<Item = (ast::Param<'_>, &[DeclaredType])> {
+    let closure = func.closure();
+    let params = closure
+        .and_then(|closure| closure.node.cast::<ast::Closure>())
+        .map(|node| node.params().children())
+        .into_iter()
+        .flatten()
+        .filter(|param| !matches!(param, ast::Param::Spread(_)));
+    let types = closure.into_iter().flat_map(|closure| &closure.param_types);
+    params.zip(types.map(Vec::as_slice))
+}
+
+/// Turn declared types into cast information for completions.
+fn declared_cast(types: &[DeclaredType]) -> CastInfo {
+    CastInfo::Union(
+        types
+            .iter()
+            .filter_map(|ty| match ty {
+                DeclaredType::Native(ty) => Some(CastInfo::Type(*ty)),
+                DeclaredType::Record(_) => None,
+            })
+            .collect(),
+    )
+}
+
 /// Complete in code mode.
 fn complete_code(ctx: &mut CompletionContext) -> bool {
     if matches!(
@@ -1224,7 +1328,7 @@ impl<'a> CompletionContext<'a> {
     }
 
     /// Add completions for a castable.
-    fn cast_completions(&mut self, cast: &'a CastInfo) {
+    fn cast_completions(&mut self, cast: &CastInfo) {
         // Prevent duplicate completions from appearing.
         if !self.seen_casts.insert(typst::utils::hash128(cast)) {
             return;
@@ -1398,6 +1502,12 @@ mod tests {
         test(s, s.len() - 2, &[], &[]);
     }
 
+    #[test]
+    fn test_autocomplete_closure_params() {
+        let s = "#let f(x as int, size as length | auto: auto) = none\n#f()";
+        test(s, s.len() - 1, &["size", "integer"], &["x"]);
+    }
+
     #[test]
     fn test_autocomplete_mutable_method() {
         let s = "#{ let x = (1, 2, 3); x. }";
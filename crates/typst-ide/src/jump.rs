@@ -263,6 +263,16 @@ mod tests {
         test_cursor(s, 14, pos(1, 37.55, 16.58));
     }
 
+    #[test]
+    fn test_jump_from_cursor_recovered() {
+        let s = "#foo() *Hello* #box[ABC]";
+        let world = TestWorld::new(s);
+        let recovered = typst::compile_recovering(&world).output.unwrap();
+        assert_eq!(recovered.errors.len(), 1);
+        assert_eq!(recovered.errors[0].message, "unknown variable: foo");
+        assert!(!jump_from_cursor(&recovered.output, &world.main, 21).is_empty());
+    }
+
     #[test]
     fn test_backlink() {
         let s = "#footnote[Hi]";
@@ -7,6 +7,7 @@ use typst::eval::CapturesVisitor;
 use typst::foundations::{repr, Capturer, CastInfo, Repr, Value};
 use typst::layout::Length;
 use typst::model::Document;
+use typst::syntax::ast::AstNode;
 use typst::syntax::{ast, LinkedNode, Side, Source, SyntaxKind};
 use typst::utils::{round_with_precision, Numeric};
 use typst::World;
@@ -33,6 +34,7 @@ pub fn tooltip(
     named_param_tooltip(world, &leaf)
         .or_else(|| font_tooltip(world, &leaf))
         .or_else(|| document.and_then(|doc| label_tooltip(doc, &leaf)))
+        .or_else(|| signature_tooltip(world, &leaf))
         .or_else(|| expr_tooltip(world, &leaf))
         .or_else(|| closure_tooltip(&leaf))
 }
@@ -106,6 +108,33 @@ fn expr_tooltip(world: &dyn World, leaf: &LinkedNode) -> Option<Tooltip> {
     (!tooltip.is_empty()).then(|| Tooltip::Code(tooltip.into()))
 }
 
+/// Tooltip with the signature of a hovered closure with type annotations.
+fn signature_tooltip(world: &dyn World, leaf: &LinkedNode) -> Option<Tooltip> {
+    if leaf.kind() != SyntaxKind::Ident {
+        return None;
+    }
+
+    let values = analyze_expr(world, leaf);
+    let [(Value::Func(func), _)] = values.as_slice() else { return None };
+    let closure = func.closure()?;
+    let node = closure.node.cast::<ast::Closure>()?;
+    if closure.param_types.iter().all(Vec::is_empty) && closure.return_types.is_empty() {
+        return None;
+    }
+
+    let mut signature = EcoString::new();
+    if let Some(name) = node.name() {
+        signature.push_str(name.as_str());
+    }
+    signature.push_str(&node.params().to_untyped().clone().into_text());
+    if let Some(returns) = node.returns() {
+        signature.push(' ');
+        signature.push_str(&returns.to_untyped().clone().into_text());
+    }
+
+    Some(Tooltip::Code(signature))
+}
+
 /// Tooltip for a hovered closure.
 fn closure_tooltip(leaf: &LinkedNode) -> Option<Tooltip> {
     // Only show this tooltip when hovering over the equals sign or arrow of
@@ -280,6 +309,18 @@ mod tests {
         test("#let f(x) = x + y", 11, Side::Before, text("This closure captures `y`."));
     }
 
+    #[test]
+    fn test_tooltip_signature() {
+        let s = "#let f(x as int, y as length | auto: auto) -> content = []\n#f(1)";
+        test(
+            s,
+            s.len() - 4,
+            Side::After,
+            code("f(x as int, y as length | auto: auto) -> content"),
+        );
+        test("#let g(x) = x\n#g(1)", 15, Side::After, code("g"));
+    }
+
     #[test]
     fn test_empty_contextual() {
         test("#{context}", 10, Side::Before, code("context()"));
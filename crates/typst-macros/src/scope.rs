@@ -138,11 +138,15 @@ fn rewrite_primitive_base(item: &syn::ItemImpl, ident_ext: &syn::Ident) -> Token
                 items.push(func.clone());
 
                 let mut sig = func.sig;
-                let inputs = sig.inputs.iter().cloned().map(|mut input| {
+                let inputs = sig.inputs.iter().cloned().filter_map(|mut input| {
                     if let syn::FnArg::Typed(typed) = &mut input {
+                        if typed.attrs.iter().any(|attr| attr.path().is_ident("external"))
+                        {
+                            return None;
+                        }
                         typed.attrs.clear();
                     }
-                    input
+                    Some(input)
                 });
                 sig.inputs = parse_quote! { #(#inputs),* };
 
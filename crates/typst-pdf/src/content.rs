@@ -9,7 +9,7 @@ use pdf_writer::types::{
     ColorSpaceOperand, LineCapStyle, LineJoinStyle, TextRenderingMode,
 };
 use pdf_writer::writers::PositionedItems;
-use pdf_writer::{Content, Finish, Name, Rect, Str};
+use pdf_writer::{Content, Finish, Name, Rect, Str, TextStr};
 use typst::diag::{bail, SourceResult};
 use typst::foundations::Repr;
 use typst::layout::{
@@ -27,7 +27,8 @@ use typst::visualize::{
 
 use crate::color::PaintEncode;
 use crate::color_font::ColorFontMap;
-use crate::extg::ExtGState;
+use crate::extg::{ExtGState, SoftMask};
+use crate::group::{GroupRemapper, PdfGroup};
 use crate::image::deferred_image;
 use crate::resources::Resources;
 use crate::{deflate_deferred, AbsExt, ContentExt, EmExt, PdfOptions, StrExt};
@@ -214,11 +215,11 @@ impl Builder<'_, ()> {
     fn set_external_graphics_state(&mut self, graphics_state: &ExtGState) {
         let current_state = &self.state.external_graphics_state;
         if current_state != graphics_state {
-            let index = self.resources.ext_gs.insert(*graphics_state);
+            let index = self.resources.ext_gs.insert(graphics_state.clone());
             let name = eco_format!("Gs{index}");
             self.content.set_parameters(Name(name.as_bytes()));
 
-            self.state.external_graphics_state = *graphics_state;
+            self.state.external_graphics_state = graphics_state.clone();
             if graphics_state.uses_opacities() {
                 self.uses_opacities = true;
             }
@@ -237,14 +238,15 @@ impl Builder<'_, ()> {
 
         let stroke_opacity = stroke.map_or(255, |stroke| get_opacity(&stroke.paint));
         let fill_opacity = fill.map_or(255, get_opacity);
-        self.set_external_graphics_state(&ExtGState { stroke_opacity, fill_opacity });
+        self.set_external_graphics_state(&ExtGState {
+            stroke_opacity,
+            fill_opacity,
+            ..ExtGState::default()
+        });
     }
 
     fn reset_opacities(&mut self) {
-        self.set_external_graphics_state(&ExtGState {
-            stroke_opacity: 255,
-            fill_opacity: 255,
-        });
+        self.set_external_graphics_state(&ExtGState::default());
     }
 
     pub fn transform(&mut self, transform: Transform) {
@@ -410,12 +412,112 @@ fn write_group(ctx: &mut Builder, pos: Point, group: &GroupItem) -> SourceResult
         ctx.content.end_path();
     }
 
-    write_frame(ctx, &group.frame)?;
+    if let Some(text) = &group.actual_text {
+        let mut span = ctx.content.begin_marked_content_with_properties(Name(b"Span"));
+        let mut properties = span.properties();
+        properties.pair(Name(b"ActualText"), TextStr(text));
+        properties.finish();
+        span.finish();
+    }
+
+    if group.is_composited() {
+        write_composited_group(ctx, group)?;
+    } else {
+        write_frame(ctx, &group.frame)?;
+    }
+
+    if group.actual_text.is_some() {
+        ctx.content.end_marked_content();
+    }
+
     ctx.restore_state();
 
     Ok(())
 }
 
+/// Encode a group that is composited as a whole into the content stream.
+///
+/// The group's frame is written into a transparency group XObject that is
+/// painted with the group's mask, opacity and blend mode. A blurred frame is
+/// replaced with an approximation of it.
+fn write_composited_group(ctx: &mut Builder, group: &GroupItem) -> SourceResult<()> {
+    let blurred;
+    let frame = if group.blur > Abs::zero() {
+        blurred = crate::group::approximate_blur(&group.frame, group.blur);
+        &blurred
+    } else {
+        &group.frame
+    };
+
+    let Some(pdf_group) = build_group(ctx, frame)? else {
+        // The group is squashed to nothing, so there is nothing to paint.
+        return Ok(());
+    };
+
+    let mut soft_mask = None;
+    if let Some(mask) = &group.mask {
+        let Some(pdf_group) = build_group(ctx, &mask.frame)? else {
+            return Ok(());
+        };
+        soft_mask = Some(SoftMask { mode: mask.mode, group: pdf_group });
+    }
+
+    let groups = ctx
+        .resources
+        .groups
+        .get_or_insert_with(|| Box::new(GroupRemapper::new()));
+    let index = groups.remapper.insert(pdf_group);
+
+    let opacity = (group.opacity.get() * 255.0).round() as u8;
+    ctx.set_external_graphics_state(&ExtGState {
+        stroke_opacity: opacity,
+        fill_opacity: opacity,
+        blend_mode: group.blend,
+        soft_mask,
+    });
+
+    let name = eco_format!("Tg{index}");
+    ctx.content.x_object(Name(name.as_bytes()));
+
+    Ok(())
+}
+
+/// Encode a frame into a separate content stream for a transparency group.
+///
+/// The group's coordinate system is that of the current content stream, so
+/// that gradients, patterns and links within it end up in the same place as
+/// if they were written inline. Returns `None` if the current transform is
+/// not invertible.
+fn build_group(ctx: &mut Builder, frame: &Frame) -> SourceResult<Option<PdfGroup>> {
+    let transform = ctx.state.transform;
+    let Some(matrix) = transform.invert() else {
+        return Ok(None);
+    };
+
+    let options = ctx.options;
+    let container_transform = ctx.state.container_transform;
+    let size = ctx.state.size;
+    let groups = ctx
+        .resources
+        .groups
+        .get_or_insert_with(|| Box::new(GroupRemapper::new()));
+
+    let mut inner = Builder::new(options, &mut groups.resources, size);
+    inner.state.container_transform = container_transform;
+    inner.transform(transform);
+    write_frame(&mut inner, frame)?;
+
+    let links = inner.links;
+    let pdf_group = PdfGroup {
+        content: deflate_deferred(inner.content.finish()).wait().clone(),
+        bbox: crate::group::bbox(frame, transform),
+        matrix,
+    };
+
+    ctx.links.extend(links);
+    Ok(Some(pdf_group))
+}
+
 /// Encode a text run into the content stream.
 fn write_text(ctx: &mut Builder, pos: Point, text: &TextItem) -> SourceResult<()> {
     if ctx.options.standards.pdfa {
@@ -1,28 +1,52 @@
 use std::collections::HashMap;
 
+use pdf_writer::types::{BlendMode as PdfBlendMode, MaskType};
 use pdf_writer::Ref;
 use typst::diag::SourceResult;
+use typst::visualize::{BlendMode, MaskMode};
 
+use crate::group::{write_xobject, PdfGroup};
 use crate::{PdfChunk, WithGlobalRefs};
 
 /// A PDF external graphics state.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct ExtGState {
     // In the range 0-255, needs to be divided before being written into the graphics state!
     pub stroke_opacity: u8,
     // In the range 0-255, needs to be divided before being written into the graphics state!
     pub fill_opacity: u8,
+    /// How painted colors are combined with the backdrop.
+    pub blend_mode: BlendMode,
+    /// A mask that determines how opaque each point of what is painted is.
+    pub soft_mask: Option<SoftMask>,
 }
 
 impl Default for ExtGState {
     fn default() -> Self {
-        Self { stroke_opacity: 255, fill_opacity: 255 }
+        Self {
+            stroke_opacity: 255,
+            fill_opacity: 255,
+            blend_mode: BlendMode::Normal,
+            soft_mask: None,
+        }
     }
 }
 
+/// A soft mask in an external graphics state.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SoftMask {
+    /// Which channel of the mask's contents determines the opacity.
+    pub mode: MaskMode,
+    /// The contents of the mask.
+    pub group: PdfGroup,
+}
+
 impl ExtGState {
     pub fn uses_opacities(&self) -> bool {
-        self.stroke_opacity != 255 || self.fill_opacity != 255
+        self.stroke_opacity != 255
+            || self.fill_opacity != 255
+            || !self.blend_mode.is_normal()
+            || self.soft_mask.is_some()
     }
 }
 
@@ -38,12 +62,38 @@ pub fn write_graphic_states(
                 continue;
             }
 
+            // The mask's contents were written with the resources of the
+            // composited groups.
+            let mut soft_mask = None;
+            if let (Some(mask), Some(groups)) =
+                (&external_gs.soft_mask, &resources.groups)
+            {
+                let mask_id = chunk.alloc();
+                write_xobject(
+                    &mut chunk,
+                    mask_id,
+                    &mask.group,
+                    groups.resources.reference,
+                );
+                soft_mask = Some((mask.mode, mask_id));
+            }
+
             let id = chunk.alloc();
-            out.insert(*external_gs, id);
-            chunk
-                .ext_graphics(id)
+            out.insert(external_gs.clone(), id);
+            let mut ext_graphics = chunk.ext_graphics(id);
+            ext_graphics
                 .non_stroking_alpha(external_gs.fill_opacity as f32 / 255.0)
                 .stroking_alpha(external_gs.stroke_opacity as f32 / 255.0);
+            if !external_gs.blend_mode.is_normal() {
+                ext_graphics.blend_mode(to_pdf_blend_mode(external_gs.blend_mode));
+            }
+            if let Some((mode, mask_id)) = soft_mask {
+                let subtype = match mode {
+                    MaskMode::Luminance => MaskType::Luminosity,
+                    MaskMode::Alpha => MaskType::Alpha,
+                };
+                ext_graphics.soft_mask().subtype(subtype).group(mask_id);
+            }
         }
 
         Ok(())
@@ -51,3 +101,24 @@ pub fn write_graphic_states(
 
     Ok((chunk, out))
 }
+
+fn to_pdf_blend_mode(blend_mode: BlendMode) -> PdfBlendMode {
+    match blend_mode {
+        BlendMode::Normal => PdfBlendMode::Normal,
+        BlendMode::Multiply => PdfBlendMode::Multiply,
+        BlendMode::Screen => PdfBlendMode::Screen,
+        BlendMode::Overlay => PdfBlendMode::Overlay,
+        BlendMode::Darken => PdfBlendMode::Darken,
+        BlendMode::Lighten => PdfBlendMode::Lighten,
+        BlendMode::ColorDodge => PdfBlendMode::ColorDodge,
+        BlendMode::ColorBurn => PdfBlendMode::ColorBurn,
+        BlendMode::HardLight => PdfBlendMode::HardLight,
+        BlendMode::SoftLight => PdfBlendMode::SoftLight,
+        BlendMode::Difference => PdfBlendMode::Difference,
+        BlendMode::Exclusion => PdfBlendMode::Exclusion,
+        BlendMode::Hue => PdfBlendMode::Hue,
+        BlendMode::Saturation => PdfBlendMode::Saturation,
+        BlendMode::Color => PdfBlendMode::Color,
+        BlendMode::Luminosity => PdfBlendMode::Luminosity,
+    }
+}
@@ -0,0 +1,276 @@
+//! Groups that are composited as a whole.
+//!
+//! A group with an opacity or a blend mode can't simply be written inline,
+//! because the opacity and blend mode would then apply to each of its items on
+//! their own. Instead, it is written into a transparency group XObject, which
+//! is then painted with an external graphics state that carries the opacity
+//! and blend mode.
+//!
+//! PDF has no blur filter, so blurred groups are approximated with the same
+//! machinery, see [`approximate_blur`].
+
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use pdf_writer::{Filter, Name, Rect, Ref};
+use typst::diag::SourceResult;
+use typst::layout::{Abs, Frame, FrameItem, GroupMask, Point, Ratio, Transform};
+use typst::syntax::Span;
+use typst::visualize::{BlendMode, Color, Geometry, MaskMode, PathItem, Shape};
+
+use crate::resources::{Remapper, ResourcesRefs};
+use crate::{transform_to_array, AbsExt, PdfChunk, Resources, WithGlobalRefs};
+
+/// Writes the composited groups to the PDF as transparency group XObjects.
+/// This is performed once after writing all pages.
+pub fn write_groups(
+    context: &WithGlobalRefs,
+) -> SourceResult<(PdfChunk, HashMap<PdfGroup, Ref>)> {
+    let mut chunk = PdfChunk::new();
+    let mut out = HashMap::new();
+    context.resources.traverse(&mut |resources| {
+        let Some(groups) = &resources.groups else {
+            return Ok(());
+        };
+
+        for pdf_group in groups.remapper.items() {
+            if out.contains_key(pdf_group) {
+                continue;
+            }
+
+            let id = chunk.alloc();
+            out.insert(pdf_group.clone(), id);
+            write_xobject(&mut chunk, id, pdf_group, groups.resources.reference);
+        }
+
+        Ok(())
+    })?;
+
+    Ok((chunk, out))
+}
+
+/// Writes a group as a transparency group XObject.
+///
+/// The group's resource dictionary is written in a later step and only
+/// referenced here.
+pub fn write_xobject(chunk: &mut PdfChunk, id: Ref, group: &PdfGroup, resources: Ref) {
+    let PdfGroup { content, bbox, matrix } = group;
+    let mut form = chunk.form_xobject(id, content);
+    form.bbox(Rect::new(
+        bbox[0].to_f32(),
+        bbox[1].to_f32(),
+        bbox[2].to_f32(),
+        bbox[3].to_f32(),
+    ));
+    form.matrix(transform_to_array(*matrix));
+    form.group()
+        .transparency()
+        .isolated(true)
+        .knockout(false)
+        .color_space()
+        .srgb();
+    form.pair(Name(b"Resources"), resources);
+    form.filter(Filter::FlateDecode);
+}
+
+/// A group's contents, written in the coordinate system of the content stream
+/// the group is painted in.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PdfGroup {
+    /// The rendered contents.
+    pub content: Vec<u8>,
+    /// The bounding box of the contents, as `[x1, y1, x2, y2]`.
+    pub bbox: [Abs; 4],
+    /// Maps the coordinate system of the contents to the one that is active
+    /// when the group is painted.
+    pub matrix: Transform,
+}
+
+/// Builds a frame that looks like the given frame blurred with a Gaussian blur
+/// of the given radius.
+///
+/// The frame is sampled at a number of offsets that are distributed like a
+/// Gaussian with a standard deviation of half the radius. The opacity of the
+/// result is the average coverage of all samples. It is computed in a
+/// luminance mask, where each sample's coverage is painted in white on an
+/// opaque black background with an opacity of `1 / n` for the `n`-th sample,
+/// so that the samples form a running average. The colors are those of the
+/// samples painted on top of each other, with the unshifted one on top.
+pub fn approximate_blur(frame: &Frame, radius: Abs) -> Frame {
+    let sigma = radius / 2.0;
+    let [x1, y1, x2, y2] = bbox(frame, Transform::identity());
+    let min = Point::new(x1, y1) - Point::splat(3.0 * sigma);
+    let max = Point::new(x2, y2) + Point::splat(3.0 * sigma);
+    let area = max - min;
+
+    // The center sample, followed by an inner ring of six and an outer ring
+    // of twelve samples, which split the Gaussian into parts of equal weight.
+    let mut offsets = vec![Point::zero()];
+    for (count, distance, phase) in [(6, 0.69, 0.0), (12, 1.52, 0.5)] {
+        for i in 0..count {
+            let angle = (i as f64 + phase) / count as f64 * TAU;
+            offsets.push(Point::new(
+                sigma * distance * angle.cos(),
+                sigma * distance * angle.sin(),
+            ));
+        }
+    }
+
+    let rect = |color: Color| {
+        let shape = Geometry::Rect(area.to_size()).filled(color.into());
+        (min, FrameItem::Shape(shape, Span::detached()))
+    };
+
+    let mut coverage = Frame::soft(frame.size());
+    for (i, &offset) in offsets.iter().enumerate() {
+        let mut shifted = Frame::soft(frame.size());
+        shifted.push_frame(offset, frame.clone());
+
+        let mut white = Frame::soft(frame.size());
+        white.push_multiple([rect(Color::WHITE)]);
+        white.mask(GroupMask { frame: shifted, mode: MaskMode::Alpha });
+
+        let mut sample = Frame::soft(frame.size());
+        sample.push_multiple([rect(Color::BLACK)]);
+        sample.push_frame(Point::zero(), white);
+        sample.composite(Ratio::new(1.0 / (i + 1) as f64), BlendMode::Normal);
+        coverage.push_frame(Point::zero(), sample);
+    }
+
+    let mut blurred = Frame::soft(frame.size());
+    for &offset in offsets.iter().rev() {
+        blurred.push_frame(offset, frame.clone());
+    }
+    blurred.mask(GroupMask { frame: coverage, mode: MaskMode::Luminance });
+    blurred
+}
+
+/// Computes the bounding box of everything that is painted by a frame when it
+/// is transformed with `ts`.
+///
+/// The result errs on the large side, since anything outside of it is cut off.
+pub fn bbox(frame: &Frame, ts: Transform) -> [Abs; 4] {
+    let mut min = Point::splat(Abs::inf());
+    let mut max = Point::splat(-Abs::inf());
+    extend(&mut min, &mut max, frame, ts);
+    if min.x > max.x || min.y > max.y {
+        return [Abs::zero(); 4];
+    }
+    [min.x, min.y, max.x, max.y]
+}
+
+/// Grows the bounding box by all items in the frame.
+fn extend(min: &mut Point, max: &mut Point, frame: &Frame, ts: Transform) {
+    let mut rect = |from: Point, to: Point| {
+        for corner in [from, Point::new(to.x, from.y), Point::new(from.x, to.y), to] {
+            let p = corner.transform(ts);
+            min.x.set_min(p.x);
+            min.y.set_min(p.y);
+            max.x.set_max(p.x);
+            max.y.set_max(p.y);
+        }
+    };
+
+    rect(Point::zero(), frame.size().to_point());
+
+    let mut groups = vec![];
+    for &(pos, ref item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => groups.push((pos, group)),
+            FrameItem::Text(text) => {
+                // Glyphs may extend beyond their advance and the font size,
+                // so we leave some room.
+                let size = text.size;
+                rect(
+                    pos - Point::new(size, 2.0 * size),
+                    pos + Point::new(text.width() + size, size),
+                );
+            }
+            FrameItem::Shape(shape, _) => {
+                let (from, to) = shape_bounds(shape);
+                rect(pos + from, pos + to);
+            }
+            FrameItem::Image(_, size, _) => rect(pos, pos + size.to_point()),
+            FrameItem::Link(_, _) | FrameItem::Tag(_) => {}
+        }
+    }
+
+    for (pos, group) in groups {
+        let ts = ts
+            .pre_concat(Transform::translate(pos.x, pos.y))
+            .pre_concat(group.transform);
+        if group.blur > Abs::zero() {
+            // A blur reaches three standard deviations beyond the contents.
+            let [x1, y1, x2, y2] = bbox(&group.frame, Transform::identity());
+            let pad = 1.5 * group.blur;
+            let (from, to) =
+                (Point::new(x1 - pad, y1 - pad), Point::new(x2 + pad, y2 + pad));
+            for corner in [from, Point::new(to.x, from.y), Point::new(from.x, to.y), to] {
+                let p = corner.transform(ts);
+                *min = min.min(p);
+                *max = max.max(p);
+            }
+        } else {
+            extend(min, max, &group.frame, ts);
+        }
+    }
+}
+
+/// The bounds of a shape, including its stroke.
+fn shape_bounds(shape: &Shape) -> (Point, Point) {
+    let (mut from, mut to) = match &shape.geometry {
+        Geometry::Line(end) => (end.min(Point::zero()), end.max(Point::zero())),
+        Geometry::Rect(size) => (Point::zero(), size.to_point()),
+        Geometry::Path(path) => {
+            // The control points of a curve enclose it.
+            let mut from = Point::zero();
+            let mut to = Point::zero();
+            for item in &path.0 {
+                let points: &[Point] = match item {
+                    PathItem::MoveTo(p) | PathItem::LineTo(p) => std::slice::from_ref(p),
+                    PathItem::CubicTo(a, b, c) => &[*a, *b, *c],
+                    PathItem::ClosePath => &[],
+                };
+                for &p in points {
+                    from = from.min(p);
+                    to = to.max(p);
+                }
+            }
+            (from, to)
+        }
+    };
+
+    if let Some(stroke) = &shape.stroke {
+        // Miter joins may reach further out than half of the thickness.
+        let pad = stroke.thickness * stroke.miter_limit.get().max(1.0) / 2.0;
+        from -= Point::splat(pad);
+        to += Point::splat(pad);
+    }
+
+    (from, to)
+}
+
+/// De-duplicate composited groups and the resources they require to be drawn.
+pub struct GroupRemapper<R> {
+    /// Group de-duplicator.
+    pub remapper: Remapper<PdfGroup>,
+    /// PDF resources that are used by these groups.
+    pub resources: Resources<R>,
+}
+
+impl GroupRemapper<()> {
+    pub fn new() -> Self {
+        Self {
+            remapper: Remapper::new("Tg"),
+            resources: Resources::default(),
+        }
+    }
+
+    /// Allocate a reference to the resource dictionary of these groups.
+    pub fn with_refs(self, refs: &ResourcesRefs) -> GroupRemapper<Ref> {
+        GroupRemapper {
+            remapper: self.remapper,
+            resources: self.resources.with_refs(refs),
+        }
+    }
+}
@@ -7,6 +7,7 @@ mod content;
 mod extg;
 mod font;
 mod gradient;
+mod group;
 mod image;
 mod named_destination;
 mod outline;
@@ -36,6 +37,7 @@ use crate::color_font::{write_color_fonts, ColorFontSlice};
 use crate::extg::{write_graphic_states, ExtGState};
 use crate::font::write_fonts;
 use crate::gradient::{write_gradients, PdfGradient};
+use crate::group::{write_groups, PdfGroup};
 use crate::image::write_images;
 use crate::named_destination::{write_named_destinations, NamedDestinations};
 use crate::page::{alloc_page_refs, traverse_pages, write_page_tree, EncodedPage};
@@ -66,6 +68,7 @@ pub fn pdf(document: &Document, options: &PdfOptions) -> SourceResult<Vec<u8>> {
                 images: builder.run(write_images)?,
                 gradients: builder.run(write_gradients)?,
                 patterns: builder.run(write_patterns)?,
+                groups: builder.run(write_groups)?,
                 ext_gs: builder.run(write_graphic_states)?,
             })
         })?
@@ -267,6 +270,8 @@ struct References {
     gradients: HashMap<PdfGradient, Ref>,
     /// The IDs of written patterns.
     patterns: HashMap<PdfPattern, Ref>,
+    /// The IDs of written composited groups.
+    groups: HashMap<PdfGroup, Ref>,
     /// The IDs of written external graphics states.
     ext_gs: HashMap<ExtGState, Ref>,
 }
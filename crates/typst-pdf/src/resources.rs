@@ -22,6 +22,7 @@ use crate::color::ColorSpaces;
 use crate::color_font::ColorFontMap;
 use crate::extg::ExtGState;
 use crate::gradient::PdfGradient;
+use crate::group::GroupRemapper;
 use crate::image::EncodedImage;
 use crate::pattern::PatternRemapper;
 use crate::{PdfChunk, Renumber, WithEverything, WithResources};
@@ -31,16 +32,16 @@ use crate::{PdfChunk, Renumber, WithEverything, WithResources};
 /// This does not allocate references to resources, only track what was used
 /// and deduplicate what can be deduplicated.
 ///
-/// You may notice that this structure is a tree: [`PatternRemapper`] and
-/// [`ColorFontMap`] (that are present in the fields of [`Resources`]),
-/// themselves contain [`Resources`] (that will be called "sub-resources" from
-/// now on). Because color glyphs and patterns are defined using content
-/// streams, just like pages, they can refer to resources too, which are tracked
-/// by the respective sub-resources.
+/// You may notice that this structure is a tree: [`PatternRemapper`],
+/// [`GroupRemapper`] and [`ColorFontMap`] (that are present in the fields of
+/// [`Resources`]), themselves contain [`Resources`] (that will be called
+/// "sub-resources" from now on). Because color glyphs, patterns and composited
+/// groups are defined using content streams, just like pages, they can refer to
+/// resources too, which are tracked by the respective sub-resources.
 ///
 /// Each instance of this structure will become a `/Resources` dictionary in
 /// the final PDF. It is not possible to use a single shared dictionary for all
-/// pages, patterns and color fonts, because if a resource is listed in its own
+/// pages, patterns, groups and color fonts, because if a resource is listed in its own
 /// `/Resources` dictionary, some PDF readers will fail to open the document.
 ///
 /// Because we need to lazily initialize sub-resources (we don't know how deep
@@ -68,6 +69,8 @@ pub struct Resources<R = Ref> {
     pub gradients: Remapper<PdfGradient>,
     /// Deduplicates patterns used across the document.
     pub patterns: Option<Box<PatternRemapper<R>>>,
+    /// Deduplicates groups that are composited as a whole.
+    pub groups: Option<Box<GroupRemapper<R>>>,
     /// Deduplicates external graphics states used across the document.
     pub ext_gs: Remapper<ExtGState>,
     /// Deduplicates color glyphs.
@@ -110,6 +113,10 @@ impl<R: Renumber> Renumber for Resources<R> {
         if let Some(patterns) = &mut self.patterns {
             patterns.resources.renumber(offset);
         }
+
+        if let Some(groups) = &mut self.groups {
+            groups.resources.renumber(offset);
+        }
     }
 }
 
@@ -123,6 +130,7 @@ impl Default for Resources<()> {
             deferred_images: HashMap::new(),
             gradients: Remapper::new("Gr"),
             patterns: None,
+            groups: None,
             ext_gs: Remapper::new("Gs"),
             color_fonts: None,
             languages: BTreeMap::new(),
@@ -148,6 +156,10 @@ impl Resources<()> {
                 .patterns
                 .zip(refs.patterns.as_ref())
                 .map(|(p, r)| Box::new(p.with_refs(r))),
+            groups: self
+                .groups
+                .zip(refs.groups.as_ref())
+                .map(|(g, r)| Box::new(g.with_refs(r))),
             ext_gs: self.ext_gs,
             color_fonts: self
                 .color_fonts
@@ -175,6 +187,9 @@ impl<R> Resources<R> {
         if let Some(patterns) = &self.patterns {
             patterns.resources.traverse(process)?;
         }
+        if let Some(groups) = &self.groups {
+            groups.resources.traverse(process)?;
+        }
         Ok(())
     }
 }
@@ -187,6 +202,7 @@ pub struct ResourcesRefs {
     pub reference: Ref,
     pub color_fonts: Option<Box<ResourcesRefs>>,
     pub patterns: Option<Box<ResourcesRefs>>,
+    pub groups: Option<Box<ResourcesRefs>>,
 }
 
 impl Renumber for ResourcesRefs {
@@ -198,6 +214,9 @@ impl Renumber for ResourcesRefs {
         if let Some(patterns) = &mut self.patterns {
             patterns.renumber(offset);
         }
+        if let Some(groups) = &mut self.groups {
+            groups.renumber(offset);
+        }
     }
 }
 
@@ -218,6 +237,10 @@ pub fn alloc_resources_refs(
                 .patterns
                 .as_ref()
                 .map(|p| Box::new(refs_for(&p.resources, chunk))),
+            groups: resources
+                .groups
+                .as_ref()
+                .map(|g| Box::new(refs_for(&g.resources, chunk))),
         }
     }
 
@@ -231,7 +254,8 @@ pub fn alloc_resources_refs(
 /// to the root node of the page tree because using the resource inheritance
 /// feature breaks PDF merging with Apple Preview.
 ///
-/// Also write resource dictionaries for Type3 fonts and patterns.
+/// Also write resource dictionaries for Type3 fonts, patterns and composited
+/// groups.
 pub fn write_resource_dictionaries(ctx: &WithEverything) -> SourceResult<(PdfChunk, ())> {
     let mut chunk = PdfChunk::new();
     let mut used_color_spaces = ColorSpaces::default();
@@ -258,9 +282,12 @@ pub fn write_resource_dictionaries(ctx: &WithEverything) -> SourceResult<(PdfChu
             to_items: color_font_slices,
         };
 
-        resources
-            .images
-            .write(&ctx.references.images, &mut chunk.indirect(images_ref).dict());
+        let mut x_objects_dict = chunk.indirect(images_ref).dict();
+        resources.images.write(&ctx.references.images, &mut x_objects_dict);
+        if let Some(g) = &resources.groups {
+            g.remapper.write(&ctx.references.groups, &mut x_objects_dict);
+        }
+        x_objects_dict.finish();
 
         let mut patterns_dict = chunk.indirect(patterns_ref).dict();
         resources
@@ -0,0 +1,97 @@
+//! Gaussian blurring of rendered layers.
+
+use tiny_skia as sk;
+
+/// Blur a pixmap in place with a Gaussian blur of the given standard deviation
+/// in pixels.
+///
+/// The Gaussian is approximated by three successive box blurs, which is
+/// visually indistinguishable and runs in time independent of the radius.
+/// Since the pixel data is premultiplied, the color channels can be averaged
+/// just like the alpha channel.
+pub fn gaussian_blur(pixmap: &mut sk::Pixmap, sigma: f32) {
+    if !sigma.is_finite() || sigma <= 0.0 {
+        return;
+    }
+
+    let width = pixmap.width() as usize;
+    let height = pixmap.height() as usize;
+    let data = pixmap.data_mut();
+    let mut scratch = vec![0; data.len()];
+
+    for radius in box_radii(sigma) {
+        if radius == 0 {
+            continue;
+        }
+
+        box_blur(data, &mut scratch, width, height, radius, width * 4, 4);
+        box_blur(&scratch, data, height, width, radius, 4, width * 4);
+    }
+}
+
+/// Compute the radii of three box blurs that together approximate a Gaussian
+/// blur with the given standard deviation.
+fn box_radii(sigma: f32) -> [usize; 3] {
+    const N: f32 = 3.0;
+    let ideal = (12.0 * sigma * sigma / N + 1.0).sqrt();
+    let mut lower = ideal.floor() as usize;
+    if lower % 2 == 0 {
+        lower = lower.saturating_sub(1);
+    }
+    let lower = lower.max(1);
+    let upper = lower + 2;
+
+    let wl = lower as f32;
+    let m = ((12.0 * sigma * sigma - N * wl * wl - 4.0 * N * wl - 3.0 * N)
+        / (-4.0 * wl - 4.0))
+        .round()
+        .clamp(0.0, N) as usize;
+
+    std::array::from_fn(|i| if i < m { lower / 2 } else { upper / 2 })
+}
+
+/// Blur `src` into `dst` along one axis with a box of the given radius.
+///
+/// There are `lines` lines of `len` pixels each. Consecutive pixels within a
+/// line are `step` bytes apart and consecutive lines start `stride` bytes
+/// apart. Pixels outside of the image count as transparent.
+fn box_blur(
+    src: &[u8],
+    dst: &mut [u8],
+    len: usize,
+    lines: usize,
+    radius: usize,
+    stride: usize,
+    step: usize,
+) {
+    let window = (2 * radius + 1) as u32;
+    for line in 0..lines {
+        let start = line * stride;
+        let at = |i: usize| start + i * step;
+
+        let mut sum = [0u32; 4];
+        for i in 0..radius.min(len) {
+            for (c, s) in sum.iter_mut().enumerate() {
+                *s += src[at(i) + c] as u32;
+            }
+        }
+
+        for i in 0..len {
+            if i + radius < len {
+                for (c, s) in sum.iter_mut().enumerate() {
+                    *s += src[at(i + radius) + c] as u32;
+                }
+            }
+
+            for (c, s) in sum.iter().enumerate() {
+                dst[at(i) + c] = ((s + window / 2) / window) as u8;
+            }
+
+            if i >= radius {
+                for (c, s) in sum.iter_mut().enumerate() {
+                    *s -= src[at(i - radius) + c] as u32;
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Rendering of Typst documents into raster images.
 
+mod blur;
 mod image;
 mod paint;
 mod shape;
@@ -10,7 +11,7 @@ use typst::layout::{
     Abs, Axes, Frame, FrameItem, FrameKind, GroupItem, Page, Point, Size, Transform,
 };
 use typst::model::Document;
-use typst::visualize::{Color, Geometry, Paint};
+use typst::visualize::{BlendMode, Color, Geometry, MaskMode, Paint};
 
 /// Export a page into a raster image.
 ///
@@ -173,7 +174,8 @@ fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
     }
 }
 
-/// Render a group frame with optional transform and clipping into the canvas.
+/// Render a group frame with optional transform, clipping, masking, blurring,
+/// and compositing into the canvas.
 fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &GroupItem) {
     let sk_transform = to_sk_transform(&group.transform);
     let state = match group.frame.kind() {
@@ -228,7 +230,53 @@ fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &Group
         }
     }
 
-    render_frame(canvas, state.with_mask(mask), &group.frame);
+    if !group.is_composited() {
+        render_frame(canvas, state.with_mask(mask), &group.frame);
+        return;
+    }
+
+    // Render the group into a separate layer so that it can be composited
+    // onto the canvas as a whole.
+    let Some(mut layer) = sk::Pixmap::new(canvas.width(), canvas.height()) else {
+        return;
+    };
+
+    // A blur must not smear the edges of the clip path, so the clip is only
+    // applied when the blurred layer is composited onto the canvas.
+    let blurred = group.blur > Abs::zero();
+    if blurred {
+        render_frame(&mut layer, state.with_mask(None), &group.frame);
+        let ts = state.transform;
+        let scale = (ts.sx * ts.sy - ts.kx * ts.ky).abs().sqrt();
+        blur::gaussian_blur(&mut layer, group.blur.to_f32() / 2.0 * scale);
+    } else {
+        render_frame(&mut layer, state.with_mask(mask), &group.frame);
+    }
+
+    if let Some(group_mask) = &group.mask {
+        let Some(mut mask_layer) = sk::Pixmap::new(canvas.width(), canvas.height())
+        else {
+            return;
+        };
+
+        render_frame(&mut mask_layer, state, &group_mask.frame);
+
+        let mask_type = match group_mask.mode {
+            MaskMode::Luminance => sk::MaskType::Luminance,
+            MaskMode::Alpha => sk::MaskType::Alpha,
+        };
+
+        layer.apply_mask(&sk::Mask::from_pixmap(mask_layer.as_ref(), mask_type));
+    }
+
+    let paint = sk::PixmapPaint {
+        opacity: group.opacity.get() as f32,
+        blend_mode: to_sk_blend_mode(group.blend),
+        quality: sk::FilterQuality::Nearest,
+    };
+
+    let clip = if blurred { mask } else { None };
+    canvas.draw_pixmap(0, 0, layer.as_ref(), &paint, sk::Transform::identity(), clip);
 }
 
 fn to_sk_transform(transform: &Transform) -> sk::Transform {
@@ -243,6 +291,27 @@ fn to_sk_transform(transform: &Transform) -> sk::Transform {
     )
 }
 
+fn to_sk_blend_mode(blend: BlendMode) -> sk::BlendMode {
+    match blend {
+        BlendMode::Normal => sk::BlendMode::SourceOver,
+        BlendMode::Multiply => sk::BlendMode::Multiply,
+        BlendMode::Screen => sk::BlendMode::Screen,
+        BlendMode::Overlay => sk::BlendMode::Overlay,
+        BlendMode::Darken => sk::BlendMode::Darken,
+        BlendMode::Lighten => sk::BlendMode::Lighten,
+        BlendMode::ColorDodge => sk::BlendMode::ColorDodge,
+        BlendMode::ColorBurn => sk::BlendMode::ColorBurn,
+        BlendMode::HardLight => sk::BlendMode::HardLight,
+        BlendMode::SoftLight => sk::BlendMode::SoftLight,
+        BlendMode::Difference => sk::BlendMode::Difference,
+        BlendMode::Exclusion => sk::BlendMode::Exclusion,
+        BlendMode::Hue => sk::BlendMode::Hue,
+        BlendMode::Saturation => sk::BlendMode::Saturation,
+        BlendMode::Color => sk::BlendMode::Color,
+        BlendMode::Luminosity => sk::BlendMode::Luminosity,
+    }
+}
+
 /// Additional methods for [`Abs`].
 trait AbsExt {
     /// Convert to a number of points as f32.
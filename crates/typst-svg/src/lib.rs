@@ -11,11 +11,12 @@ use std::fmt::{self, Display, Formatter, Write};
 use ecow::EcoString;
 use ttf_parser::OutlineBuilder;
 use typst::layout::{
-    Abs, Frame, FrameItem, FrameKind, GroupItem, Page, Point, Ratio, Size, Transform,
+    Abs, Frame, FrameItem, FrameKind, GroupItem, GroupMask, Page, Point, Ratio, Size,
+    Transform,
 };
 use typst::model::Document;
 use typst::utils::hash128;
-use typst::visualize::{Geometry, Gradient, Pattern};
+use typst::visualize::{BlendMode, Geometry, Gradient, MaskMode, Pattern};
 use xmlwriter::XmlWriter;
 
 use crate::paint::{GradientRef, PatternRef, SVGSubGradient};
@@ -75,6 +76,14 @@ struct SVGRenderer {
     /// attribute of the group. The clip path is in the format of `M x y L x y C
     /// x1 y1 x2 y2 x y Z`.
     clip_paths: Deduplicator<EcoString>,
+    /// Masks are used to make parts of a group transparent. They are
+    /// referenced by the `mask` attribute of the group and hold the frame
+    /// that forms the mask along with the state it is rendered in.
+    masks: Deduplicator<(State, GroupMask)>,
+    /// Blur filters are referenced by the `filter` attribute of a blurred
+    /// group. They hold the blur's standard deviation and the corners of the
+    /// region that the blurred group may cover.
+    filters: Deduplicator<(Abs, Point, Point)>,
     /// Deduplicated gradients with transform matrices. They use a reference
     /// (`href`) to a "source" gradient instead of being defined inline.
     /// This saves a lot of space since gradients are often reused but with
@@ -149,6 +158,8 @@ impl SVGRenderer {
             xml: XmlWriter::new(xmlwriter::Options::default()),
             glyphs: Deduplicator::new('g'),
             clip_paths: Deduplicator::new('c'),
+            masks: Deduplicator::new('m'),
+            filters: Deduplicator::new('b'),
             gradient_refs: Deduplicator::new('g'),
             gradients: Deduplicator::new('f'),
             conic_subgradients: Deduplicator::new('s'),
@@ -250,7 +261,39 @@ impl SVGRenderer {
             self.xml.write_attribute_fmt("clip-path", format_args!("url(#{id})"));
         }
 
-        self.render_frame(state, group.transform, &group.frame);
+        if let Some(mask) = &group.mask {
+            let hash = hash128(&(mask, state.transform, state.size));
+            let id = self.masks.insert_with(hash, || (state, mask.clone()));
+            self.xml.write_attribute_fmt("mask", format_args!("url(#{id})"));
+        }
+
+        if group.opacity != Ratio::one() {
+            self.xml.write_attribute("opacity", &group.opacity.get());
+        }
+
+        if !group.blend.is_normal() {
+            self.xml.write_attribute_fmt(
+                "style",
+                format_args!("mix-blend-mode: {}", css_blend_mode(group.blend)),
+            );
+        }
+
+        if group.blur > Abs::zero() {
+            // The filter region is given in the group's own coordinates, so the
+            // filter must be applied inside of the group's transform.
+            let filter = blur_filter(group);
+            let id = self.filters.insert_with(hash128(&filter), || filter);
+            self.xml.start_element("g");
+            if !group.transform.is_identity() {
+                self.xml.write_attribute("transform", &SvgMatrix(group.transform));
+            }
+            self.xml.write_attribute_fmt("filter", format_args!("url(#{id})"));
+            self.render_frame(state, Transform::identity(), &group.frame);
+            self.xml.end_element();
+        } else {
+            self.render_frame(state, group.transform, &group.frame);
+        }
+
         self.xml.end_element();
     }
 
@@ -258,6 +301,8 @@ impl SVGRenderer {
     fn finalize(mut self) -> String {
         self.write_glyph_defs();
         self.write_clip_path_defs();
+        self.write_mask_defs();
+        self.write_filter_defs();
         self.write_gradients();
         self.write_gradient_refs();
         self.write_subgradients();
@@ -286,6 +331,93 @@ impl SVGRenderer {
 
         self.xml.end_element();
     }
+
+    /// Build the mask definitions.
+    fn write_mask_defs(&mut self) {
+        if self.masks.is_empty() {
+            return;
+        }
+
+        self.xml.start_element("defs");
+        self.xml.write_attribute("id", "mask");
+
+        for (id, (state, mask)) in
+            self.masks.iter().map(|(i, m)| (i, m.clone())).collect::<Vec<_>>()
+        {
+            self.xml.start_element("mask");
+            self.xml.write_attribute("id", &id);
+            self.xml.write_attribute(
+                "mask-type",
+                match mask.mode {
+                    MaskMode::Luminance => "luminance",
+                    MaskMode::Alpha => "alpha",
+                },
+            );
+            self.render_frame(state, Transform::identity(), &mask.frame);
+            self.xml.end_element();
+        }
+
+        self.xml.end_element();
+    }
+
+    /// Build the blur filter definitions.
+    fn write_filter_defs(&mut self) {
+        if self.filters.is_empty() {
+            return;
+        }
+
+        self.xml.start_element("defs");
+        self.xml.write_attribute("id", "filter");
+
+        for (id, &(sigma, min, max)) in self.filters.iter() {
+            let size = max - min;
+            self.xml.start_element("filter");
+            self.xml.write_attribute("id", &id);
+            self.xml.write_attribute("filterUnits", "userSpaceOnUse");
+            self.xml.write_attribute("x", &min.x.to_pt());
+            self.xml.write_attribute("y", &min.y.to_pt());
+            self.xml.write_attribute("width", &size.x.to_pt());
+            self.xml.write_attribute("height", &size.y.to_pt());
+            self.xml.start_element("feGaussianBlur");
+            self.xml.write_attribute("stdDeviation", &sigma.to_pt());
+            self.xml.end_element();
+            self.xml.end_element();
+        }
+
+        self.xml.end_element();
+    }
+}
+
+/// The parameters of the filter for a blurred group.
+///
+/// The filter region covers the group's frame and three standard deviations
+/// around it, beyond which the blur is imperceptible.
+fn blur_filter(group: &GroupItem) -> (Abs, Point, Point) {
+    let sigma = group.blur / 2.0;
+    let pad = Point::splat(3.0 * sigma);
+    (sigma, -pad, group.frame.size().to_point() + pad)
+}
+
+/// The CSS name of a blend mode.
+fn css_blend_mode(blend: BlendMode) -> &'static str {
+    match blend {
+        BlendMode::Normal => "normal",
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Overlay => "overlay",
+        BlendMode::Darken => "darken",
+        BlendMode::Lighten => "lighten",
+        BlendMode::ColorDodge => "color-dodge",
+        BlendMode::ColorBurn => "color-burn",
+        BlendMode::HardLight => "hard-light",
+        BlendMode::SoftLight => "soft-light",
+        BlendMode::Difference => "difference",
+        BlendMode::Exclusion => "exclusion",
+        BlendMode::Hue => "hue",
+        BlendMode::Saturation => "saturation",
+        BlendMode::Color => "color",
+        BlendMode::Luminosity => "luminosity",
+    }
 }
 
 /// Deduplicates its elements. It is used to deduplicate glyphs and clip paths.
@@ -1262,6 +1262,13 @@ impl<'a> Named<'a> {
     pub fn pattern(self) -> Pattern<'a> {
         self.0.cast_last_match().unwrap_or_default()
     }
+
+    /// The type annotation of a named parameter: `as bool`.
+    ///
+    /// This should only be accessed if this `Named` is contained in a `Param`.
+    pub fn annotation(self) -> Option<TypeAnnotation<'a>> {
+        self.0.cast_first_match()
+    }
 }
 
 node! {
@@ -1665,6 +1672,11 @@ impl<'a> Closure<'a> {
         self.0.cast_first_match().unwrap_or_default()
     }
 
+    /// The type annotation of the return value: `-> content`.
+    pub fn returns(self) -> Option<TypeAnnotation<'a>> {
+        self.0.cast_first_match()
+    }
+
     /// The body of the closure.
     pub fn body(self) -> Expr<'a> {
         self.0.cast_last_match().unwrap_or_default()
@@ -1688,15 +1700,29 @@ impl<'a> Params<'a> {
 pub enum Param<'a> {
     /// A positional parameter: `x`.
     Pos(Pattern<'a>),
+    /// A positional parameter with a type annotation: `x as int`.
+    Annotated(Annotated<'a>),
     /// A named parameter with a default value: `draw: false`.
     Named(Named<'a>),
     /// An argument sink: `..args` or `..`.
     Spread(Spread<'a>),
 }
 
+impl<'a> Param<'a> {
+    /// The parameter's type annotation, if any.
+    pub fn annotation(self) -> Option<TypeAnnotation<'a>> {
+        match self {
+            Self::Annotated(v) => Some(v.annotation()),
+            Self::Named(v) => v.annotation(),
+            Self::Pos(_) | Self::Spread(_) => Option::None,
+        }
+    }
+}
+
 impl<'a> AstNode<'a> for Param<'a> {
     fn from_untyped(node: &'a SyntaxNode) -> Option<Self> {
         match node.kind() {
+            SyntaxKind::Annotated => node.cast().map(Self::Annotated),
             SyntaxKind::Named => node.cast().map(Self::Named),
             SyntaxKind::Spread => node.cast().map(Self::Spread),
             _ => node.cast().map(Self::Pos),
@@ -1706,12 +1732,42 @@ impl<'a> AstNode<'a> for Param<'a> {
     fn to_untyped(self) -> &'a SyntaxNode {
         match self {
             Self::Pos(v) => v.to_untyped(),
+            Self::Annotated(v) => v.to_untyped(),
             Self::Named(v) => v.to_untyped(),
             Self::Spread(v) => v.to_untyped(),
         }
     }
 }
 
+node! {
+    /// A positional parameter with a type annotation: `x as int`.
+    Annotated
+}
+
+impl<'a> Annotated<'a> {
+    /// The parameter's pattern: `x`.
+    pub fn pattern(self) -> Pattern<'a> {
+        self.0.cast_first_match().unwrap_or_default()
+    }
+
+    /// The parameter's type annotation: `as int`.
+    pub fn annotation(self) -> TypeAnnotation<'a> {
+        self.0.cast_last_match().unwrap_or_default()
+    }
+}
+
+node! {
+    /// A type annotation: `as int | none` or `-> content`.
+    TypeAnnotation
+}
+
+impl<'a> TypeAnnotation<'a> {
+    /// The alternatives of the annotation: `int` and `none`.
+    pub fn types(self) -> impl DoubleEndedIterator<Item = Expr<'a>> {
+        self.0.children().filter_map(SyntaxNode::cast)
+    }
+}
+
 /// The kind of a pattern.
 #[derive(Debug, Copy, Clone, Hash)]
 pub enum Pattern<'a> {
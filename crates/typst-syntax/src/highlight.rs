@@ -224,6 +224,8 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
         SyntaxKind::SlashEq => Some(Tag::Operator),
         SyntaxKind::Dots => Some(Tag::Operator),
         SyntaxKind::Arrow => Some(Tag::Operator),
+        SyntaxKind::ThinArrow => Some(Tag::Operator),
+        SyntaxKind::Pipe => Some(Tag::Operator),
         SyntaxKind::Root => Some(Tag::MathOperator),
 
         SyntaxKind::Not => Some(Tag::Keyword),
@@ -269,6 +271,8 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
         SyntaxKind::Spread => None,
         SyntaxKind::Closure => None,
         SyntaxKind::Params => None,
+        SyntaxKind::Annotated => None,
+        SyntaxKind::TypeAnnotation => None,
         SyntaxKind::LetBinding => None,
         SyntaxKind::SetRule => None,
         SyntaxKind::ShowRule => None,
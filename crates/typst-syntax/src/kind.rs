@@ -158,6 +158,10 @@ pub enum SyntaxKind {
     Dots,
     /// An arrow between a closure's parameters and body: `=>`.
     Arrow,
+    /// An arrow between a closure's parameters and its return type: `->`.
+    ThinArrow,
+    /// Separates the alternatives of a type annotation: `|`.
+    Pipe,
     /// A root: `√`, `∛` or `∜`.
     Root,
 
@@ -246,6 +250,10 @@ pub enum SyntaxKind {
     Closure,
     /// A closure's parameters: `(x, y)`.
     Params,
+    /// A positional closure parameter with a type annotation: `x as int`.
+    Annotated,
+    /// A type annotation: `as int | none` or `-> content`.
+    TypeAnnotation,
     /// A let binding: `let x = 1`.
     LetBinding,
     /// A set rule: `set text(...)`.
@@ -441,6 +449,8 @@ impl SyntaxKind {
             Self::SlashEq => "divide-assign operator",
             Self::Dots => "dots",
             Self::Arrow => "arrow",
+            Self::ThinArrow => "thin arrow",
+            Self::Pipe => "pipe",
             Self::Root => "root",
             Self::Not => "operator `not`",
             Self::And => "operator `and`",
@@ -484,6 +494,8 @@ impl SyntaxKind {
             Self::Spread => "spread",
             Self::Closure => "closure",
             Self::Params => "closure parameters",
+            Self::Annotated => "annotated parameter",
+            Self::TypeAnnotation => "type annotation",
             Self::LetBinding => "`let` expression",
             Self::SetRule => "`set` expression",
             Self::ShowRule => "`show` expression",
@@ -615,6 +615,7 @@ impl Lexer<'_> {
             '/' if self.s.eat_if('=') => SyntaxKind::SlashEq,
             '.' if self.s.eat_if('.') => SyntaxKind::Dots,
             '=' if self.s.eat_if('>') => SyntaxKind::Arrow,
+            '-' if self.s.eat_if('>') => SyntaxKind::ThinArrow,
 
             '{' => SyntaxKind::LeftBrace,
             '}' => SyntaxKind::RightBrace,
@@ -634,6 +635,7 @@ impl Lexer<'_> {
             '=' => SyntaxKind::Eq,
             '<' => SyntaxKind::Lt,
             '>' => SyntaxKind::Gt,
+            '|' => SyntaxKind::Pipe,
 
             c if is_id_start(c) => self.ident(start),
 
@@ -864,6 +864,9 @@ fn let_binding(p: &mut Parser) {
     if p.eat_if(SyntaxKind::Ident) {
         if p.directly_at(SyntaxKind::LeftParen) {
             params(p);
+            if p.at(SyntaxKind::ThinArrow) {
+                type_annotation(p);
+            }
             closure = true;
         }
     } else {
@@ -1096,8 +1099,8 @@ fn expr_with_paren(p: &mut Parser, atomic: bool) {
         return;
     }
 
-    // If, however, '=>' or '=' follows, we must backtrack and reparse as either
-    // a parameter list or a destructuring. To be able to do that, we created a
+    // If, however, '=>', '->', or '=' follows, we must backtrack and reparse as
+    // either a parameter list or a destructuring. To be able to do that, we created a
     // parser checkpoint before our speculative parse, which we can restore.
     //
     // However, naive backtracking has a fatal flaw: It can lead to exponential
@@ -1113,9 +1116,12 @@ fn expr_with_paren(p: &mut Parser, atomic: bool) {
     // again, we can then just restore this result. In this way, no
     // parenthesized expression is parsed more than twice, leading to a worst
     // case running time of O(2n).
-    if p.at(SyntaxKind::Arrow) {
+    if p.at(SyntaxKind::Arrow) || p.at(SyntaxKind::ThinArrow) {
         p.restore(checkpoint);
         params(p);
+        if p.at(SyntaxKind::ThinArrow) {
+            type_annotation(p);
+        }
         if !p.expect(SyntaxKind::Arrow) {
             return;
         }
@@ -1363,6 +1369,12 @@ fn param<'s>(p: &mut Parser<'s>, seen: &mut HashSet<&'s str>, sink: &mut bool) {
     let was_at_pat = p.at_set(set::PATTERN);
     pattern(p, false, seen, Some("parameter"));
 
+    // Parses a type annotation: `x as int`.
+    let annotated = p.at(SyntaxKind::As);
+    if annotated {
+        type_annotation(p);
+    }
+
     // Parses a named parameter: `thickness: 12pt`.
     if p.eat_if(SyntaxKind::Colon) {
         // Recover from bad parameter name.
@@ -1372,7 +1384,25 @@ fn param<'s>(p: &mut Parser<'s>, seen: &mut HashSet<&'s str>, sink: &mut bool) {
 
         code_expr(p);
         p.wrap(m, SyntaxKind::Named);
+    } else if annotated {
+        p.wrap(m, SyntaxKind::Annotated);
+    }
+}
+
+/// Parses a type annotation of a parameter or return value: `as length | auto`
+/// or `-> content`.
+fn type_annotation(p: &mut Parser) {
+    let m = p.marker();
+    if !p.eat_if(SyntaxKind::As) {
+        p.assert(SyntaxKind::ThinArrow);
+    }
+
+    code_expr_prec(p, true, 0);
+    while p.eat_if(SyntaxKind::Pipe) {
+        code_expr_prec(p, true, 0);
     }
+
+    p.wrap(m, SyntaxKind::TypeAnnotation);
 }
 
 /// Parses a binding or reassignment pattern.
@@ -1901,3 +1931,38 @@ impl IndexMut<Marker> for Parser<'_> {
         &mut self.nodes[m.0]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, parse_code, SyntaxKind, SyntaxNode};
+
+    /// Whether the tree contains a node of the given kind.
+    fn contains(node: &SyntaxNode, kind: SyntaxKind) -> bool {
+        node.kind() == kind || node.children().any(|child| contains(child, kind))
+    }
+
+    #[track_caller]
+    fn test(root: SyntaxNode, erroneous: bool, annotated: bool) {
+        assert_eq!(root.erroneous(), erroneous, "{root:#?}");
+        for kind in [SyntaxKind::ThinArrow, SyntaxKind::Pipe] {
+            assert_eq!(contains(&root, kind), annotated, "{root:#?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_arrow_and_pipe_in_markup() {
+        test(parse("#x->y"), false, false);
+        test(parse("#x -> y | z"), false, false);
+        test(parse("#f(x)->y|z"), false, false);
+        test(parse("$#x->y|z$"), false, false);
+        test(parse("$a -> b | c$"), false, false);
+    }
+
+    #[test]
+    fn test_parse_arrow_and_pipe_in_code() {
+        test(parse_code("x => x - 1 > y"), false, false);
+        test(parse_code("(x, y) => x - -y"), false, false);
+        test(parse_code("x - > y"), true, false);
+        test(parse_code("(x as int | none) -> int => x"), false, true);
+    }
+}
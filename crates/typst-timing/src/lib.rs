@@ -1,5 +1,9 @@
 //! Performance timing for Typst.
 
+mod profile;
+
+pub use self::profile::*;
+
 use std::hash::Hash;
 use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
@@ -0,0 +1,271 @@
+//! Profiling of Typst-level functions, show rules, and context expressions.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use typst_syntax::Span;
+
+/// Whether the profiler is enabled. Defaults to `false`.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The global profile.
+static PROFILE: Mutex<Option<Profile>> = Mutex::new(None);
+
+thread_local! {
+    /// The scopes that are currently open on this thread, innermost last.
+    static STACK: RefCell<Vec<Open>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Aggregated measurements.
+#[derive(Default)]
+struct Profile {
+    /// The distinct profiled items, in order of first appearance.
+    items: Vec<Profiled>,
+    /// The index of each item in `items`.
+    indices: HashMap<Profiled, usize>,
+    /// The statistics of each item, parallel to `items`.
+    stats: Vec<ProfileStats>,
+    /// The self time of each distinct stack of items.
+    stacks: HashMap<Vec<usize>, Duration>,
+}
+
+/// A scope that is currently open.
+struct Open {
+    /// The index of the profiled item.
+    index: usize,
+    /// When the scope was opened.
+    start: Instant,
+    /// The time spent in nested scopes.
+    nested: Duration,
+}
+
+/// Something that can be profiled.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Profiled {
+    /// What kind of thing this is, e.g. `"function"` or `"show rule"`.
+    pub kind: &'static str,
+    /// The thing's name, if it has one.
+    pub name: Option<String>,
+    /// Where the thing is defined.
+    pub span: Span,
+}
+
+/// How much time was spent in a profiled item.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ProfileStats {
+    /// How often the item was entered.
+    pub calls: u64,
+    /// The time spent in the item, including nested items. Recursive entries
+    /// are only counted once.
+    pub total: Duration,
+    /// The time spent in the item itself, excluding nested items.
+    pub own: Duration,
+}
+
+/// Enable the profiler.
+#[inline]
+pub fn enable_profiling() {
+    ENABLED.store(true, Relaxed);
+}
+
+/// Whether the profiler is enabled.
+#[inline]
+pub fn is_profiling() -> bool {
+    ENABLED.load(Relaxed)
+}
+
+/// Clears the profile.
+#[inline]
+pub fn clear_profile() {
+    *PROFILE.lock() = None;
+}
+
+/// The statistics of each profiled item, in order of first appearance.
+pub fn profile_summary() -> Vec<(Profiled, ProfileStats)> {
+    let profile = PROFILE.lock();
+    let Some(profile) = profile.as_ref() else { return vec![] };
+    profile
+        .items
+        .iter()
+        .cloned()
+        .zip(profile.stats.iter().copied())
+        .collect()
+}
+
+/// The self time of each distinct stack of profiled items, outermost first.
+///
+/// This is the input for a flame graph.
+pub fn profile_stacks() -> Vec<(Vec<Profiled>, Duration)> {
+    let profile = PROFILE.lock();
+    let Some(profile) = profile.as_ref() else { return vec![] };
+    profile
+        .stacks
+        .iter()
+        .map(|(stack, &time)| {
+            (stack.iter().map(|&i| profile.items[i].clone()).collect(), time)
+        })
+        .collect()
+}
+
+/// A scope that measures the time spent in a profiled item until it is
+/// dropped.
+pub struct ProfileScope(());
+
+impl ProfileScope {
+    /// Open a new scope if profiling is enabled.
+    pub fn new(kind: &'static str, name: Option<&str>, span: Span) -> Option<Self> {
+        if !is_profiling() {
+            return None;
+        }
+
+        let item = Profiled { kind, name: name.map(Into::into), span };
+        let index = {
+            let mut profile = PROFILE.lock();
+            let profile = profile.get_or_insert_with(Profile::default);
+            match profile.indices.get(&item) {
+                Some(&index) => index,
+                None => {
+                    let index = profile.items.len();
+                    profile.indices.insert(item.clone(), index);
+                    profile.items.push(item);
+                    profile.stats.push(ProfileStats::default());
+                    index
+                }
+            }
+        };
+
+        let open = Open {
+            index,
+            start: Instant::now(),
+            nested: Duration::ZERO,
+        };
+        STACK.with(|stack| stack.borrow_mut().push(open));
+        Some(Self(()))
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let end = Instant::now();
+        STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let Some(open) = stack.pop() else { return };
+            let elapsed = end.saturating_duration_since(open.start);
+            let own = elapsed.saturating_sub(open.nested);
+            if let Some(parent) = stack.last_mut() {
+                parent.nested += elapsed;
+            }
+
+            let mut profile = PROFILE.lock();
+            let Some(profile) = profile.as_mut() else { return };
+            let recursive = stack.iter().any(|outer| outer.index == open.index);
+            let Some(stats) = profile.stats.get_mut(open.index) else { return };
+            stats.calls += 1;
+            stats.own += own;
+            if !recursive {
+                stats.total += elapsed;
+            }
+
+            let path = stack
+                .iter()
+                .map(|outer| outer.index)
+                .chain(std::iter::once(open.index))
+                .collect();
+            *profile.stacks.entry(path).or_default() += own;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use super::*;
+
+    /// The profile is global, so tests that use it must not run in parallel.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    fn scope(name: &str) -> ProfileScope {
+        ProfileScope::new("function", Some(name), Span::detached()).unwrap()
+    }
+
+    fn stats(name: &str) -> ProfileStats {
+        profile_summary()
+            .into_iter()
+            .find(|(item, _)| item.name.as_deref() == Some(name))
+            .map(|(_, stats)| stats)
+            .unwrap()
+    }
+
+    fn stack(names: &[&str]) -> Duration {
+        profile_stacks()
+            .into_iter()
+            .find(|(stack, _)| {
+                stack
+                    .iter()
+                    .map(|item| item.name.as_deref().unwrap())
+                    .eq(names.iter().copied())
+            })
+            .map(|(_, time)| time)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_profile_nesting() {
+        let _guard = LOCK.lock();
+        clear_profile();
+        enable_profiling();
+
+        {
+            let _outer = scope("outer");
+            sleep(Duration::from_millis(2));
+            for _ in 0..2 {
+                let _inner = scope("inner");
+                sleep(Duration::from_millis(2));
+            }
+        }
+
+        let outer = stats("outer");
+        let inner = stats("inner");
+        assert_eq!(profile_summary().len(), 2);
+        assert_eq!(outer.calls, 1);
+        assert_eq!(inner.calls, 2);
+        assert_eq!(inner.own, inner.total);
+        assert_eq!(outer.own + inner.total, outer.total);
+        assert!(outer.own >= Duration::from_millis(2));
+        assert!(inner.own >= Duration::from_millis(4));
+
+        assert_eq!(profile_stacks().len(), 2);
+        assert_eq!(stack(&["outer"]), outer.own);
+        assert_eq!(stack(&["outer", "inner"]), inner.own);
+    }
+
+    #[test]
+    fn test_profile_recursion() {
+        let _guard = LOCK.lock();
+        clear_profile();
+        enable_profiling();
+
+        {
+            let _first = scope("f");
+            sleep(Duration::from_millis(2));
+            let _second = scope("f");
+            sleep(Duration::from_millis(2));
+        }
+
+        // The recursive entry adds to the calls and the own time, but its
+        // time is already part of the outer entry's total.
+        let f = stats("f");
+        assert_eq!(profile_summary().len(), 1);
+        assert_eq!(f.calls, 2);
+        assert_eq!(f.own, f.total);
+        assert!(f.total >= Duration::from_millis(4));
+
+        assert_eq!(profile_stacks().len(), 2);
+        assert_eq!(stack(&["f"]) + stack(&["f", "f"]), f.own);
+        assert!(stack(&["f", "f"]) >= Duration::from_millis(2));
+    }
+}
@@ -147,6 +147,15 @@ pub struct Warned<T> {
     pub warnings: EcoVec<SourceDiagnostic>,
 }
 
+/// An output that was produced despite errors.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Recovered<T> {
+    /// The produced output, with placeholders where errors occurred.
+    pub output: T,
+    /// The errors that were recovered from.
+    pub errors: EcoVec<SourceDiagnostic>,
+}
+
 /// An error or warning in a source file.
 ///
 /// The contained spans will only be detached if any of the input source files
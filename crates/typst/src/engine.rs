@@ -8,8 +8,10 @@ use ecow::EcoVec;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
 use crate::diag::{bail, HintedStrResult, SourceDiagnostic, SourceResult, StrResult};
-use crate::foundations::{Styles, Value};
+use crate::eval::debug::Attached;
+use crate::foundations::{Content, NativeElement, Styles, Value};
 use crate::introspection::Introspector;
+use crate::model::ErrorElem;
 use crate::syntax::{FileId, Span};
 use crate::World;
 
@@ -42,6 +44,20 @@ impl Engine<'_> {
         }
     }
 
+    /// Handles errors by producing an [`ErrorElem`] placeholder in their
+    /// place. The errors are delayed like with [`delay`](Self::delay).
+    ///
+    /// Only used when [recovering](Traced::recovering) from errors.
+    pub fn recover(&mut self, errors: EcoVec<SourceDiagnostic>, span: Span) -> Content {
+        let message = match errors.first() {
+            Some(error) => error.message.clone(),
+            None => "unknown error".into(),
+        };
+
+        self.sink.delay(errors);
+        ErrorElem::new(message).pack().spanned(span)
+    }
+
     /// Runs tasks on the engine in parallel.
     pub fn parallelize<P, I, T, U, F>(&mut self, iter: P, f: F) -> impl Iterator<Item = U>
     where
@@ -83,16 +99,34 @@ impl Engine<'_> {
     }
 }
 
-/// May hold a span that is currently under inspection.
+/// May hold a span that is currently under inspection, decides whether
+/// compilation recovers from errors, and may hold an attached debugger.
 #[derive(Default)]
-pub struct Traced(Option<Span>);
+pub struct Traced {
+    span: Option<Span>,
+    recover: bool,
+    debugger: Option<Attached>,
+}
 
 impl Traced {
     /// Wraps a to-be-traced `Span`.
     ///
     /// Call `Traced::default()` to trace nothing.
     pub fn new(traced: Span) -> Self {
-        Self(Some(traced))
+        Self { span: Some(traced), ..Self::default() }
+    }
+
+    /// Recover from errors in markup expressions and show rules by replacing
+    /// them with a placeholder instead of failing.
+    pub fn with_recovery(mut self) -> Self {
+        self.recover = true;
+        self
+    }
+
+    /// Notify a debugger before evaluating statements.
+    pub fn with_debugger(mut self, debugger: Attached) -> Self {
+        self.debugger = Some(debugger);
+        self
     }
 }
 
@@ -104,12 +138,22 @@ impl Traced {
     /// We hide the span if it isn't in the given file so that only results for
     /// the file with the traced span are invalidated.
     pub fn get(&self, id: FileId) -> Option<Span> {
-        if self.0.and_then(Span::id) == Some(id) {
-            self.0
+        if self.span.and_then(Span::id) == Some(id) {
+            self.span
         } else {
             None
         }
     }
+
+    /// Whether to recover from errors.
+    pub fn recovering(&self) -> bool {
+        self.recover
+    }
+
+    /// The attached debugger, if any.
+    pub fn debugger(&self) -> Option<Attached> {
+        self.debugger.clone()
+    }
 }
 
 /// A push-only sink for delayed errors, warnings, and traced values.
@@ -2,14 +2,15 @@ use comemo::{Tracked, TrackedMut};
 use ecow::{eco_format, EcoString, EcoVec};
 
 use crate::diag::{
-    bail, error, At, HintedStrResult, HintedString, SourceDiagnostic, SourceResult,
-    Trace, Tracepoint,
+    bail, error, warning, At, HintedStrResult, HintedString, SourceDiagnostic,
+    SourceResult, Trace, Tracepoint,
 };
 use crate::engine::{Engine, Sink, Traced};
-use crate::eval::{Access, Eval, FlowEvent, Route, Vm};
+use crate::eval::{debug, Access, Eval, FlowEvent, Route, Vm};
 use crate::foundations::{
     call_method_mut, is_mutating_method, Arg, Args, Bytes, Capturer, Closure, Content,
-    Context, Func, IntoValue, NativeElement, Scope, Scopes, Value,
+    Context, DeclaredType, FromValue, Func, IntoValue, NativeElement, Scope, Scopes,
+    Value,
 };
 use crate::introspection::Introspector;
 use crate::math::LrElem;
@@ -125,14 +126,26 @@ impl Eval for ast::Closure<'_> {
     type Output = Value;
 
     fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
-        // Evaluate default values of named parameters.
+        // Evaluate type annotations and default values of named parameters.
         let mut defaults = Vec::new();
+        let mut param_types = Vec::new();
         for param in self.params().children() {
+            if let ast::Param::Spread(_) = param {
+                continue;
+            }
+
+            let types = eval_annotation(vm, param.annotation())?;
             if let ast::Param::Named(named) = param {
-                defaults.push(named.expr().eval(vm)?);
+                let expr = named.expr();
+                let default = expr.eval(vm)?;
+                defaults.push(DeclaredType::check(&types, default).at(expr.span())?);
             }
+
+            param_types.push(types);
         }
 
+        let return_types = eval_annotation(vm, self.returns())?;
+
         // Collect captured variables.
         let captured = {
             let mut visitor = CapturesVisitor::new(Some(&vm.scopes), Capturer::Function);
@@ -148,14 +161,31 @@ impl Eval for ast::Closure<'_> {
             num_pos_params: self
                 .params()
                 .children()
-                .filter(|p| matches!(p, ast::Param::Pos(_)))
+                .filter(|p| matches!(p, ast::Param::Pos(_) | ast::Param::Annotated(_)))
                 .count(),
+            param_types,
+            return_types,
         };
 
         Ok(Value::Func(Func::from(closure).spanned(self.params().span())))
     }
 }
 
+/// Evaluate a type annotation to the types it allows.
+fn eval_annotation(
+    vm: &mut Vm,
+    annotation: Option<ast::TypeAnnotation>,
+) -> SourceResult<Vec<DeclaredType>> {
+    let Some(annotation) = annotation else { return Ok(vec![]) };
+    annotation
+        .types()
+        .map(|expr| {
+            let value = expr.eval(vm)?;
+            DeclaredType::from_value(value).at(expr.span())
+        })
+        .collect()
+}
+
 /// Call the function in the context with the arguments.
 #[comemo::memoize]
 #[allow(clippy::too_many_arguments)]
@@ -191,6 +221,7 @@ pub(crate) fn call_closure(
 
     // Prepare VM.
     let mut vm = Vm::new(engine, context, scopes, body.span());
+    let _call = debug::enter(&vm, || name.map(|name| name.get().clone()), body.span());
 
     // Provide the closure itself for recursive calls.
     if let Some(name) = name {
@@ -203,20 +234,17 @@ pub(crate) fn call_closure(
     let mut sink = None;
     let mut sink_pos_values = None;
     let mut defaults = closure.defaults.iter();
+    let mut param_types = closure.param_types.iter();
     for p in params.children() {
         match p {
-            ast::Param::Pos(pattern) => match pattern {
-                ast::Pattern::Normal(ast::Expr::Ident(ident)) => {
-                    vm.define(ident, args.expect::<Value>(&ident)?)
-                }
-                pattern => {
-                    crate::eval::destructure(
-                        &mut vm,
-                        pattern,
-                        args.expect::<Value>("pattern parameter")?,
-                    )?;
-                }
-            },
+            ast::Param::Pos(pattern) => {
+                param_types.next();
+                define_pos_param(&mut vm, pattern, &[], &mut args)?;
+            }
+            ast::Param::Annotated(annotated) => {
+                let types = param_types.next().unwrap();
+                define_pos_param(&mut vm, annotated.pattern(), types, &mut args)?;
+            }
             ast::Param::Spread(spread) => {
                 sink = Some(spread.sink_ident());
                 if let Some(sink_size) = sink_size {
@@ -225,9 +253,14 @@ pub(crate) fn call_closure(
             }
             ast::Param::Named(named) => {
                 let name = named.name();
+                let types = param_types.next().unwrap();
                 let default = defaults.next().unwrap();
-                let value =
-                    args.named::<Value>(&name)?.unwrap_or_else(|| default.clone());
+                let value = match args.named::<Spanned<Value>>(&name)? {
+                    Some(Spanned { v, span }) => {
+                        DeclaredType::check(types, v).at(span)?
+                    }
+                    None => default.clone(),
+                };
                 vm.define(name, value);
             }
         }
@@ -249,14 +282,37 @@ pub(crate) fn call_closure(
 
     // Handle control flow.
     let output = body.eval(&mut vm)?;
-    match vm.flow {
-        Some(FlowEvent::Return(_, Some(explicit))) => return Ok(explicit),
-        Some(FlowEvent::Return(_, None)) => {}
+    let (output, span) = match vm.flow {
+        Some(FlowEvent::Return(span, Some(explicit))) => (explicit, span),
+        Some(FlowEvent::Return(_, None)) => (output, body.span()),
         Some(flow) => bail!(flow.forbidden()),
-        None => {}
-    }
+        None => (output, body.span()),
+    };
 
-    Ok(output)
+    DeclaredType::check(&closure.return_types, output).at(span)
+}
+
+/// Bind the argument for a positional parameter, checking it against the
+/// parameter's declared types.
+fn define_pos_param(
+    vm: &mut Vm,
+    pattern: ast::Pattern,
+    types: &[DeclaredType],
+    args: &mut Args,
+) -> SourceResult<()> {
+    match pattern {
+        ast::Pattern::Normal(ast::Expr::Ident(ident)) => {
+            let Spanned { v, span } = args.expect::<Spanned<Value>>(&ident)?;
+            vm.define(ident, DeclaredType::check(types, v).at(span)?);
+        }
+        pattern => {
+            let Spanned { v, span } =
+                args.expect::<Spanned<Value>>("pattern parameter")?;
+            let value = DeclaredType::check(types, v).at(span)?;
+            crate::eval::destructure(vm, pattern, value)?;
+        }
+    }
+    Ok(())
 }
 
 /// This used only as the return value of `eval_field_call`.
@@ -317,8 +373,20 @@ fn eval_field_call(
         // Call plugins by converting args to bytes.
         let bytes = args.all::<Bytes>()?;
         args.finish()?;
-        let value = plugin.call(&field, bytes).at(span)?.into_value();
-        Ok(FieldCall::Resolved(value))
+        let output = plugin.call(&field, bytes).at(span)?;
+        let printed = output.printed.trim_end();
+        if !printed.is_empty() {
+            vm.engine.sink.warn(warning!(span, "plugin printed: {printed}"));
+        }
+        Ok(FieldCall::Resolved(output.bytes.into_value()))
+    } else if let Value::Record(record) = &target {
+        // Call methods of user-defined types with the record as the first
+        // argument.
+        let Some(method) = record.ty().method(&field).cloned() else {
+            bail!(missing_field_call_error(target, field))
+        };
+        args.insert(0, target_expr.span(), target);
+        Ok(FieldCall::Normal(Value::Func(method), args))
     } else if let Some(callee) = target.ty().scope().get(&field) {
         args.insert(0, target_expr.span(), target);
         Ok(FieldCall::Normal(callee.clone(), args))
@@ -338,8 +406,8 @@ fn eval_field_call(
 
 /// Produce an error when we cannot call the field.
 fn missing_field_call_error(target: Value, field: Ident) -> SourceDiagnostic {
-    let mut error =
-        error!(field.span(), "type {} has no method `{}`", target.ty(), field.as_str());
+    let ty = DeclaredType::of(&target);
+    let mut error = error!(field.span(), "type {ty} has no method `{}`", field.as_str());
 
     match target {
         Value::Dict(ref dict) if matches!(dict.get(&field), Ok(Value::Func(_))) => {
@@ -466,11 +534,18 @@ impl<'a> CapturesVisitor<'a> {
             // of named parameters cannot access previous parameter bindings.
             Some(ast::Expr::Closure(expr)) => {
                 for param in expr.params().children() {
+                    if let Some(annotation) = param.annotation() {
+                        self.visit(annotation.to_untyped());
+                    }
                     if let ast::Param::Named(named) = param {
                         self.visit(named.expr().to_untyped());
                     }
                 }
 
+                if let Some(returns) = expr.returns() {
+                    self.visit(returns.to_untyped());
+                }
+
                 self.internal.enter();
                 if let Some(name) = expr.name() {
                     self.bind(name);
@@ -483,6 +558,11 @@ impl<'a> CapturesVisitor<'a> {
                                 self.bind(ident);
                             }
                         }
+                        ast::Param::Annotated(annotated) => {
+                            for ident in annotated.pattern().bindings() {
+                                self.bind(ident);
+                            }
+                        }
                         ast::Param::Named(named) => self.bind(named.name()),
                         ast::Param::Spread(spread) => {
                             if let Some(ident) = spread.sink_ident() {
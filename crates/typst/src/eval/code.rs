@@ -1,7 +1,7 @@
 use ecow::{eco_vec, EcoVec};
 
 use crate::diag::{bail, error, At, SourceResult};
-use crate::eval::{ops, CapturesVisitor, Eval, Vm};
+use crate::eval::{debug, ops, CapturesVisitor, Eval, Vm};
 use crate::foundations::{
     Array, Capturer, Closure, Content, ContextElem, Dict, Func, NativeElement, Str, Value,
 };
@@ -25,6 +25,7 @@ fn eval_code<'a>(
 
     while let Some(expr) = exprs.next() {
         let span = expr.span();
+        debug::statement(vm, span);
         let value = match expr {
             ast::Expr::Set(set) => {
                 let styles = set.eval(vm)?;
@@ -351,6 +352,8 @@ impl Eval for ast::Contextual<'_> {
             defaults: vec![],
             captured,
             num_pos_params: 0,
+            param_types: vec![],
+            return_types: vec![],
         };
 
         let func = Func::from(closure).spanned(body.span());
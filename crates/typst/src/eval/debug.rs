@@ -0,0 +1,489 @@
+//! Stepping through evaluation.
+//!
+//! A [`Debugger`] can be attached to a compilation with
+//! [`compile_with_debugger`](crate::compile_with_debugger) to observe the
+//! evaluation of markup and code. Before each statement, it is handed a
+//! [`Frame`] with the statement's span, the calls in progress, and the
+//! variables in scope. It may block to pause evaluation. The [`Controller`]
+//! implements breakpoints and stepping on top of this interface.
+//!
+//! The debugger reaches evaluation through the engine's
+//! [`Traced`](crate::engine::Traced), so there is no global state and several
+//! compilations can be debugged independently. Two caveats apply:
+//!
+//! - Evaluation is memoized. Attaching a debugger invalidates results from
+//!   compilations without it, but within a debugged compilation, a module or
+//!   function call that was already evaluated with the same inputs is not
+//!   stepped through a second time.
+//! - Layout runs in parallel, so show rules and functions called during
+//!   layout may be evaluated on several threads at once. The calls in progress
+//!   are tracked per thread and only one thread is stopped at a time, but
+//!   other threads keep running until they reach a statement themselves.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::ThreadId;
+
+use ecow::EcoString;
+
+use crate::eval::Vm;
+use crate::foundations::{Scopes, Value};
+use crate::syntax::{FileId, Span};
+
+/// Observes evaluation and can pause it.
+pub trait Debugger: Send + Sync {
+    /// Called before a statement is evaluated.
+    ///
+    /// Statements are the expressions in code blocks and the expressions
+    /// embedded into markup with a hash. Evaluation only continues once this
+    /// method returns.
+    fn statement(&self, frame: &Frame);
+}
+
+/// A debugger attached to a compilation, along with the calls in progress.
+///
+/// The hash of a handle identifies the attachment, so that memoized results
+/// from one compilation are only reused with the same debugger.
+#[derive(Clone)]
+pub struct Attached {
+    debugger: Arc<dyn Debugger>,
+    /// The calls in progress on each thread evaluating code.
+    stacks: Arc<Mutex<HashMap<ThreadId, Vec<Call>>>>,
+}
+
+impl Attached {
+    /// Prepare a debugger for attaching it to a compilation.
+    pub fn new(debugger: Arc<dyn Debugger>) -> Self {
+        Self { debugger, stacks: Arc::default() }
+    }
+
+    /// Notify the debugger that a statement is about to be evaluated.
+    fn statement(&self, span: Span, scopes: &Scopes) {
+        let thread = std::thread::current().id();
+        let stack = {
+            let mut stacks = self.stacks.lock().unwrap();
+            let stack = stacks.entry(thread).or_default();
+            if let Some(call) = stack.last_mut() {
+                call.span = span;
+            }
+            stack.clone()
+        };
+
+        // The lock must not be held while the debugger blocks, so that other
+        // threads can still enter and leave calls.
+        self.debugger.statement(&Frame { span, stack: &stack, scopes });
+    }
+
+    /// Change the calls in progress on the current thread.
+    fn with_stack(&self, f: impl FnOnce(&mut Vec<Call>)) {
+        let thread = std::thread::current().id();
+        let mut stacks = self.stacks.lock().unwrap();
+        let stack = stacks.entry(thread).or_default();
+        f(stack);
+        if stack.is_empty() {
+            stacks.remove(&thread);
+        }
+    }
+}
+
+impl Hash for Attached {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.stacks).hash(state);
+    }
+}
+
+/// A module evaluation or closure call that is in progress.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Call {
+    /// The name of the closure or the path of the module, if any.
+    pub name: Option<EcoString>,
+    /// The span of the statement that is being evaluated in this call.
+    pub span: Span,
+}
+
+/// The state of evaluation just before a statement.
+pub struct Frame<'a> {
+    span: Span,
+    stack: &'a [Call],
+    scopes: &'a Scopes<'a>,
+}
+
+impl Frame<'_> {
+    /// The span of the statement that is about to be evaluated.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// The calls in progress, outermost first.
+    pub fn stack(&self) -> &[Call] {
+        self.stack
+    }
+
+    /// How many calls are in progress.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// The variables in scope, innermost first.
+    ///
+    /// Shadowed variables and the standard library are not included.
+    pub fn variables(&self) -> Vec<(EcoString, Value)> {
+        let mut seen = HashSet::new();
+        std::iter::once(&self.scopes.top)
+            .chain(self.scopes.scopes.iter().rev())
+            .flat_map(|scope| scope.iter())
+            .filter(|(name, ..)| seen.insert(*name))
+            .map(|(name, value, _)| (name.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// Notify the debugger, if any, that a statement is about to be evaluated.
+#[inline]
+pub(crate) fn statement(vm: &Vm, span: Span) {
+    if let Some(attached) = &vm.debugger {
+        attached.statement(span, &vm.scopes);
+    }
+}
+
+/// Record that a module evaluation or closure call starts. It ends when the
+/// returned guard is dropped.
+pub(crate) fn enter(
+    vm: &Vm,
+    name: impl FnOnce() -> Option<EcoString>,
+    span: Span,
+) -> Entered {
+    let attached = vm.debugger.clone();
+    if let Some(attached) = &attached {
+        let call = Call { name: name(), span };
+        attached.with_stack(|stack| stack.push(call));
+    }
+    Entered(attached)
+}
+
+/// Ends a call when dropped.
+#[must_use]
+pub(crate) struct Entered(Option<Attached>);
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        if let Some(attached) = &self.0 {
+            attached.with_stack(|stack| {
+                stack.pop();
+            });
+        }
+    }
+}
+
+/// A debugger with breakpoints and stepping.
+///
+/// When evaluation stops, the `on_stop` callback is invoked and the
+/// evaluating thread blocks until [`resume`](Self::resume) is called.
+pub struct Controller {
+    state: Mutex<State>,
+    resumed: Condvar,
+    on_stop: Box<dyn Fn(&Stop) + Send + Sync>,
+}
+
+/// The mutable state of a [`Controller`].
+struct State {
+    breakpoints: HashMap<FileId, HashSet<Span>>,
+    mode: Mode,
+    stopped: Option<Stop>,
+}
+
+impl State {
+    /// Why to stop before a statement with the given span and call depth, if
+    /// at all.
+    fn reason(&self, span: Span, depth: usize) -> Option<Reason> {
+        match self.mode {
+            Mode::Entry => Some(Reason::Entry),
+            Mode::Pause => Some(Reason::Pause),
+            Mode::Into => Some(Reason::Step),
+            Mode::Over(d) if depth <= d => Some(Reason::Step),
+            Mode::Out(d) if depth < d => Some(Reason::Step),
+            _ => None,
+        }
+        .or_else(|| {
+            let spans = self.breakpoints.get(&span.id()?)?;
+            spans.contains(&span).then_some(Reason::Breakpoint)
+        })
+    }
+}
+
+/// When to stop next.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Mode {
+    Continue,
+    Entry,
+    Pause,
+    Into,
+    Over(usize),
+    Out(usize),
+}
+
+/// How to resume evaluation after a stop.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Step {
+    /// Run until a breakpoint is hit.
+    Continue,
+    /// Stop at the next statement.
+    Into,
+    /// Stop at the next statement that is not part of a deeper call.
+    Over,
+    /// Stop at the next statement of the calling function or module.
+    Out,
+}
+
+/// Why evaluation stopped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Reason {
+    /// At the first statement.
+    Entry,
+    /// At a breakpoint.
+    Breakpoint,
+    /// After a step.
+    Step,
+    /// Because a pause was requested.
+    Pause,
+}
+
+/// A snapshot of the state of evaluation at a stop.
+#[derive(Debug, Clone)]
+pub struct Stop {
+    /// Why evaluation stopped.
+    pub reason: Reason,
+    /// The span of the statement that is about to be evaluated.
+    pub span: Span,
+    /// The calls in progress, outermost first.
+    pub stack: Vec<Call>,
+    /// The variables in scope, innermost first.
+    pub variables: Vec<(EcoString, Value)>,
+}
+
+impl Controller {
+    /// Create a new controller that doesn't stop until a breakpoint is set or
+    /// a pause is requested.
+    pub fn new(on_stop: impl Fn(&Stop) + Send + Sync + 'static) -> Self {
+        Self {
+            state: Mutex::new(State {
+                breakpoints: HashMap::new(),
+                mode: Mode::Continue,
+                stopped: None,
+            }),
+            resumed: Condvar::new(),
+            on_stop: Box::new(on_stop),
+        }
+    }
+
+    /// Stop at the first statement that is evaluated.
+    pub fn stop_on_entry(&self) {
+        self.state.lock().unwrap().mode = Mode::Entry;
+    }
+
+    /// Replace the breakpoints in a file. Evaluation stops before each
+    /// statement whose span is among the given ones.
+    pub fn set_breakpoints(&self, id: FileId, spans: impl IntoIterator<Item = Span>) {
+        let mut state = self.state.lock().unwrap();
+        state.breakpoints.insert(id, spans.into_iter().collect());
+    }
+
+    /// Remove all breakpoints.
+    pub fn clear_breakpoints(&self) {
+        self.state.lock().unwrap().breakpoints.clear();
+    }
+
+    /// Stop at the next statement. Does nothing if already stopped.
+    pub fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.stopped.is_none() {
+            state.mode = Mode::Pause;
+        }
+    }
+
+    /// Resume evaluation after a stop. Does nothing if not stopped.
+    pub fn resume(&self, step: Step) {
+        let mut state = self.state.lock().unwrap();
+        let Some(stop) = state.stopped.take() else { return };
+        let depth = stop.stack.len();
+        state.mode = match step {
+            Step::Continue => Mode::Continue,
+            Step::Into => Mode::Into,
+            Step::Over => Mode::Over(depth),
+            Step::Out => Mode::Out(depth),
+        };
+        self.resumed.notify_all();
+    }
+
+    /// The current stop, if evaluation is stopped.
+    pub fn stopped(&self) -> Option<Stop> {
+        self.state.lock().unwrap().stopped.clone()
+    }
+}
+
+impl Debugger for Controller {
+    fn statement(&self, frame: &Frame) {
+        // Only one thread can be stopped at a time.
+        let mut state = self
+            .resumed
+            .wait_while(self.state.lock().unwrap(), |state| state.stopped.is_some())
+            .unwrap();
+
+        let Some(reason) = state.reason(frame.span(), frame.depth()) else { return };
+        let stop = Stop {
+            reason,
+            span: frame.span(),
+            stack: frame.stack().to_vec(),
+            variables: frame.variables(),
+        };
+
+        state.mode = Mode::Continue;
+        state.stopped = Some(stop.clone());
+        drop(state);
+
+        (self.on_stop)(&stop);
+
+        let state = self.state.lock().unwrap();
+        drop(
+            self.resumed
+                .wait_while(state, |state| state.stopped.is_some())
+                .unwrap(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::syntax::Source;
+
+    /// Drives a controller with statements evaluated on another thread.
+    struct Harness {
+        controller: Controller,
+        sender: mpsc::Sender<Option<Reason>>,
+        events: mpsc::Receiver<Option<Reason>>,
+        id: FileId,
+        spans: Vec<Span>,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            let (sender, events) = mpsc::channel();
+            let controller = Controller::new({
+                let sender = sender.clone();
+                move |stop| sender.send(Some(stop.reason)).unwrap()
+            });
+            let source = Source::detached("#a #b #c");
+            let spans = source.root().children().map(|node| node.span()).collect();
+            Self { controller, sender, events, id: source.id(), spans }
+        }
+
+        /// Evaluate the `i`-th statement at the given call depth. If evaluation
+        /// stops, it is resumed with the given step and the reason is returned.
+        fn statement(&self, i: usize, depth: usize, step: Step) -> Option<Reason> {
+            let span = self.spans[i];
+            let stack = vec![Call { name: None, span }; depth];
+            let controller = &self.controller;
+            let sender = self.sender.clone();
+            std::thread::scope(|scope| {
+                scope.spawn(move || {
+                    let scopes = Scopes::new(None);
+                    controller.statement(&Frame { span, stack: &stack, scopes: &scopes });
+                    sender.send(None).unwrap();
+                });
+
+                let reason = self.events.recv().unwrap();
+                if reason.is_some() {
+                    assert!(controller.stopped().is_some());
+                    controller.resume(step);
+                    assert_eq!(self.events.recv().unwrap(), None);
+                }
+                reason
+            })
+        }
+    }
+
+    #[test]
+    fn test_controller_continue() {
+        let h = Harness::new();
+        assert_eq!(h.statement(0, 1, Step::Continue), None);
+        assert_eq!(h.statement(1, 2, Step::Continue), None);
+        assert!(h.controller.stopped().is_none());
+    }
+
+    #[test]
+    fn test_controller_entry() {
+        let h = Harness::new();
+        h.controller.stop_on_entry();
+        assert_eq!(h.statement(0, 1, Step::Continue), Some(Reason::Entry));
+        assert_eq!(h.statement(1, 1, Step::Continue), None);
+    }
+
+    #[test]
+    fn test_controller_breakpoints() {
+        let h = Harness::new();
+        h.controller.set_breakpoints(h.id, [h.spans[1]]);
+        assert_eq!(h.statement(0, 1, Step::Continue), None);
+        assert_eq!(h.statement(1, 1, Step::Continue), Some(Reason::Breakpoint));
+        assert_eq!(h.statement(1, 2, Step::Continue), Some(Reason::Breakpoint));
+        h.controller.clear_breakpoints();
+        assert_eq!(h.statement(1, 1, Step::Continue), None);
+    }
+
+    #[test]
+    fn test_controller_step_into() {
+        let h = Harness::new();
+        h.controller.stop_on_entry();
+        assert_eq!(h.statement(0, 1, Step::Into), Some(Reason::Entry));
+        assert_eq!(h.statement(1, 2, Step::Into), Some(Reason::Step));
+        assert_eq!(h.statement(2, 1, Step::Continue), Some(Reason::Step));
+        assert_eq!(h.statement(0, 1, Step::Continue), None);
+    }
+
+    #[test]
+    fn test_controller_step_over() {
+        let h = Harness::new();
+        h.controller.stop_on_entry();
+        assert_eq!(h.statement(0, 2, Step::Over), Some(Reason::Entry));
+        assert_eq!(h.statement(1, 3, Step::Over), None);
+        assert_eq!(h.statement(2, 2, Step::Over), Some(Reason::Step));
+        assert_eq!(h.statement(0, 1, Step::Continue), Some(Reason::Step));
+    }
+
+    #[test]
+    fn test_controller_step_out() {
+        let h = Harness::new();
+        h.controller.stop_on_entry();
+        assert_eq!(h.statement(0, 2, Step::Out), Some(Reason::Entry));
+        assert_eq!(h.statement(1, 2, Step::Out), None);
+        assert_eq!(h.statement(2, 3, Step::Out), None);
+        assert_eq!(h.statement(0, 1, Step::Continue), Some(Reason::Step));
+    }
+
+    #[test]
+    fn test_controller_step_hits_breakpoint() {
+        let h = Harness::new();
+        h.controller.set_breakpoints(h.id, [h.spans[1]]);
+        h.controller.stop_on_entry();
+        assert_eq!(h.statement(0, 1, Step::Out), Some(Reason::Entry));
+        assert_eq!(h.statement(1, 1, Step::Continue), Some(Reason::Breakpoint));
+    }
+
+    #[test]
+    fn test_controller_pause() {
+        let h = Harness::new();
+        h.controller.pause();
+        assert_eq!(h.statement(0, 1, Step::Continue), Some(Reason::Pause));
+        assert_eq!(h.statement(1, 1, Step::Continue), None);
+    }
+
+    #[test]
+    fn test_controller_resume_without_stop() {
+        let h = Harness::new();
+        h.controller.resume(Step::Into);
+        assert_eq!(h.statement(0, 1, Step::Continue), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::diag::{warning, At, SourceResult};
-use crate::eval::{Eval, Vm};
+use crate::eval::{debug, Eval, Vm};
 use crate::foundations::{
     Content, Label, NativeElement, Repr, Smart, Unlabellable, Value,
 };
@@ -31,9 +31,19 @@ fn eval_markup<'a>(
     let mut seq = Vec::with_capacity(exprs.size_hint().1.unwrap_or_default());
 
     while let Some(expr) = exprs.next() {
+        if expr.hash() {
+            debug::statement(vm, expr.span());
+        }
+
         match expr {
             ast::Expr::Set(set) => {
-                let styles = set.eval(vm)?;
+                let styles = match set.eval(vm) {
+                    Err(errors) if vm.engine.traced.recovering() => {
+                        seq.push(vm.engine.recover(errors, set.span()));
+                        continue;
+                    }
+                    result => result?,
+                };
                 if vm.flow.is_some() {
                     break;
                 }
@@ -41,7 +51,13 @@ fn eval_markup<'a>(
                 seq.push(eval_markup(vm, exprs)?.styled_with_map(styles))
             }
             ast::Expr::Show(show) => {
-                let recipe = show.eval(vm)?;
+                let recipe = match show.eval(vm) {
+                    Err(errors) if vm.engine.traced.recovering() => {
+                        seq.push(vm.engine.recover(errors, show.span()));
+                        continue;
+                    }
+                    result => result?,
+                };
                 if vm.flow.is_some() {
                     break;
                 }
@@ -49,8 +65,12 @@ fn eval_markup<'a>(
                 let tail = eval_markup(vm, exprs)?;
                 seq.push(tail.styled_with_recipe(&mut vm.engine, vm.context, recipe)?)
             }
-            expr => match expr.eval(vm)? {
-                Value::Label(label) => {
+            expr => match expr.eval(vm) {
+                Err(errors) if vm.engine.traced.recovering() => {
+                    seq.push(vm.engine.recover(errors, expr.span()));
+                }
+                Err(errors) => return Err(errors),
+                Ok(Value::Label(label)) => {
                     if let Some(elem) =
                         seq.iter_mut().rev().find(|node| !node.can::<dyn Unlabellable>())
                     {
@@ -70,7 +90,7 @@ fn eval_markup<'a>(
                         ));
                     }
                 }
-                value => seq.push(value.display().spanned(expr.span())),
+                Ok(value) => seq.push(value.display().spanned(expr.span())),
             },
         }
 
@@ -1,5 +1,6 @@
 //! Evaluation of markup and code.
 
+pub mod debug;
 pub(crate) mod ops;
 
 mod access;
@@ -22,6 +23,7 @@ pub(crate) use self::binding::*;
 pub(crate) use self::flow::*;
 
 use comemo::{Track, Tracked, TrackedMut};
+use ecow::eco_format;
 
 use crate::diag::{bail, SourceResult};
 use crate::engine::{Engine, Route, Sink, Traced};
@@ -62,11 +64,22 @@ pub fn eval(
     let scopes = Scopes::new(Some(world.library()));
     let root = source.root();
     let mut vm = Vm::new(engine, context.track(), scopes, root.span());
-
-    // Check for well-formedness unless we are in trace mode.
+    let _call = debug::enter(
+        &vm,
+        || Some(eco_format!("{}", id.vpath().as_rootless_path().display())),
+        root.span(),
+    );
+
+    // Check for well-formedness unless we are in trace mode. When recovering,
+    // the errors are reported, but the rest of the file is still evaluated.
     let errors = root.errors();
-    if !errors.is_empty() && vm.inspected.is_none() {
-        return Err(errors.into_iter().map(Into::into).collect());
+    if !errors.is_empty() {
+        let errors = errors.into_iter().map(Into::into).collect();
+        if vm.engine.traced.recovering() {
+            vm.engine.sink.delay(errors);
+        } else if vm.inspected.is_none() {
+            return Err(errors);
+        }
     }
 
     // Evaluate the module.
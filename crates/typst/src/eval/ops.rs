@@ -541,6 +541,7 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Type(a), Type(b)) => a == b,
         (Module(a), Module(b)) => a == b,
         (Plugin(a), Plugin(b)) => a == b,
+        (Record(a), Record(b)) => a == b,
         (Datetime(a), Datetime(b)) => a == b,
         (Duration(a), Duration(b)) => a == b,
         (Dyn(a), Dyn(b)) => a == b,
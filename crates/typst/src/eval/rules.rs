@@ -18,17 +18,19 @@ impl Eval for ast::SetRule<'_> {
         }
 
         let target = self.target();
-        let target = target
-            .eval(vm)?
-            .cast::<Func>()
-            .and_then(|func| {
-                func.element().ok_or_else(|| {
-                    "only element functions can be used in set rules".into()
-                })
-            })
-            .at(target.span())?;
-        let args = self.args().eval(vm)?.spanned(self.span());
-        Ok(target.set(&mut vm.engine, args)?.spanned(self.span()).liftable())
+        let func = target.eval(vm)?.cast::<Func>().at(target.span())?;
+        let styles = if let Some(custom) = func.custom_element() {
+            let args = self.args().eval(vm)?.spanned(self.span());
+            custom.set(args)?
+        } else {
+            let elem = func
+                .element()
+                .ok_or("only element functions can be used in set rules")
+                .at(target.span())?;
+            let args = self.args().eval(vm)?.spanned(self.span());
+            elem.set(&mut vm.engine, args)?
+        };
+        Ok(styles.spanned(self.span()).liftable())
     }
 }
 
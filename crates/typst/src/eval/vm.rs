@@ -1,6 +1,7 @@
 use comemo::Tracked;
 
 use crate::engine::Engine;
+use crate::eval::debug::Attached;
 use crate::eval::FlowEvent;
 use crate::foundations::{Context, IntoValue, Scopes, Value};
 use crate::syntax::ast::{self, AstNode};
@@ -20,6 +21,8 @@ pub struct Vm<'a> {
     pub(crate) scopes: Scopes<'a>,
     /// A span that is currently under inspection.
     pub(crate) inspected: Option<Span>,
+    /// The debugger to notify before statements, if any.
+    pub(crate) debugger: Option<Attached>,
     /// Data that is contextually made accessible to code behind the scenes.
     pub(crate) context: Tracked<'a, Context<'a>>,
 }
@@ -33,7 +36,15 @@ impl<'a> Vm<'a> {
         target: Span,
     ) -> Self {
         let inspected = target.id().and_then(|id| engine.traced.get(id));
-        Self { engine, context, flow: None, scopes, inspected }
+        let debugger = engine.traced.debugger();
+        Self {
+            engine,
+            context,
+            flow: None,
+            scopes,
+            inspected,
+            debugger,
+        }
     }
 
     /// Access the underlying world.
@@ -8,7 +8,9 @@ use smallvec::SmallVec;
 use unicode_math_class::MathClass;
 
 use crate::diag::{At, HintedStrResult, HintedString, SourceResult, StrResult};
-use crate::foundations::{array, repr, NativeElement, Packed, Repr, Str, Type, Value};
+use crate::foundations::{
+    array, repr, DeclaredType, NativeElement, Packed, Repr, Str, Type, Value,
+};
 use crate::syntax::{Span, Spanned};
 
 #[rustfmt::skip]
@@ -325,7 +327,7 @@ impl CastInfo {
 
         if !matching_type {
             msg.push_str(", found ");
-            write!(msg, "{}", found.ty()).unwrap();
+            write!(msg, "{}", DeclaredType::of(found)).unwrap();
         }
 
         let mut msg: HintedString = msg.into();
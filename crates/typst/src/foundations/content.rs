@@ -14,9 +14,9 @@ use smallvec::smallvec;
 use crate::diag::{SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, func, scope, ty, Context, Dict, Element, Fields, IntoValue, Label,
-    NativeElement, Recipe, RecipeIndex, Repr, Selector, Str, Style, StyleChain, Styles,
-    Value,
+    elem, func, scope, ty, Context, CustomElem, CustomElement, Dict, Element, Fields,
+    Func, IntoValue, Label, NativeElement, Recipe, RecipeIndex, Repr, Selector, Str,
+    Style, StyleChain, Styles, Value,
 };
 use crate::introspection::Location;
 use crate::layout::{AlignElem, Alignment, Axes, Length, MoveElem, PadElem, Rel, Sides};
@@ -118,6 +118,29 @@ impl Content {
         self.inner.elem.dyn_elem()
     }
 
+    /// Get the element of this content. For instances of user-defined
+    /// elements, this is the native element that wraps them; use
+    /// [`custom_elem`](Self::custom_elem) to get the user-defined element
+    /// itself.
+    pub fn func(&self) -> Element {
+        self.elem()
+    }
+
+    /// Get the user-defined element of this content, if it is an instance of
+    /// one.
+    pub fn custom_elem(&self) -> Option<&CustomElement> {
+        self.to_packed::<CustomElem>().map(|elem| elem.element())
+    }
+
+    /// The name of this content's element, taking user-defined elements into
+    /// account.
+    pub fn elem_name(&self) -> &str {
+        match self.custom_elem() {
+            Some(custom) => custom.name(),
+            None => self.elem().name(),
+        }
+    }
+
     /// Get the span of the content.
     pub fn span(&self) -> Span {
         self.span
@@ -215,6 +238,9 @@ impl Content {
                 return Ok(label.into_value());
             }
         }
+        if let Some(elem) = self.to_packed::<CustomElem>() {
+            return elem.get(name);
+        }
         let id = self.elem().field_id(name).ok_or(FieldAccessError::Unknown)?;
         self.get(id, None)
     }
@@ -527,9 +553,12 @@ impl Content {
     /// element. Can be compared with global functions to check whether you have
     /// a specific
     /// kind of element.
-    #[func]
-    pub fn func(&self) -> Element {
-        self.elem()
+    #[func(name = "func")]
+    pub fn elem_func(&self) -> Func {
+        match self.custom_elem() {
+            Some(custom) => custom.clone().into(),
+            None => self.elem().into(),
+        }
     }
 
     /// Whether the content has the specified field.
@@ -543,6 +572,10 @@ impl Content {
             return self.label().is_some();
         }
 
+        if let Some(elem) = self.to_packed::<CustomElem>() {
+            return elem.values.contains(&field);
+        }
+
         let Some(id) = self.elem().field_id(&field) else {
             return false;
         };
@@ -577,7 +610,10 @@ impl Content {
     /// ```
     #[func]
     pub fn fields(&self) -> Dict {
-        let mut dict = self.inner.elem.fields();
+        let mut dict = match self.to_packed::<CustomElem>() {
+            Some(elem) => elem.values.clone(),
+            None => self.inner.elem.fields(),
+        };
         if let Some(label) = self.label() {
             dict.insert("label".into(), label.into_value());
         }
@@ -696,7 +732,7 @@ impl Serialize for Content {
         S: Serializer,
     {
         serializer.collect_map(
-            iter::once(("func".into(), self.func().name().into_value()))
+            iter::once(("func".into(), self.elem_name().into_value()))
                 .chain(self.fields()),
         )
     }
@@ -977,7 +1013,7 @@ impl FieldAccessError {
     /// Formats the error message given the content and the field name.
     #[cold]
     pub fn message(self, content: &Content, field: &str) -> EcoString {
-        let elem_name = content.elem().name();
+        let elem_name = content.elem_name();
         match self {
             FieldAccessError::Unknown => {
                 eco_format!("{elem_name} does not have field {}", field.repr())
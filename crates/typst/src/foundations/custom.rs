@@ -0,0 +1,521 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use comemo::Track;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    cast, elem, func, repr, Args, AutoValue, CastInfo, Construct, Content, Context, Dict,
+    FieldAccessError, Fold, FromValue, Func, NativeElement, NoneValue, Packed,
+    RecordType, Reflect, Repr, Selector, Show, Str, StyleChain, Styles, Synthesize, Type,
+    Value,
+};
+use crate::introspection::{Count, CounterUpdate, Locatable};
+use crate::syntax::{Span, Spanned};
+use crate::utils::{LazyHash, NonZeroExt};
+
+/// Defines a new element.
+///
+/// Elements defined this way work just like the built-in ones: Calling the
+/// resulting element function creates content, and the function can be used
+/// in [set rules]($styling/#set-rules), [show rules]($styling/#show-rules),
+/// and [selectors]($selector). Each instance of the element is
+/// [locatable]($location/#locatable), so it can be [labelled]($label),
+/// [queried]($query), and [counted]($counter).
+///
+/// ```example
+/// #let theorem = element(
+///   "theorem",
+///   fields: (
+///     title: (type: content, default: none),
+///     body: (type: content, positional: true, required: true),
+///   ),
+///   display: it => block[
+///     *Theorem #counter(it.func()).display()*
+///     #if it.title != none [(#it.title)]
+///     #it.body
+///   ],
+/// )
+///
+/// #show theorem: set text(blue)
+///
+/// #theorem[$a^2 + b^2 = c^2$]
+/// #theorem(title: [Euclid])[
+///   There are infinitely many primes.
+/// ]
+/// ```
+///
+/// # Fields
+/// Each field is described by a dictionary with the following keys, all of
+/// which are optional:
+/// - `type`: A [type] or an array of types the field's value must have. This
+///   can also be a type defined with the [`record`] function. A field of
+///   type [`content`] also accepts strings and symbols. If the
+///   default value is `{none}`, `{none}` is accepted, too. If omitted, the
+///   field accepts any value.
+/// - `default`: The value the field has when it is neither given nor set with
+///   a set rule. Defaults to `{none}`.
+/// - `positional`: Whether the field is given positionally instead of by
+///   name. Positional fields are filled in the order in which they are
+///   declared. Defaults to `{false}`.
+/// - `required`: Whether the field must be given when creating the element.
+///   Required fields cannot be configured with set rules. Defaults to
+///   `{false}`.
+///
+/// Instead of a dictionary, you can also just write the field's type.
+///
+/// # Counting
+/// Elements are counted by [`counter`]($counter) like any other locatable
+/// element. If the element has a field called `numbering` and its value is
+/// `{none}`, the instance is not counted, just like an unnumbered heading.
+#[func(name = "element")]
+pub fn define_element(
+    /// The call site span.
+    span: Span,
+    /// The element's name.
+    name: Str,
+    /// The element's fields, mapping from field names to field descriptions.
+    #[named]
+    #[default]
+    fields: Dict,
+    /// How to display the element.
+    ///
+    /// Receives an instance of the element, with all of its fields resolved,
+    /// and returns the content to display. If omitted, the element displays
+    /// as nothing. A show rule for the element takes precedence over this
+    /// function.
+    #[named]
+    display: Option<Func>,
+) -> StrResult<Func> {
+    if name.is_empty() {
+        bail!("element name must not be empty");
+    }
+
+    let fields = CustomField::parse_all(fields)?;
+    if fields.iter().any(|field| field.name == "label") {
+        bail!("field name `label` is reserved");
+    }
+
+    Ok(CustomElement(Arc::new(LazyHash::new(CustomElementData {
+        name: name.into(),
+        fields,
+        display,
+        span,
+    })))
+    .into())
+}
+
+/// A user-defined element, created with the [`element`] function.
+#[derive(Clone, PartialEq, Hash)]
+pub struct CustomElement(Arc<LazyHash<CustomElementData>>);
+
+/// The definition of a user-defined element.
+#[derive(Debug, PartialEq, Hash)]
+struct CustomElementData {
+    /// The element's name.
+    name: EcoString,
+    /// The element's fields, in declaration order.
+    fields: Vec<CustomField>,
+    /// The function that displays the element.
+    display: Option<Func>,
+    /// The span of the definition. Distinguishes otherwise identical elements.
+    span: Span,
+}
+
+impl CustomElement {
+    /// The element's name.
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The element's fields, in declaration order.
+    pub fn fields(&self) -> &[CustomField] {
+        &self.0.fields
+    }
+
+    /// The field with the given name, if the element has one.
+    pub fn field(&self, name: &str) -> Option<&CustomField> {
+        self.0.fields.iter().find(|field| field.name == name)
+    }
+
+    /// The function that displays the element.
+    pub fn display(&self) -> Option<&Func> {
+        self.0.display.as_ref()
+    }
+
+    /// Construct an instance of this element.
+    pub fn construct(&self, args: &mut Args) -> SourceResult<Content> {
+        let values = CustomField::take_all(self.fields(), args)?;
+        Ok(CustomElem::new(self.clone(), values).pack())
+    }
+
+    /// Execute a set rule for this element and return the resulting style map.
+    pub fn set(&self, mut args: Args) -> SourceResult<Styles> {
+        let mut values = Dict::new();
+        for field in self.fields().iter().filter(|field| !field.required) {
+            let value = if field.positional {
+                args.eat::<Spanned<Value>>()?
+            } else {
+                args.named::<Spanned<Value>>(&field.name)?
+            };
+
+            if let Some(Spanned { v, span }) = value {
+                values.insert(field.name.clone().into(), field.check(v).at(span)?);
+            }
+        }
+
+        args.finish()?;
+
+        let mut styles = Styles::new();
+        styles
+            .set(CustomElem::set_settings(CustomSettings(vec![(self.clone(), values)])));
+        Ok(styles)
+    }
+
+    /// Create a selector for this element.
+    pub fn select(&self) -> Selector {
+        Selector::Custom(self.clone(), None)
+    }
+
+    /// Create a selector for this element, filtering for those whose fields
+    /// match the given ones.
+    pub fn where_(&self, fields: Dict) -> StrResult<Selector> {
+        for (key, _) in fields.iter() {
+            if self.field(key).is_none() {
+                bail!("element `{}` does not have field `{}`", self.name(), key);
+            }
+        }
+        Ok(Selector::Custom(self.clone(), Some(fields)))
+    }
+}
+
+impl Debug for CustomElement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Element({})", self.name())
+    }
+}
+
+/// A field of a user-defined element or type.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct CustomField {
+    /// The field's name.
+    pub name: EcoString,
+    /// The types the field's value may have. Empty if any value is allowed.
+    pub types: Vec<DeclaredType>,
+    /// The value used when the field is neither given nor set.
+    pub default: Value,
+    /// Whether the field is given positionally.
+    pub positional: bool,
+    /// Whether the field must be given.
+    pub required: bool,
+}
+
+impl CustomField {
+    /// Parse fields from a dictionary mapping names to descriptions.
+    pub fn parse_all(fields: Dict) -> StrResult<Vec<Self>> {
+        fields
+            .into_iter()
+            .map(|(name, spec)| Self::parse(name.into(), spec))
+            .collect()
+    }
+
+    /// Parse a field from its name and description.
+    fn parse(name: EcoString, spec: Value) -> StrResult<Self> {
+        if !crate::syntax::is_ident(&name) {
+            bail!("`{name}` is not a valid field name");
+        }
+
+        let mut dict = match spec {
+            Value::Dict(dict) => dict,
+            ty @ (Value::Type(_) | Value::Func(_)) => {
+                let mut dict = Dict::new();
+                dict.insert("type".into(), ty);
+                dict
+            }
+            v => bail!("expected dictionary or type, found {}", v.ty()),
+        };
+
+        let types = match dict.take("type").ok() {
+            None => vec![],
+            Some(Value::Array(array)) => array
+                .into_iter()
+                .map(DeclaredType::from_value)
+                .collect::<Result<_, _>>()
+                .map_err(|err| err.message().clone())?,
+            Some(v) => {
+                vec![DeclaredType::from_value(v).map_err(|err| err.message().clone())?]
+            }
+        };
+
+        let flag = |dict: &mut Dict, key: &str| -> StrResult<bool> {
+            match dict.take(key).ok() {
+                None => Ok(false),
+                Some(Value::Bool(v)) => Ok(v),
+                Some(v) => bail!("expected boolean, found {}", v.ty()),
+            }
+        };
+
+        let positional = flag(&mut dict, "positional")?;
+        let required = flag(&mut dict, "required")?;
+        let default = dict.take("default").ok();
+        dict.finish(&["type", "default", "positional", "required"])?;
+
+        if required && default.is_some() {
+            bail!("required field `{name}` cannot have a default value");
+        }
+
+        let mut field = Self {
+            name,
+            types,
+            default: Value::None,
+            positional,
+            required,
+        };
+
+        if let Some(default) = default {
+            field.default = field.check(default).map_err(|err| {
+                eco_format!("invalid default for `{}`: {err}", field.name)
+            })?;
+        }
+
+        Ok(field)
+    }
+
+    /// Take the values of the given fields from the arguments.
+    ///
+    /// Fails if a required field is missing. Fields that are not given are
+    /// left out.
+    pub fn take_all(fields: &[Self], args: &mut Args) -> SourceResult<Dict> {
+        let mut values = Dict::new();
+        for field in fields {
+            let value = if field.positional {
+                if field.required {
+                    Some(args.expect::<Spanned<Value>>(&field.name)?)
+                } else {
+                    args.eat::<Spanned<Value>>()?
+                }
+            } else {
+                let value = args.named::<Spanned<Value>>(&field.name)?;
+                if value.is_none() && field.required {
+                    bail!(args.span, "missing argument: {}", field.name);
+                }
+                value
+            };
+
+            if let Some(Spanned { v, span }) = value {
+                values.insert(field.name.clone().into(), field.check(v).at(span)?);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Check that a value is valid for this field, converting it to content if
+    /// necessary.
+    fn check(&self, value: Value) -> StrResult<Value> {
+        if matches!((&value, &self.default), (Value::None, Value::None)) {
+            return Ok(value);
+        }
+
+        DeclaredType::check(&self.types, value)
+    }
+}
+
+/// A type that the value of a field or parameter is declared to have.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum DeclaredType {
+    /// A built-in type.
+    Native(Type),
+    /// A user-defined type.
+    Record(RecordType),
+}
+
+impl DeclaredType {
+    /// The type of a value, as returned by `type()`.
+    ///
+    /// This differs from [`Value::ty`] for records, whose native type is the
+    /// generic record type.
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Record(record) => Self::Record(record.ty().clone()),
+            value => Self::Native(value.ty()),
+        }
+    }
+
+    /// Whether the value has this type.
+    pub fn matches(&self, value: &Value) -> bool {
+        Self::of(value) == *self
+    }
+
+    /// Check that a value has one of the given types, converting it to content
+    /// if necessary. If no types are given, any value is valid.
+    pub fn check(types: &[Self], value: Value) -> StrResult<Value> {
+        if types.is_empty() || types.iter().any(|ty| ty.matches(&value)) {
+            return Ok(value);
+        }
+
+        let content = Self::Native(Type::of::<Content>());
+        if types.contains(&content) && Content::castable(&value) {
+            return Content::from_value(value)
+                .map(Value::Content)
+                .map_err(|err| err.message().clone());
+        }
+
+        // If only built-in types are involved, we can produce the usual cast
+        // error, including its hints.
+        let native = types
+            .iter()
+            .map(|ty| match ty {
+                Self::Native(ty) => Some(CastInfo::Type(*ty)),
+                Self::Record(_) => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(native) = native {
+            return Err(CastInfo::Union(native).error(&value).message().clone());
+        }
+
+        let expected = types.iter().map(|ty| eco_format!("{ty}")).collect::<Vec<_>>();
+        Err(eco_format!(
+            "expected {}, found {}",
+            repr::separated_list(&expected, "or"),
+            Self::of(&value),
+        ))
+    }
+}
+
+impl Display for DeclaredType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Native(ty) => Display::fmt(ty, f),
+            Self::Record(ty) => f.pad(ty.name()),
+        }
+    }
+}
+
+cast! {
+    DeclaredType,
+    self => match self {
+        Self::Native(ty) => ty.into_value(),
+        Self::Record(ty) => ty.into_value(),
+    },
+    v: Type => Self::Native(v),
+    v: RecordType => Self::Record(v),
+    _: NoneValue => Self::Native(Type::of::<NoneValue>()),
+    _: AutoValue => Self::Native(Type::of::<AutoValue>()),
+}
+
+/// An instance of a user-defined element.
+#[elem(Construct, Repr, Locatable, Synthesize, Show, Count)]
+pub struct CustomElem {
+    /// The element this is an instance of.
+    #[required]
+    #[internal]
+    pub element: CustomElement,
+
+    /// The values of the element's fields.
+    #[required]
+    #[internal]
+    pub values: Dict,
+
+    /// Field values configured through set rules, for all user-defined
+    /// elements.
+    #[internal]
+    #[fold]
+    #[ghost]
+    pub settings: CustomSettings,
+}
+
+impl CustomElem {
+    /// Get a field by name.
+    pub fn get(&self, name: &str) -> Result<Value, FieldAccessError> {
+        match self.values.get(name) {
+            Ok(value) => Ok(value.clone()),
+            Err(_) if self.element.field(name).is_some() => Err(FieldAccessError::Unset),
+            Err(_) => Err(FieldAccessError::Unknown),
+        }
+    }
+}
+
+impl Construct for CustomElem {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually")
+    }
+}
+
+impl Repr for CustomElem {
+    fn repr(&self) -> EcoString {
+        let fields = self
+            .values
+            .iter()
+            .map(|(name, value)| eco_format!("{}: {}", name, value.repr()))
+            .collect::<Vec<_>>();
+        eco_format!("{}{}", self.element.name(), repr::pretty_array_like(&fields, false))
+    }
+}
+
+impl Synthesize for Packed<CustomElem> {
+    fn synthesize(&mut self, _: &mut Engine, styles: StyleChain) -> SourceResult<()> {
+        let settings = CustomElem::settings_in(styles);
+        let element = self.element.clone();
+        let elem = self.as_mut();
+        for field in element.fields() {
+            if elem.values.contains(&field.name) {
+                continue;
+            }
+
+            let value = settings
+                .get(&element, &field.name)
+                .cloned()
+                .unwrap_or_else(|| field.default.clone());
+            elem.values.insert(field.name.clone().into(), value);
+        }
+        Ok(())
+    }
+}
+
+impl Show for Packed<CustomElem> {
+    #[typst_macros::time(name = "custom element", span = self.span())]
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let Some(display) = self.element.display() else {
+            return Ok(Content::empty());
+        };
+
+        let context = Context::new(self.location(), Some(styles));
+        Ok(display
+            .call(engine, context.track(), [self.clone().pack()])?
+            .display())
+    }
+}
+
+impl Count for Packed<CustomElem> {
+    fn update(&self) -> Option<CounterUpdate> {
+        match self.values.get("numbering") {
+            Ok(Value::None) if self.element.field("numbering").is_some() => None,
+            _ => Some(CounterUpdate::Step(NonZeroUsize::ONE)),
+        }
+    }
+}
+
+/// Field values of user-defined elements that were configured through set
+/// rules.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct CustomSettings(Vec<(CustomElement, Dict)>);
+
+impl CustomSettings {
+    /// Get the innermost set value of a field of the given element.
+    pub fn get(&self, element: &CustomElement, name: &str) -> Option<&Value> {
+        self.0
+            .iter()
+            .rev()
+            .filter(|(elem, _)| elem == element)
+            .find_map(|(_, values)| values.get(name).ok())
+    }
+}
+
+impl Fold for CustomSettings {
+    fn fold(self, outer: Self) -> Self {
+        Self(self.0.fold(outer.0))
+    }
+}
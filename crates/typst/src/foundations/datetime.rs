@@ -2,16 +2,21 @@ use std::cmp::Ordering;
 use std::hash::Hash;
 use std::ops::{Add, Sub};
 
+use comemo::Tracked;
 use ecow::{eco_format, EcoString, EcoVec};
-use time::error::{Format, InvalidFormatDescription};
+use time::error::{Format, InvalidFormatDescription, Parse};
+use time::format_description::modifier::{MonthRepr, WeekdayRepr};
+use time::format_description::{Component, OwnedFormatItem};
 use time::macros::format_description;
-use time::{format_description, Month, PrimitiveDateTime};
+use time::{format_description, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
 
 use crate::diag::{bail, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, func, repr, scope, ty, Dict, Duration, Repr, Smart, Str, Value,
+    cast, func, repr, scope, ty, Cast, Context, Dict, Duration, Repr, Smart, Str,
+    StyleChain, Value,
 };
+use crate::text::{localized_str, localized_term, Lang, Region, TextElem};
 use crate::World;
 
 /// Represents a date, a time, or a combination of both.
@@ -93,9 +98,9 @@ use crate::World;
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     month is padded.
 ///   - `repr`: Can be either `numerical`, `long` or `short`. Specifies if the
-///     month should be displayed as a number or a word. Unfortunately, when
-///     choosing the word representation, it can currently only display the
-///     English version. In the future, it is planned to support localization.
+///     month should be displayed as a number or a word. The word is displayed
+///     in the [text language]($text.lang) if the datetime is displayed within
+///     [context] and in English otherwise.
 /// - `day`: Displays the day of the datetime.
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     day is padded.
@@ -107,10 +112,10 @@ use crate::World;
 ///      and 53.
 /// - `weekday`: Displays the weekday of the date.
 ///   - `repr` Can be either `long`, `short`, `sunday` or `monday`. In the case
-///     of `long` and `short`, the corresponding English name will be displayed
-///     (same as for the month, other languages are currently not supported). In
-///     the case of `sunday` and `monday`, the numerical value will be displayed
-///     (assuming Sunday and Monday as the first day of the week, respectively).
+///     of `long` and `short`, the name of the weekday will be displayed (in the
+///     same language as the month). In the case of `sunday` and `monday`, the
+///     numerical value will be displayed (assuming Sunday and Monday as the
+///     first day of the week, respectively).
 ///   - `one_indexed`: Can be either `true` or `false`. Defines whether the
 ///     numerical representation of the week starts with 0 or 1.
 /// - `hour`: Displays the hour of the date.
@@ -128,6 +133,10 @@ use crate::World;
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     second is padded.
 ///
+/// The same format can be used to [parse]($datetime.parse) a datetime from a
+/// string. When parsing, month and weekday names are always expected in
+/// English.
+///
 /// Keep in mind that not always all components can be used. For example, if you
 /// create a new datetime with `{datetime(year: 2023, month: 10, day: 13)}`, it
 /// will be stored as a plain date internally, meaning that you cannot use
@@ -229,6 +238,90 @@ impl Datetime {
             Datetime::Time(_) => "time",
         }
     }
+
+    /// Format the datetime, writing month and weekday names in the language
+    /// of the style chain, if any.
+    fn format_into(
+        &self,
+        out: &mut EcoString,
+        item: &OwnedFormatItem,
+        styles: Option<StyleChain>,
+    ) -> StrResult<()> {
+        let name = |key: EcoString| match styles {
+            Some(styles) => localized_term(styles, &key),
+            None => localized_str(Lang::ENGLISH, None, &key).into(),
+        };
+        let insufficient =
+            || EcoString::from("failed to format datetime (insufficient information)");
+
+        match item {
+            OwnedFormatItem::Compound(items) => {
+                for item in items.iter() {
+                    self.format_into(out, item, styles)?;
+                }
+            }
+            // When formatting, optional items are always displayed and the
+            // first alternative is used.
+            OwnedFormatItem::Optional(item) => self.format_into(out, item, styles)?,
+            OwnedFormatItem::First(items) => {
+                if let Some(item) = items.first() {
+                    self.format_into(out, item, styles)?;
+                }
+            }
+            OwnedFormatItem::Component(Component::Month(month))
+                if month.repr != MonthRepr::Numerical =>
+            {
+                let number = self.month().ok_or_else(insufficient)?;
+                out.push_str(&match month.repr {
+                    MonthRepr::Short => name(eco_format!("month-short-{number}")),
+                    _ => name(eco_format!("month-{number}")),
+                });
+            }
+            OwnedFormatItem::Component(Component::Weekday(weekday))
+                if matches!(weekday.repr, WeekdayRepr::Long | WeekdayRepr::Short) =>
+            {
+                let number = self.weekday().ok_or_else(insufficient)?;
+                out.push_str(&match weekday.repr {
+                    WeekdayRepr::Short => name(eco_format!("weekday-short-{number}")),
+                    _ => name(eco_format!("weekday-{number}")),
+                });
+            }
+            item => {
+                let formatted = match self {
+                    Self::Date(date) => date.format(item),
+                    Self::Time(time) => time.format(item),
+                    Self::Datetime(datetime) => datetime.format(item),
+                };
+                out.push_str(&formatted.map_err(format_time_format_error)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The conventional pattern for displaying this kind of datetime in the
+    /// given style and the language and region of the style chain.
+    fn style_pattern(
+        &self,
+        style: DisplayStyle,
+        styles: Option<StyleChain>,
+    ) -> EcoString {
+        let (lang, region) = match styles {
+            Some(styles) => (TextElem::lang_in(styles), TextElem::region_in(styles)),
+            None => (Lang::ENGLISH, None),
+        };
+
+        let date = date_pattern(style, lang, region);
+        let time = time_pattern(style, lang, region);
+        match self {
+            Self::Date(_) => date.into(),
+            Self::Time(_) => time.into(),
+            Self::Datetime(_) => match lang.as_str() {
+                "ja" | "zh" => eco_format!("{date} {time}"),
+                _ => eco_format!("{date}, {time}"),
+            },
+        }
+    }
 }
 
 #[scope]
@@ -332,6 +425,66 @@ impl Datetime {
             .ok_or("unable to get the current date")?)
     }
 
+    /// Parses a datetime from a string.
+    ///
+    /// Without a pattern, the string is expected in
+    /// [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) format. Dates
+    /// (`2024-03-01`), times (`14:30` or `14:30:15`), and combinations of both
+    /// (`2024-03-01T14:30:15`) are supported. Combinations may end with a
+    /// UTC offset, as in `2024-03-01T14:30:15+02:00` or `2024-03-01T12:30Z`.
+    ///
+    /// With a pattern, the string must match it exactly. See the
+    /// [format syntax]($datetime/#format) for more information. Depending on
+    /// the components in the pattern, the result is a date, a time, or both.
+    ///
+    /// ```example
+    /// #datetime.parse("2024-03-01").display() \
+    /// #datetime.parse(
+    ///   "01.03.2024 14:30",
+    ///   "[day].[month].[year] [hour]:[minute]",
+    /// ).display() \
+    /// #datetime.parse(
+    ///   "2024-03-01T14:30:00+02:00",
+    ///   offset: duration(hours: 5, minutes: 30),
+    /// ).display()
+    /// ```
+    #[func]
+    pub fn parse(
+        /// The string to parse.
+        string: Str,
+        /// The format the string is in.
+        #[default]
+        pattern: Smart<DisplayPattern>,
+        /// The UTC offset to convert the datetime to, either in hours or as a
+        /// [duration] for offsets like `{duration(hours: 5, minutes: 30)}`. It
+        /// must be less than 24 hours in either direction.
+        ///
+        /// Only applies if the string specifies an offset. If set to `{auto}`,
+        /// the datetime is kept as written and the offset is discarded.
+        #[named]
+        #[default]
+        offset: Smart<Offset>,
+    ) -> StrResult<Datetime> {
+        let (datetime, parsed_offset) = match pattern {
+            Smart::Auto => parse_iso(&string).ok_or_else(|| {
+                eco_format!(
+                    "failed to parse datetime ({} is not in ISO 8601 format)",
+                    string.repr()
+                )
+            })?,
+            Smart::Custom(DisplayPattern(_, format)) => parse_pattern(&string, &format)?,
+        };
+
+        let (Smart::Custom(Offset(to)), Some(from), Datetime::Datetime(datetime)) =
+            (offset, parsed_offset, datetime)
+        else {
+            return Ok(datetime);
+        };
+
+        let converted = datetime.assume_offset(from).to_offset(to);
+        Ok(Datetime::Datetime(PrimitiveDateTime::new(converted.date(), converted.time())))
+    }
+
     /// Displays the datetime in a specified format.
     ///
     /// Depending on whether you have defined just a date, a time or both, the
@@ -341,30 +494,57 @@ impl Datetime {
     /// `[[year]-[month]-[day] [hour]:[minute]:[second]]`.
     ///
     /// See the [format syntax]($datetime/#format) for more information.
+    ///
+    /// When called within [context], names of months and weekdays are
+    /// displayed in the [text language]($text.lang). Month names are in the
+    /// form used within a date, which is the genitive in languages like
+    /// Russian or Polish. For languages that Typst has no names for, English
+    /// names are used. All names can be adjusted with the text function's
+    /// [`translations`]($text.translations) parameter.
+    ///
+    /// ```example
+    /// #let date = datetime(year: 2024, month: 3, day: 1)
+    /// #set text(lang: "de")
+    /// #context date.display(style: "long") \
+    /// #context date.display("[weekday], [day padding:none]. [month repr:long]")
+    /// ```
     #[func]
     pub fn display(
         &self,
+        /// The callsite context.
+        context: Tracked<Context>,
         /// The format used to display the datetime.
         #[default]
         pattern: Smart<DisplayPattern>,
+        /// A conventional format for the text language and region to use
+        /// instead of a pattern.
+        #[named]
+        style: Option<DisplayStyle>,
     ) -> StrResult<EcoString> {
-        let pat = |s| format_description::parse_borrowed::<2>(s).unwrap();
-        let result = match pattern {
-            Smart::Auto => match self {
-                Self::Date(date) => date.format(&pat("[year]-[month]-[day]")),
-                Self::Time(time) => time.format(&pat("[hour]:[minute]:[second]")),
-                Self::Datetime(datetime) => {
-                    datetime.format(&pat("[year]-[month]-[day] [hour]:[minute]:[second]"))
-                }
-            },
-
-            Smart::Custom(DisplayPattern(_, format)) => match self {
-                Self::Date(date) => date.format(&format),
-                Self::Time(time) => time.format(&format),
-                Self::Datetime(datetime) => datetime.format(&format),
-            },
+        let styles = context.styles().ok();
+        let format = match (pattern, style) {
+            (Smart::Custom(_), Some(_)) => {
+                bail!("cannot specify both a pattern and a style")
+            }
+            (Smart::Custom(DisplayPattern(_, format)), None) => format,
+            (Smart::Auto, style) => {
+                let pattern = match style {
+                    Some(style) => self.style_pattern(style, styles),
+                    None => match self {
+                        Self::Date(_) => "[year]-[month]-[day]".into(),
+                        Self::Time(_) => "[hour]:[minute]:[second]".into(),
+                        Self::Datetime(_) => {
+                            "[year]-[month]-[day] [hour]:[minute]:[second]".into()
+                        }
+                    },
+                };
+                format_description::parse_owned::<2>(&pattern).unwrap()
+            }
         };
-        result.map(EcoString::from).map_err(format_time_format_error)
+
+        let mut out = EcoString::new();
+        self.format_into(&mut out, &format, styles)?;
+        Ok(out)
     }
 
     /// The year if it was specified, or `{none}` for times without a date.
@@ -533,6 +713,162 @@ cast! {
     v: u8 => Self::try_from(v).map_err(|_| "month is invalid")?
 }
 
+/// A UTC offset, given in hours or as a duration.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Offset(UtcOffset);
+
+impl Offset {
+    /// Create an offset from a number of seconds, which must amount to less
+    /// than a day.
+    fn from_seconds(seconds: i64) -> StrResult<Self> {
+        i32::try_from(seconds)
+            .ok()
+            .filter(|seconds| seconds.unsigned_abs() < 24 * 60 * 60)
+            .and_then(|seconds| UtcOffset::from_whole_seconds(seconds).ok())
+            .map(Self)
+            .ok_or_else(|| "offset must be less than 24 hours".into())
+    }
+}
+
+cast! {
+    Offset,
+    self => Duration::from(time::Duration::seconds(self.0.whole_seconds().into()))
+        .into_value(),
+    v: i64 => Self::from_seconds(v.saturating_mul(60 * 60))?,
+    v: Duration => Self::from_seconds(time::Duration::from(v).whole_seconds())?,
+}
+
+/// A conventional format in which a datetime can be displayed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum DisplayStyle {
+    /// With the name of the month, as in "March 1, 2024".
+    Long,
+    /// With the abbreviated name of the month, as in "Mar 1, 2024".
+    Medium,
+    /// Only with numbers, as in "3/1/24".
+    Short,
+}
+
+/// The conventional pattern for a date in the given language and region.
+fn date_pattern(style: DisplayStyle, lang: Lang, region: Option<Region>) -> &'static str {
+    let us = region.map_or(true, |region| region == "US");
+    match (style, lang.as_str()) {
+        (_, "ja" | "zh") if style != DisplayStyle::Short => {
+            "[year]年[month padding:none]月[day padding:none]日"
+        }
+        (_, "ja" | "zh") => "[year]/[month]/[day]",
+        (DisplayStyle::Long, "en") if us => {
+            "[month repr:long] [day padding:none], [year]"
+        }
+        (DisplayStyle::Medium, "en") if us => {
+            "[month repr:short] [day padding:none], [year]"
+        }
+        (DisplayStyle::Short, "en") if us => {
+            "[month padding:none]/[day padding:none]/[year repr:last_two]"
+        }
+        (DisplayStyle::Long, "de" | "da" | "nb" | "nn") => {
+            "[day padding:none]. [month repr:long] [year]"
+        }
+        (DisplayStyle::Medium, "de" | "da" | "nb" | "nn") => {
+            "[day padding:none]. [month repr:short] [year]"
+        }
+        (DisplayStyle::Short, "de" | "da" | "nb" | "nn" | "fi" | "cs" | "pl" | "ru") => {
+            "[day].[month].[year]"
+        }
+        (DisplayStyle::Long, "es" | "pt") => {
+            "[day padding:none] de [month repr:long] de [year]"
+        }
+        (DisplayStyle::Long, _) => "[day padding:none] [month repr:long] [year]",
+        (DisplayStyle::Medium, _) => "[day padding:none] [month repr:short] [year]",
+        (DisplayStyle::Short, "nl") => "[day]-[month]-[year]",
+        (DisplayStyle::Short, "sv") => "[year]-[month]-[day]",
+        (DisplayStyle::Short, _) => "[day]/[month]/[year]",
+    }
+}
+
+/// The conventional pattern for a time in the given language and region.
+fn time_pattern(style: DisplayStyle, lang: Lang, region: Option<Region>) -> &'static str {
+    let twelve_hour =
+        lang == Lang::ENGLISH && region.map_or(true, |region| region == "US");
+    match (style, twelve_hour) {
+        (DisplayStyle::Short, true) => "[hour repr:12 padding:none]:[minute] [period]",
+        (_, true) => "[hour repr:12 padding:none]:[minute]:[second] [period]",
+        (DisplayStyle::Short, false) => "[hour]:[minute]",
+        (_, false) => "[hour]:[minute]:[second]",
+    }
+}
+
+/// Parse a datetime in ISO 8601 format, along with its UTC offset, if any.
+fn parse_iso(string: &str) -> Option<(Datetime, Option<UtcOffset>)> {
+    let pat = |s: &str| format_description::parse_owned::<2>(s).unwrap();
+    let date = "[year]-[month]-[day]";
+    let time = "[hour]:[minute][optional [:[second][optional [.[subsecond]]]]]";
+    let datetime = eco_format!("{date}T{time}");
+
+    // The date and time may also be separated by a lowercase `t` or a space.
+    let string = string.replacen(['t', ' '], "T", 1);
+    if let Some(rest) = string.strip_suffix(['Z', 'z']) {
+        let datetime = PrimitiveDateTime::parse(rest, &pat(&datetime)).ok()?;
+        return Some((Datetime::Datetime(datetime), Some(UtcOffset::UTC)));
+    }
+
+    let with_offset =
+        eco_format!("{datetime}[offset_hour sign:mandatory]:[offset_minute]");
+    if let Ok(datetime) = OffsetDateTime::parse(&string, &pat(&with_offset)) {
+        let primitive = PrimitiveDateTime::new(datetime.date(), datetime.time());
+        return Some((Datetime::Datetime(primitive), Some(datetime.offset())));
+    }
+
+    if let Ok(datetime) = PrimitiveDateTime::parse(&string, &pat(&datetime)) {
+        Some((Datetime::Datetime(datetime), None))
+    } else if let Ok(date) = time::Date::parse(&string, &pat(date)) {
+        Some((Datetime::Date(date), None))
+    } else if let Ok(time) = time::Time::parse(&string, &pat(time)) {
+        Some((Datetime::Time(time), None))
+    } else {
+        None
+    }
+}
+
+/// Parse a datetime according to a pattern, along with its UTC offset, if the
+/// pattern contains one.
+fn parse_pattern(
+    string: &str,
+    format: &OwnedFormatItem,
+) -> StrResult<(Datetime, Option<UtcOffset>)> {
+    // The kind of the result depends on which components the pattern
+    // contains, so we try all of them, from most to least specific.
+    let mut errors = vec![];
+    match OffsetDateTime::parse(string, format) {
+        Ok(datetime) => {
+            let primitive = PrimitiveDateTime::new(datetime.date(), datetime.time());
+            return Ok((Datetime::Datetime(primitive), Some(datetime.offset())));
+        }
+        Err(err) => errors.push(err),
+    }
+    match PrimitiveDateTime::parse(string, format) {
+        Ok(datetime) => return Ok((Datetime::Datetime(datetime), None)),
+        Err(err) => errors.push(err),
+    }
+    match time::Date::parse(string, format) {
+        Ok(date) => return Ok((Datetime::Date(date), None)),
+        Err(err) => errors.push(err),
+    }
+    match time::Time::parse(string, format) {
+        Ok(time) => return Ok((Datetime::Time(time), None)),
+        Err(err) => errors.push(err),
+    }
+
+    // Missing components are the expected failure for all but one kind, so
+    // a mismatch between the string and the pattern is more interesting.
+    match errors.iter().find(|err| !matches!(err, Parse::TryFromParsed(_))) {
+        Some(err) => bail!("failed to parse datetime ({err})"),
+        None => bail!(
+            "failed to parse datetime (pattern describes neither a date nor a time)"
+        ),
+    }
+}
+
 /// Format the `Format` error of the time crate in an appropriate way.
 fn format_time_format_error(error: Format) -> EcoString {
     match error {
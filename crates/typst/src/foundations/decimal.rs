@@ -2,11 +2,14 @@ use std::fmt::{self, Display, Formatter};
 use std::ops::Neg;
 use std::str::FromStr;
 
+use comemo::Tracked;
 use ecow::{eco_format, EcoString};
 use rust_decimal::MathematicalOps;
 
 use crate::diag::{warning, At, SourceResult};
-use crate::foundations::{cast, func, repr, scope, ty, Engine, Repr, Str};
+use crate::foundations::{
+    cast, func, repr, scope, ty, Args, Context, Engine, NumberFormat, Repr, Str,
+};
 use crate::syntax::{ast, Span, Spanned};
 use crate::World;
 
@@ -278,6 +281,34 @@ impl Decimal {
             }
         }
     }
+
+    /// Formats the decimal for display according to the conventions of the
+    /// [text language]($text.lang) and [region]($text.region).
+    ///
+    /// This works just like [`int.format`]($int.format), but without any loss
+    /// of precision.
+    ///
+    /// ```example
+    /// #let amount = decimal("1234567.895")
+    /// #amount.format(currency: "USD") \
+    /// #set text(lang: "de")
+    /// #context amount.format(currency: "EUR")
+    /// ```
+    #[func]
+    pub fn format(
+        self,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The formatting options. These are the same as for
+        /// [`int.format`]($int.format).
+        args: &mut Args,
+    ) -> SourceResult<Str> {
+        let format = NumberFormat::from_args(args)?;
+        format
+            .format_decimal(&self.0.to_string(), context.styles().ok())
+            .map(Into::into)
+            .at(args.span)
+    }
 }
 
 /// Emits a warning when a decimal is constructed from a float literal.
@@ -1,10 +1,12 @@
 use std::num::ParseFloatError;
 
+use comemo::Tracked;
 use ecow::{eco_format, EcoString};
 
-use crate::diag::StrResult;
+use crate::diag::{At, SourceResult, StrResult};
 use crate::foundations::{
-    bail, cast, func, repr, scope, ty, Bytes, Decimal, Endianness, Repr, Str,
+    bail, cast, func, repr, scope, ty, Args, Bytes, Context, Decimal, Endianness,
+    NumberFormat, Repr, Str,
 };
 use crate::layout::Ratio;
 
@@ -110,6 +112,36 @@ impl f64 {
         f64::signum(self)
     }
 
+    /// Formats the float for display according to the conventions of the
+    /// [text language]($text.lang) and [region]($text.region).
+    ///
+    /// This works just like [`int.format`]($int.format). Infinite and NaN
+    /// values are displayed as `∞` and `NaN`.
+    ///
+    /// ```example
+    /// #let x = 1234567.891
+    /// #x.format(digits: 2) \
+    /// #(0.125).format(percent: true) \
+    /// #x.format(notation: "scientific", significant: 3) \
+    /// #set text(lang: "de")
+    /// #context x.format(digits: 2)
+    /// ```
+    #[func]
+    pub fn format(
+        self,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The formatting options. These are the same as for
+        /// [`int.format`]($int.format).
+        args: &mut Args,
+    ) -> SourceResult<Str> {
+        let format = NumberFormat::from_args(args)?;
+        format
+            .format_float(self, context.styles().ok())
+            .map(Into::into)
+            .at(args.span)
+    }
+
     /// Converts bytes to a float.
     ///
     /// ```example
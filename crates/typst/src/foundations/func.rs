@@ -8,8 +8,8 @@ use once_cell::sync::Lazy;
 use crate::diag::{bail, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, repr, scope, ty, Args, CastInfo, Content, Context, Element, IntoArgs, Scope,
-    Selector, Type, Value,
+    cast, repr, scope, ty, Args, CastInfo, Content, Context, CustomElement, DeclaredType,
+    Element, IntoArgs, Record, RecordType, Scope, Selector, Type, Value,
 };
 use crate::syntax::{ast, Span, SyntaxNode};
 use crate::utils::{singleton, LazyHash, Static};
@@ -56,7 +56,8 @@ pub use typst_macros::func;
 /// [tables]($table). When called, these create an element of their respective
 /// kind. In contrast to normal functions, they can further be used in [set
 /// rules]($styling/#set-rules), [show rules]($styling/#show-rules), and
-/// [selectors]($selector).
+/// [selectors]($selector). You can define your own element functions with the
+/// [`element`] function.
 ///
 /// # Function scopes
 /// Functions can hold related definitions in their own scope, similar to a
@@ -106,6 +107,28 @@ pub use typst_macros::func;
 /// ]
 /// ```
 ///
+/// # Type annotations
+/// Parameters can optionally be annotated with the types they accept by
+/// writing `as` and one or more types separated by `|` after the parameter's
+/// name. For named parameters, the annotation comes before the colon. Likewise,
+/// the return value can be annotated with `->` and its types after the
+/// parameter list. Besides built-in types, annotations can name
+/// [user-defined types]($record) as well as `{none}` and `{auto}`.
+///
+/// Annotations are checked whenever the function is called. An argument of
+/// the wrong type results in an error at the argument, instead of somewhere
+/// deep inside the function. As with built-in functions, strings and symbols
+/// are accepted for parameters of type [`content`].
+///
+/// ```example
+/// #let badge(body as content, fill as color | auto: auto) -> content = {
+///   let fill = if fill == auto { aqua } else { fill }
+///   box(inset: 3pt, fill: fill, body)
+/// }
+///
+/// #badge[New] #badge("Hot", fill: red)
+/// ```
+///
 /// # Importing functions
 /// Functions can be imported from one file ([`module`]($scripting/#modules)) into
 /// another using `{import}`. For example, assume that we have defined the `alert`
@@ -150,6 +173,10 @@ enum Repr {
     Native(Static<NativeFuncData>),
     /// A function for an element.
     Element(Element),
+    /// A function for a user-defined element.
+    Custom(CustomElement),
+    /// The constructor of a user-defined type.
+    Record(RecordType),
     /// A user-defined closure.
     Closure(Arc<LazyHash<Closure>>),
     /// A nested function with pre-applied arguments.
@@ -164,6 +191,8 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(native.name),
             Repr::Element(elem) => Some(elem.name()),
+            Repr::Custom(elem) => Some(elem.name()),
+            Repr::Record(ty) => Some(ty.name()),
             Repr::Closure(closure) => closure.name(),
             Repr::With(with) => with.0.name(),
         }
@@ -176,6 +205,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(native.title),
             Repr::Element(elem) => Some(elem.title()),
+            Repr::Custom(_) | Repr::Record(_) => None,
             Repr::Closure(_) => None,
             Repr::With(with) => with.0.title(),
         }
@@ -186,6 +216,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(native.docs),
             Repr::Element(elem) => Some(elem.docs()),
+            Repr::Custom(_) | Repr::Record(_) => None,
             Repr::Closure(_) => None,
             Repr::With(with) => with.0.docs(),
         }
@@ -204,6 +235,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(&native.0.params),
             Repr::Element(elem) => Some(elem.params()),
+            Repr::Custom(_) | Repr::Record(_) => None,
             Repr::Closure(_) => None,
             Repr::With(with) => with.0.params(),
         }
@@ -218,9 +250,12 @@ impl Func {
     pub fn returns(&self) -> Option<&'static CastInfo> {
         match &self.repr {
             Repr::Native(native) => Some(&native.0.returns),
-            Repr::Element(_) => {
+            Repr::Element(_) | Repr::Custom(_) => {
                 Some(singleton!(CastInfo, CastInfo::Type(Type::of::<Content>())))
             }
+            Repr::Record(_) => {
+                Some(singleton!(CastInfo, CastInfo::Type(Type::of::<Record>())))
+            }
             Repr::Closure(_) => None,
             Repr::With(with) => with.0.returns(),
         }
@@ -231,6 +266,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => native.keywords,
             Repr::Element(elem) => elem.keywords(),
+            Repr::Custom(_) | Repr::Record(_) => &[],
             Repr::Closure(_) => &[],
             Repr::With(with) => with.0.keywords(),
         }
@@ -241,6 +277,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(&native.0.scope),
             Repr::Element(elem) => Some(elem.scope()),
+            Repr::Custom(_) | Repr::Record(_) => None,
             Repr::Closure(_) => None,
             Repr::With(with) => with.0.scope(),
         }
@@ -267,6 +304,31 @@ impl Func {
         }
     }
 
+    /// Extract the user-defined element, if it is one.
+    pub fn custom_element(&self) -> Option<&CustomElement> {
+        match &self.repr {
+            Repr::Custom(elem) => Some(elem),
+            _ => None,
+        }
+    }
+
+    /// Extract the user-defined type, if this is its constructor.
+    pub fn record_type(&self) -> Option<&RecordType> {
+        match &self.repr {
+            Repr::Record(ty) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Extract the closure, if this is a user-defined closure.
+    pub fn closure(&self) -> Option<&Closure> {
+        match &self.repr {
+            Repr::Closure(closure) => Some(&***closure),
+            Repr::With(with) => with.0.closure(),
+            _ => None,
+        }
+    }
+
     /// Call the function with the given context and arguments.
     pub fn call<A: IntoArgs>(
         &self,
@@ -296,17 +358,39 @@ impl Func {
                 args.finish()?;
                 Ok(Value::Content(value))
             }
-            Repr::Closure(closure) => crate::eval::call_closure(
-                self,
-                closure,
-                engine.world,
-                engine.introspector,
-                engine.traced,
-                TrackedMut::reborrow_mut(&mut engine.sink),
-                engine.route.track(),
-                context,
-                args,
-            ),
+            Repr::Custom(elem) => {
+                let value = elem.construct(&mut args)?;
+                args.finish()?;
+                Ok(Value::Content(value))
+            }
+            Repr::Record(ty) => {
+                let value = ty.construct(&mut args)?;
+                args.finish()?;
+                Ok(Value::Record(value))
+            }
+            Repr::Closure(closure) => {
+                let kind = if closure.node.is::<ast::Closure>() {
+                    "function"
+                } else {
+                    "context"
+                };
+                let _scope = typst_timing::ProfileScope::new(
+                    kind,
+                    closure.name(),
+                    closure.node.span(),
+                );
+                crate::eval::call_closure(
+                    self,
+                    closure,
+                    engine.world,
+                    engine.introspector,
+                    engine.traced,
+                    TrackedMut::reborrow_mut(&mut engine.sink),
+                    engine.route.track(),
+                    context,
+                    args,
+                )
+            }
             Repr::With(with) => {
                 args.items = with.1.items.iter().cloned().chain(args.items).collect();
                 with.0.call(engine, context, args)
@@ -372,6 +456,10 @@ impl Func {
         let fields = args.to_named();
         args.items.retain(|arg| arg.name.is_none());
 
+        if let Some(custom) = self.custom_element() {
+            return custom.where_(fields);
+        }
+
         let element = self
             .element()
             .ok_or("`where()` can only be called on element functions")?;
@@ -435,6 +523,18 @@ impl From<Element> for Func {
     }
 }
 
+impl From<CustomElement> for Func {
+    fn from(elem: CustomElement) -> Self {
+        Repr::Custom(elem).into()
+    }
+}
+
+impl From<RecordType> for Func {
+    fn from(ty: RecordType) -> Self {
+        Repr::Record(ty).into()
+    }
+}
+
 /// A Typst function that is defined by a native Rust type that shadows a
 /// native Rust function.
 pub trait NativeFunc {
@@ -519,6 +619,12 @@ pub struct Closure {
     pub captured: Scope,
     /// The number of positional parameters in the closure.
     pub num_pos_params: usize,
+    /// The declared types of the parameters other than the argument sink, in
+    /// order. Empty for parameters without a type annotation.
+    pub param_types: Vec<Vec<DeclaredType>>,
+    /// The declared types of the return value. Empty if there is no return
+    /// type annotation.
+    pub return_types: Vec<DeclaredType>,
 }
 
 impl Closure {
@@ -1,10 +1,12 @@
 use std::num::{NonZeroI64, NonZeroIsize, NonZeroU64, NonZeroUsize, ParseIntError};
 
+use comemo::Tracked;
 use ecow::{eco_format, EcoString};
 
-use crate::diag::StrResult;
+use crate::diag::{At, SourceResult, StrResult};
 use crate::foundations::{
-    bail, cast, func, repr, scope, ty, Bytes, Cast, Decimal, Repr, Str, Value,
+    bail, cast, func, repr, scope, ty, Args, Bytes, Cast, Context, Decimal, Notation,
+    NumberFormat, Repr, Smart, Str, Value,
 };
 
 /// A whole number.
@@ -217,6 +219,77 @@ impl i64 {
         }
     }
 
+    /// Formats the integer for display according to the conventions of the
+    /// [text language]($text.lang) and [region]($text.region).
+    ///
+    /// When called outside of [context], English conventions are used.
+    ///
+    /// ```example
+    /// #let n = 1234567
+    /// #n.format() \
+    /// #n.format(notation: "engineering") \
+    /// #n.format(significant: 2) \
+    /// #set text(lang: "de")
+    /// #context n.format(currency: "EUR")
+    /// ```
+    #[func]
+    pub fn format(
+        self,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The real arguments (the other arguments are just for the docs, the
+        /// other number types share them, so we parse them manually).
+        args: &mut Args,
+        /// The number of digits to display after the decimal separator. The
+        /// number is rounded half away from zero if necessary. At most 28
+        /// digits can be requested.
+        ///
+        /// If set to `{auto}`, amounts of a currency are displayed with the
+        /// conventional number of digits for the currency and other numbers
+        /// with as many digits as they have.
+        #[external]
+        #[named]
+        #[default]
+        digits: Smart<usize>,
+        /// The number of significant digits to round to instead of a number of
+        /// digits after the decimal separator. At most 28 significant digits
+        /// can be requested.
+        #[external]
+        #[named]
+        significant: Option<usize>,
+        /// How the number is written.
+        #[external]
+        #[named]
+        #[default(Notation::Standard)]
+        notation: Notation,
+        /// Whether to group the digits before the decimal separator, as in
+        /// `{"1,000,000"}`. If set to `{auto}`, digits are grouped according to
+        /// the conventions of the language.
+        #[external]
+        #[named]
+        #[default]
+        grouping: Smart<bool>,
+        /// Whether to display the number as a percentage. The number is
+        /// multiplied by 100 for this.
+        #[external]
+        #[named]
+        #[default(false)]
+        percent: bool,
+        /// A currency the number is an amount of. Either an
+        /// [ISO 4217](https://en.wikipedia.org/wiki/ISO_4217) code like
+        /// `{"EUR"}`, which is replaced by its symbol where one exists, or the
+        /// symbol itself.
+        #[external]
+        #[named]
+        currency: Option<EcoString>,
+    ) -> SourceResult<Str> {
+        let format = NumberFormat::from_args(args)?;
+        format
+            .format_int(self, context.styles().ok())
+            .map(Into::into)
+            .at(args.span)
+    }
+
     /// Converts bytes to an integer.
     ///
     /// ```example
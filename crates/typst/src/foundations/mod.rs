@@ -14,6 +14,7 @@ mod bytes;
 mod cast;
 mod content;
 mod context;
+mod custom;
 mod datetime;
 mod decimal;
 mod dict;
@@ -27,7 +28,9 @@ mod label;
 mod methods;
 mod module;
 mod none;
+mod number;
 mod plugin;
+mod record;
 mod scope;
 mod selector;
 mod str;
@@ -43,6 +46,7 @@ pub use self::bytes::*;
 pub use self::cast::*;
 pub use self::content::*;
 pub use self::context::*;
+pub use self::custom::*;
 pub use self::datetime::*;
 pub use self::decimal::*;
 pub use self::dict::*;
@@ -56,7 +60,9 @@ pub use self::label::*;
 pub(crate) use self::methods::*;
 pub use self::module::*;
 pub use self::none::*;
+pub use self::number::*;
 pub use self::plugin::*;
+pub use self::record::*;
 pub use self::repr::Repr;
 pub use self::scope::*;
 pub use self::selector::*;
@@ -116,6 +122,8 @@ pub(super) fn define(global: &mut Scope, inputs: Dict) {
     global.define_func::<assert>();
     global.define_func::<eval>();
     global.define_func::<style>();
+    global.define_func::<define_element>();
+    global.define_func::<define_record>();
     global.define_module(calc::module());
     global.define_module(sys::module(inputs));
 }
@@ -0,0 +1,469 @@
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, SourceResult, StrResult};
+use crate::foundations::{repr, Args, Cast, Smart, StyleChain};
+use crate::text::{Lang, Region, TextElem};
+
+/// The maximum number of digits after the decimal separator or significant
+/// digits that can be requested. This is the precision of decimals.
+const MAX_DIGITS: usize = 28;
+
+/// How a number is written.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Notation {
+    /// Written out in full, as in `1234.5`.
+    Standard,
+    /// With a single digit before the decimal separator and a power of ten,
+    /// as in `1.2345×10³`.
+    Scientific,
+    /// With one to three digits before the decimal separator and a power of
+    /// ten whose exponent is a multiple of three, as in `12.345×10³`.
+    Engineering,
+}
+
+/// Settings for formatting a number.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct NumberFormat {
+    /// The number of digits after the decimal separator.
+    pub digits: Smart<usize>,
+    /// The number of significant digits.
+    pub significant: Option<usize>,
+    /// How the number is written.
+    pub notation: Notation,
+    /// Whether to group the digits before the decimal separator.
+    pub grouping: Smart<bool>,
+    /// Whether to display the number as a percentage.
+    pub percent: bool,
+    /// The currency the number is an amount of.
+    pub currency: Option<EcoString>,
+}
+
+impl NumberFormat {
+    /// Parse the settings from the named arguments of a `format` method.
+    ///
+    /// The arguments are documented on `int.format`.
+    pub fn from_args(args: &mut Args) -> SourceResult<Self> {
+        let format = Self {
+            digits: args.named("digits")?.unwrap_or_default(),
+            significant: args.named("significant")?,
+            notation: args.named("notation")?.unwrap_or(Notation::Standard),
+            grouping: args.named("grouping")?.unwrap_or_default(),
+            percent: args.named("percent")?.unwrap_or(false),
+            currency: args.named("currency")?,
+        };
+        args.take().finish()?;
+        Ok(format)
+    }
+
+    /// Format an integer.
+    pub fn format_int(
+        &self,
+        value: i64,
+        styles: Option<StyleChain>,
+    ) -> StrResult<EcoString> {
+        self.format(Digits::from_int(value), styles)
+    }
+
+    /// Format a float. Infinity and NaN are displayed as such.
+    pub fn format_float(
+        &self,
+        value: f64,
+        styles: Option<StyleChain>,
+    ) -> StrResult<EcoString> {
+        if value.is_nan() {
+            return Ok("NaN".into());
+        } else if value.is_infinite() {
+            let sign = if value < 0.0 { repr::MINUS_SIGN } else { "" };
+            return Ok(eco_format!("{sign}∞"));
+        }
+        self.format(Digits::from_float(value), styles)
+    }
+
+    /// Format a number given by its decimal representation, as in `-12.5`.
+    pub fn format_decimal(
+        &self,
+        value: &str,
+        styles: Option<StyleChain>,
+    ) -> StrResult<EcoString> {
+        self.format(Digits::from_decimal(value), styles)
+    }
+
+    /// Format the digits of a number.
+    fn format(
+        &self,
+        mut number: Digits,
+        styles: Option<StyleChain>,
+    ) -> StrResult<EcoString> {
+        let (lang, region) = match styles {
+            Some(styles) => (TextElem::lang_in(styles), TextElem::region_in(styles)),
+            None => (Lang::ENGLISH, None),
+        };
+        let symbols = Symbols::new(lang, region);
+
+        if self.percent && self.currency.is_some() {
+            bail!("cannot display a number as both a percentage and a currency");
+        }
+
+        let rounding = match (self.digits, self.significant) {
+            (Smart::Custom(_), Some(_)) => {
+                bail!("cannot specify both the digits and the significant digits")
+            }
+            (Smart::Custom(digits), None) if digits > MAX_DIGITS => {
+                bail!("number of digits must be at most {MAX_DIGITS}")
+            }
+            (Smart::Custom(digits), None) => Rounding::Fraction(digits),
+            (Smart::Auto, Some(0)) => {
+                bail!("number of significant digits must be positive")
+            }
+            (Smart::Auto, Some(significant)) if significant > MAX_DIGITS => {
+                bail!("number of significant digits must be at most {MAX_DIGITS}")
+            }
+            (Smart::Auto, Some(significant)) => Rounding::Significant(significant),
+            (Smart::Auto, None) => match &self.currency {
+                Some(currency) => Rounding::Fraction(currency_digits(currency)),
+                None => Rounding::Shortest,
+            },
+        };
+
+        if self.percent {
+            number.exponent += 2;
+        }
+
+        // Move the decimal separator for the notation, then round. Rounding
+        // can carry into a new leading digit, which may require moving the
+        // separator once more.
+        let mut exponent = 0;
+        if self.notation != Notation::Standard && !number.is_zero() {
+            exponent = self.notation_exponent(number.leading());
+            number.exponent -= exponent;
+        }
+        let mut min_fraction = number.round(rounding);
+        if self.notation != Notation::Standard && !number.is_zero() {
+            let shift = self.notation_exponent(number.leading());
+            if shift != 0 {
+                exponent += shift;
+                number.exponent -= shift;
+                min_fraction = number.round(rounding);
+            }
+        }
+
+        let (integer, fraction) = number.split(min_fraction);
+        let grouping =
+            self.grouping.unwrap_or(true) && integer.len() >= 3 + symbols.min_grouping;
+
+        let mut out = EcoString::new();
+        if number.negative && !number.is_zero() {
+            out.push_str(repr::MINUS_SIGN);
+        }
+
+        let (prefix, suffix) = match &self.currency {
+            Some(currency) => symbols.currency(currency_symbol(currency)),
+            None if self.percent => (EcoString::new(), symbols.percent.into()),
+            None => (EcoString::new(), EcoString::new()),
+        };
+
+        out.push_str(&prefix);
+        for (i, c) in integer.chars().enumerate() {
+            if grouping && i > 0 && (integer.len() - i) % 3 == 0 {
+                out.push_str(symbols.group);
+            }
+            out.push(c);
+        }
+        if !fraction.is_empty() {
+            out.push(symbols.decimal);
+            out.push_str(&fraction);
+        }
+        if self.notation != Notation::Standard {
+            out.push_str("×10");
+            out.push_str(&superscript(exponent));
+        }
+        out.push_str(&suffix);
+
+        Ok(out)
+    }
+
+    /// The exponent by which to move the decimal separator for the notation,
+    /// given the position of the leading digit.
+    fn notation_exponent(&self, leading: i32) -> i32 {
+        match self.notation {
+            Notation::Standard => 0,
+            Notation::Scientific => leading,
+            Notation::Engineering => leading.div_euclid(3) * 3,
+        }
+    }
+}
+
+/// How to round a number.
+#[derive(Debug, Copy, Clone)]
+enum Rounding {
+    /// Keep the digits as they are.
+    Shortest,
+    /// Round to a fixed number of digits after the decimal separator.
+    Fraction(usize),
+    /// Round to a number of significant digits.
+    Significant(usize),
+}
+
+/// A number in base ten: `digits` times ten to the power of `exponent`.
+#[derive(Debug, Clone)]
+struct Digits {
+    /// Whether the number is negative.
+    negative: bool,
+    /// The digits, without leading zeros. Empty for zero.
+    digits: Vec<u8>,
+    /// The power of ten of the last digit.
+    exponent: i32,
+}
+
+impl Digits {
+    /// The digits of an integer.
+    fn from_int(value: i64) -> Self {
+        Self::parse(value < 0, &value.unsigned_abs().to_string(), 0)
+    }
+
+    /// The shortest digits that uniquely identify a finite float.
+    fn from_float(value: f64) -> Self {
+        let formatted = format!("{:e}", value.abs());
+        let (mantissa, exponent) = formatted.split_once('e').unwrap();
+        let fraction = mantissa.split_once('.').map_or(0, |(_, f)| f.len());
+        let exponent = exponent.parse::<i32>().unwrap() - fraction as i32;
+        Self::parse(value.is_sign_negative(), &mantissa.replace('.', ""), exponent)
+    }
+
+    /// The digits of a number in decimal representation.
+    fn from_decimal(value: &str) -> Self {
+        let (negative, value) = match value.strip_prefix(['-', '\u{2212}']) {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let fraction = value.split_once('.').map_or(0, |(_, f)| f.len());
+        Self::parse(negative, &value.replace('.', ""), -(fraction as i32))
+    }
+
+    /// Create digits from a string of ASCII digits.
+    fn parse(negative: bool, digits: &str, exponent: i32) -> Self {
+        let digits = digits.trim_start_matches('0').bytes().map(|b| b - b'0').collect();
+        Self { negative, digits, exponent }
+    }
+
+    /// Whether the number is zero.
+    fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// The power of ten of the leading digit.
+    fn leading(&self) -> i32 {
+        self.exponent + self.digits.len() as i32 - 1
+    }
+
+    /// Round the number and return the minimum number of digits to display
+    /// after the decimal separator.
+    fn round(&mut self, rounding: Rounding) -> usize {
+        match rounding {
+            Rounding::Shortest => 0,
+            Rounding::Fraction(digits) => {
+                self.round_at(-(digits as i32));
+                digits
+            }
+            Rounding::Significant(digits) => {
+                let leading = if self.is_zero() { 0 } else { self.leading() };
+                self.round_at(leading - digits as i32 + 1);
+                // Rounding may have carried into a new leading digit.
+                let leading = if self.is_zero() { 0 } else { self.leading() };
+                (digits as i32 - 1 - leading).max(0) as usize
+            }
+        }
+    }
+
+    /// Round half away from zero to the given power of ten.
+    fn round_at(&mut self, power: i32) {
+        let drop = power - self.exponent;
+        if drop <= 0 {
+            return;
+        }
+
+        let len = self.digits.len();
+        let (keep, next) = match usize::try_from(drop) {
+            Ok(drop) if drop <= len => (len - drop, self.digits[len - drop]),
+            _ => (0, 0),
+        };
+
+        self.digits.truncate(keep);
+        self.exponent = power;
+        if next >= 5 {
+            let mut i = self.digits.len();
+            loop {
+                if i == 0 {
+                    self.digits.insert(0, 1);
+                    break;
+                }
+                i -= 1;
+                if self.digits[i] == 9 {
+                    self.digits[i] = 0;
+                } else {
+                    self.digits[i] += 1;
+                    break;
+                }
+            }
+        }
+
+        let zeros = self.digits.iter().take_while(|&&d| d == 0).count();
+        self.digits.drain(..zeros);
+    }
+
+    /// Split into the digits before and after the decimal separator, keeping
+    /// at least `min_fraction` digits after it.
+    fn split(&self, min_fraction: usize) -> (String, String) {
+        let digits: String = self.digits.iter().map(|d| char::from(b'0' + d)).collect();
+        let (mut integer, mut fraction) = if self.exponent >= 0 {
+            (digits + &"0".repeat(self.exponent as usize), String::new())
+        } else {
+            let point = self.digits.len() as i32 + self.exponent;
+            if point > 0 {
+                let (integer, fraction) = digits.split_at(point as usize);
+                (integer.to_string(), fraction.to_string())
+            } else {
+                (String::new(), "0".repeat(-point as usize) + &digits)
+            }
+        };
+
+        if integer.is_empty() {
+            integer.push('0');
+        }
+
+        let trimmed = fraction.trim_end_matches('0').len().max(min_fraction);
+        fraction.truncate(trimmed);
+        while fraction.len() < min_fraction {
+            fraction.push('0');
+        }
+
+        (integer, fraction)
+    }
+}
+
+/// The symbols used for numbers in a language and region.
+struct Symbols {
+    /// The decimal separator.
+    decimal: char,
+    /// The separator between groups of digits.
+    group: &'static str,
+    /// The minimum number of digits in the leading group for grouping to
+    /// apply.
+    min_grouping: usize,
+    /// The percent sign, with preceding space if any.
+    percent: &'static str,
+    /// Whether currency symbols precede the amount.
+    currency_first: bool,
+    /// The space between currency symbols and the amount.
+    currency_space: &'static str,
+}
+
+impl Symbols {
+    /// The symbols for the given language and region.
+    fn new(lang: Lang, region: Option<Region>) -> Self {
+        let (decimal, group) = match lang.as_str() {
+            "de" if region.is_some_and(|r| r == "CH" || r == "LI") => ('.', "’"),
+            "de" | "nl" | "es" | "it" | "pt" | "da" | "ca" | "gl" | "el" | "is"
+            | "ro" | "sl" | "sr" | "tr" | "vi" => (',', "."),
+            "fr" => (',', "\u{202f}"),
+            "nb" | "nn" | "sv" | "fi" | "cs" | "pl" | "ru" | "uk" | "et" | "hu"
+            | "sq" | "la" => (',', "\u{a0}"),
+            _ => ('.', ","),
+        };
+
+        let min_grouping = match lang.as_str() {
+            "es" | "pl" => 2,
+            _ => 1,
+        };
+
+        let percent = match lang.as_str() {
+            "en" | "ja" | "zh" | "it" | "nl" | "pt" | "pl" | "he" | "tl" => "%",
+            "fr" => "\u{202f}%",
+            _ => "\u{a0}%",
+        };
+
+        let (currency_first, currency_space) = match lang.as_str() {
+            "en" | "ja" | "zh" | "he" | "tl" | "tr" => (true, ""),
+            "nl" => (true, "\u{a0}"),
+            _ => (false, "\u{a0}"),
+        };
+
+        Self {
+            decimal,
+            group,
+            min_grouping,
+            percent,
+            currency_first,
+            currency_space,
+        }
+    }
+
+    /// The text before and after an amount in the given currency.
+    fn currency(&self, symbol: &str) -> (EcoString, EcoString) {
+        // Codes are separated by a space even where symbols aren't.
+        let space =
+            if self.currency_space.is_empty() && symbol.len() > 1 && symbol.is_ascii() {
+                "\u{a0}"
+            } else {
+                self.currency_space
+            };
+
+        if self.currency_first {
+            (eco_format!("{symbol}{space}"), EcoString::new())
+        } else {
+            (EcoString::new(), eco_format!("{space}{symbol}"))
+        }
+    }
+}
+
+/// The symbol for a currency given by its ISO 4217 code. Other strings are
+/// used as the symbol directly.
+fn currency_symbol(currency: &str) -> &str {
+    match currency {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" | "CNY" => "¥",
+        "INR" => "₹",
+        "KRW" => "₩",
+        "RUB" => "₽",
+        "UAH" => "₴",
+        "TRY" => "₺",
+        "ILS" => "₪",
+        "VND" => "₫",
+        "PHP" => "₱",
+        other => other,
+    }
+}
+
+/// The number of digits after the decimal separator that are conventionally
+/// displayed for amounts of a currency.
+fn currency_digits(currency: &str) -> usize {
+    match currency {
+        "JPY" | "¥" | "KRW" | "₩" | "VND" | "₫" | "ISK" => 0,
+        _ => 2,
+    }
+}
+
+/// Write an exponent with superscript digits.
+fn superscript(exponent: i32) -> EcoString {
+    let mut out = EcoString::new();
+    if exponent < 0 {
+        out.push('⁻');
+    }
+    for c in exponent.unsigned_abs().to_string().chars() {
+        out.push(match c {
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        });
+    }
+    out
+}
@@ -3,7 +3,7 @@ use std::hash::{Hash, Hasher};
 use std::sync::{Arc, Mutex};
 
 use ecow::{eco_format, EcoString};
-use wasmi::{AsContext, AsContextMut};
+use wasmi::{AsContext, AsContextMut, Caller};
 
 use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::engine::Engine;
@@ -19,14 +19,19 @@ use crate::World;
 /// functions that perform the necessary conversions between native Typst types
 /// and bytes.
 ///
-/// Plugins run in isolation from your system, which means that printing,
-/// reading files, or anything like that will not be supported for security
-/// reasons. To run as a plugin, a program needs to be compiled to a 32-bit
-/// shared WebAssembly library. Many compilers will use the
-/// [WASI ABI](https://wasi.dev/) by default or as their only option (e.g.
-/// emscripten), which allows printing, reading files, etc. This ABI will not
-/// directly work with Typst. You will either need to compile to a different
-/// target or [stub all functions](https://github.com/astrale-sharp/wasm-minimal-protocol/blob/master/wasi-stub).
+/// Plugins run in isolation from your system, which means that reading files,
+/// accessing the network, or anything like that will not be supported for
+/// security reasons. To run as a plugin, a program needs to be compiled to a
+/// 32-bit shared WebAssembly library.
+///
+/// Many compilers will use the [WASI ABI](https://wasi.dev/) by default or as
+/// their only option (e.g. emscripten or Rust's `wasm32-wasip1` target). Such
+/// plugins can be loaded as they are: Typst provides deterministic stand-ins
+/// for the WASI functions they import. The clock always reports the same time,
+/// random numbers come from a generator that starts from the same seed for
+/// each call, and there is no file system, environment, or command line.
+/// Anything the plugin prints to stdout or stderr is shown as a warning. All
+/// other WASI functions report an error to the plugin.
 ///
 /// # Plugins and Packages
 /// Plugins are distributed as packages. A package can make use of a plugin
@@ -105,6 +110,11 @@ use crate::World;
 ///   immediately after this function returns. If the message should be
 ///   interpreted as an error message, it should be encoded as UTF-8.
 ///
+/// Plugin modules may additionally import any function from the
+/// `wasi_snapshot_preview1` module, as described above. If a WASI module
+/// exports an `_initialize` function, it is called once when the plugin is
+/// loaded.
+///
 /// # Resources
 /// For more resources, check out the
 /// [wasm-minimal-protocol repository](https://github.com/astrale-sharp/wasm-minimal-protocol).
@@ -145,6 +155,27 @@ struct StoreData {
     args: Vec<Bytes>,
     output: Vec<u8>,
     memory_error: Option<MemoryError>,
+    /// What the plugin printed to stdout or stderr through WASI.
+    printed: Vec<u8>,
+    /// The state of the generator behind WASI's `random_get`.
+    rng: u64,
+}
+
+impl StoreData {
+    /// Reset the WASI state, so that a call can't observe previous calls.
+    fn reset_wasi(&mut self) {
+        self.printed.clear();
+        self.rng = WASI_RANDOM_SEED;
+    }
+}
+
+/// The result of calling a plugin function.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct PluginOutput {
+    /// The bytes returned by the function.
+    pub bytes: Bytes,
+    /// What the function printed to stdout or stderr through WASI.
+    pub printed: EcoString,
 }
 
 #[scope]
@@ -190,8 +221,10 @@ impl Plugin {
                 wasm_minimal_protocol_write_args_to_buffer,
             )
             .unwrap();
+        link_wasi(&mut linker, &module)?;
 
         let mut store = Store::new(&engine, StoreData::default());
+        store.data_mut().reset_wasi();
         let instance = linker
             .instantiate(&mut store, &module)
             .and_then(|pre_instance| pre_instance.start(&mut store))
@@ -205,9 +238,17 @@ impl Plugin {
             bail!("plugin does not export its memory");
         }
 
+        // WASI reactors must be initialized before anything else is called.
+        if let Ok(initialize) = instance.get_typed_func::<(), ()>(&store, "_initialize") {
+            initialize
+                .call(&mut store, ())
+                .map_err(|err| eco_format!("plugin failed to initialize: {err}"))?;
+        }
+
         // Collect exported functions.
         let functions = instance
             .exports(&store)
+            .filter(|export| export.name() != "_initialize")
             .filter_map(|export| {
                 let name = export.name().into();
                 export.into_func().map(|func| (name, func))
@@ -220,7 +261,7 @@ impl Plugin {
     /// Call the plugin function with the given `name`.
     #[comemo::memoize]
     #[typst_macros::time(name = "call plugin")]
-    pub fn call(&self, name: &str, args: Vec<Bytes>) -> StrResult<Bytes> {
+    pub fn call(&self, name: &str, args: Vec<Bytes>) -> StrResult<PluginOutput> {
         // Find the function with the given name.
         let func = self
             .0
@@ -264,6 +305,7 @@ impl Plugin {
 
         // Store the input data.
         store.data_mut().args = args;
+        store.data_mut().reset_wasi();
 
         // Call the function.
         let mut code = wasmi::Val::I32(-1);
@@ -280,6 +322,7 @@ impl Plugin {
 
         // Extract the returned data.
         let output = std::mem::take(&mut store.data_mut().output);
+        let printed = std::mem::take(&mut store.data_mut().printed);
 
         // Parse the functions return value.
         match code {
@@ -293,7 +336,10 @@ impl Plugin {
             _ => bail!("plugin did not respect the protocol"),
         };
 
-        Ok(output.into())
+        Ok(PluginOutput {
+            bytes: output.into(),
+            printed: String::from_utf8_lossy(&printed).into(),
+        })
     }
 
     /// An iterator over all the function names defined by the plugin.
@@ -363,3 +409,267 @@ fn wasm_minimal_protocol_send_result_to_host(
     }
     caller.data_mut().output = buffer;
 }
+
+/// The module from which WASI functions are imported.
+const WASI: &str = "wasi_snapshot_preview1";
+
+/// The time reported by WASI's clocks, in nanoseconds since the Unix epoch.
+const WASI_TIME: u64 = 0;
+
+/// The seed for WASI's `random_get`.
+const WASI_RANDOM_SEED: u64 = 0x7970_7374;
+
+/// WASI error numbers.
+const ERRNO_SUCCESS: i32 = 0;
+const ERRNO_BADF: i32 = 8;
+const ERRNO_FAULT: i32 = 21;
+const ERRNO_NOSYS: i32 = 52;
+const ERRNO_SPIPE: i32 = 70;
+const ERRNO_NOTCAPABLE: i32 = 76;
+
+/// Define deterministic stand-ins for all WASI functions the module imports.
+///
+/// Functions that can't be meaningfully provided without breaking purity or
+/// isolation report an error to the plugin.
+fn link_wasi(
+    linker: &mut wasmi::Linker<StoreData>,
+    module: &wasmi::Module,
+) -> StrResult<()> {
+    for import in module.imports() {
+        if import.module() != WASI {
+            continue;
+        }
+
+        let wasmi::ExternType::Func(ty) = import.ty() else { continue };
+        let name = import.name();
+        match name {
+            "args_get" | "environ_get" => linker.func_wrap(WASI, name, wasi_get),
+            "args_sizes_get" | "environ_sizes_get" => {
+                linker.func_wrap(WASI, name, wasi_sizes_get)
+            }
+            "clock_res_get" => linker.func_wrap(WASI, name, wasi_clock_res_get),
+            "clock_time_get" => linker.func_wrap(WASI, name, wasi_clock_time_get),
+            "random_get" => linker.func_wrap(WASI, name, wasi_random_get),
+            "fd_write" => linker.func_wrap(WASI, name, wasi_fd_write),
+            "fd_read" => linker.func_wrap(WASI, name, wasi_fd_read),
+            "fd_fdstat_get" => linker.func_wrap(WASI, name, wasi_fd_fdstat_get),
+            "fd_seek" => linker.func_wrap(WASI, name, wasi_fd_seek),
+            "fd_close" => linker.func_wrap(WASI, name, wasi_fd_close),
+            "sched_yield" => linker.func_wrap(WASI, name, wasi_sched_yield),
+            "proc_exit" => linker.func_wrap(WASI, name, wasi_proc_exit),
+            _ => {
+                // There are no preopened directories, so path-based functions
+                // are never permitted.
+                let errno = if name.starts_with("path_") {
+                    ERRNO_NOTCAPABLE
+                } else if name.starts_with("fd_") {
+                    ERRNO_BADF
+                } else {
+                    ERRNO_NOSYS
+                };
+                let returns_errno = ty.results() == [wasmi::core::ValType::I32];
+                linker.func_new(WASI, name, ty.clone(), move |_, _, results| {
+                    if returns_errno {
+                        results[0] = wasmi::Val::I32(errno);
+                    }
+                    Ok(())
+                })
+            }
+        }
+        .map_err(|err| eco_format!("failed to provide WASI function {name} ({err})"))?;
+    }
+
+    Ok(())
+}
+
+/// Turn the result of a memory access into a WASI error number.
+fn errno(result: Result<(), i32>) -> i32 {
+    result.err().unwrap_or(ERRNO_SUCCESS)
+}
+
+/// Get the plugin's memory.
+fn wasi_memory(caller: &Caller<StoreData>) -> Result<wasmi::Memory, i32> {
+    caller
+        .get_export("memory")
+        .and_then(wasmi::Extern::into_memory)
+        .ok_or(ERRNO_FAULT)
+}
+
+/// Read from the plugin's memory.
+///
+/// The range is checked against the memory's size before anything is copied,
+/// so that a bogus length can't make us allocate a huge buffer.
+fn wasi_read(caller: &Caller<StoreData>, ptr: u32, len: u32) -> Result<Vec<u8>, i32> {
+    let memory = wasi_memory(caller)?;
+    let start = ptr as usize;
+    let end = start.checked_add(len as usize).ok_or(ERRNO_FAULT)?;
+    memory
+        .data(caller)
+        .get(start..end)
+        .map(<[u8]>::to_vec)
+        .ok_or(ERRNO_FAULT)
+}
+
+/// Write into the plugin's memory.
+fn wasi_write(caller: &mut Caller<StoreData>, ptr: u32, data: &[u8]) -> Result<(), i32> {
+    let memory = wasi_memory(caller)?;
+    memory.write(caller, ptr as usize, data).map_err(|_| ERRNO_FAULT)
+}
+
+/// Provide empty command line arguments or environment variables.
+fn wasi_get(_: Caller<StoreData>, _: u32, _: u32) -> i32 {
+    ERRNO_SUCCESS
+}
+
+/// Report that there are no command line arguments or environment variables.
+fn wasi_sizes_get(mut caller: Caller<StoreData>, count_ptr: u32, size_ptr: u32) -> i32 {
+    errno(
+        wasi_write(&mut caller, count_ptr, &0u32.to_le_bytes())
+            .and_then(|()| wasi_write(&mut caller, size_ptr, &0u32.to_le_bytes())),
+    )
+}
+
+/// Report the resolution of a clock.
+fn wasi_clock_res_get(mut caller: Caller<StoreData>, _: i32, res_ptr: u32) -> i32 {
+    errno(wasi_write(&mut caller, res_ptr, &1u64.to_le_bytes()))
+}
+
+/// Report the time of a clock, which is always the same.
+fn wasi_clock_time_get(
+    mut caller: Caller<StoreData>,
+    _: i32,
+    _: i64,
+    time_ptr: u32,
+) -> i32 {
+    errno(wasi_write(&mut caller, time_ptr, &WASI_TIME.to_le_bytes()))
+}
+
+/// Fill a buffer with pseudo-random bytes.
+fn wasi_random_get(mut caller: Caller<StoreData>, buf: u32, len: u32) -> i32 {
+    let mut chunk = [0; 256];
+    let mut offset = 0;
+    while offset < len {
+        let n = (len - offset).min(chunk.len() as u32);
+        for bytes in chunk[..n as usize].chunks_mut(8) {
+            let value = splitmix64(&mut caller.data_mut().rng).to_le_bytes();
+            bytes.copy_from_slice(&value[..bytes.len()]);
+        }
+        let ptr = buf.wrapping_add(offset);
+        if let Err(errno) = wasi_write(&mut caller, ptr, &chunk[..n as usize]) {
+            return errno;
+        }
+        offset += n;
+    }
+    ERRNO_SUCCESS
+}
+
+/// Capture what the plugin writes to stdout or stderr.
+fn wasi_fd_write(
+    mut caller: Caller<StoreData>,
+    fd: i32,
+    iovs: u32,
+    iovs_len: u32,
+    nwritten_ptr: u32,
+) -> i32 {
+    if fd != 1 && fd != 2 {
+        return ERRNO_BADF;
+    }
+
+    let mut written = 0u32;
+    for i in 0..iovs_len {
+        // Each I/O vector consists of a pointer and a length.
+        let iov = match wasi_read(&caller, iovs.wrapping_add(8 * i), 8) {
+            Ok(iov) => iov,
+            Err(errno) => return errno,
+        };
+        let ptr = u32::from_le_bytes([iov[0], iov[1], iov[2], iov[3]]);
+        let len = u32::from_le_bytes([iov[4], iov[5], iov[6], iov[7]]);
+        match wasi_read(&caller, ptr, len) {
+            Ok(data) => caller.data_mut().printed.extend_from_slice(&data),
+            Err(errno) => return errno,
+        }
+        written = written.wrapping_add(len);
+    }
+
+    errno(wasi_write(&mut caller, nwritten_ptr, &written.to_le_bytes()))
+}
+
+/// Read from stdin, which is always empty.
+fn wasi_fd_read(
+    mut caller: Caller<StoreData>,
+    fd: i32,
+    _: u32,
+    _: u32,
+    nread_ptr: u32,
+) -> i32 {
+    if fd != 0 {
+        return ERRNO_BADF;
+    }
+    errno(wasi_write(&mut caller, nread_ptr, &0u32.to_le_bytes()))
+}
+
+/// Describe the standard streams as character devices.
+fn wasi_fd_fdstat_get(mut caller: Caller<StoreData>, fd: i32, ptr: u32) -> i32 {
+    if !(0..=2).contains(&fd) {
+        return ERRNO_BADF;
+    }
+
+    // The `fdstat` struct: file type, flags, base and inheriting rights.
+    const CHARACTER_DEVICE: u8 = 2;
+    let mut stat = [0; 24];
+    stat[0] = CHARACTER_DEVICE;
+    stat[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+    errno(wasi_write(&mut caller, ptr, &stat))
+}
+
+/// Seek in a file descriptor, which isn't possible for the standard streams.
+fn wasi_fd_seek(_: Caller<StoreData>, fd: i32, _: i64, _: i32, _: u32) -> i32 {
+    if (0..=2).contains(&fd) {
+        ERRNO_SPIPE
+    } else {
+        ERRNO_BADF
+    }
+}
+
+/// Close a file descriptor. Only the standard streams exist.
+fn wasi_fd_close(_: Caller<StoreData>, fd: i32) -> i32 {
+    if (0..=2).contains(&fd) {
+        ERRNO_SUCCESS
+    } else {
+        ERRNO_BADF
+    }
+}
+
+/// Yield to other threads, of which there are none.
+fn wasi_sched_yield(_: Caller<StoreData>) -> i32 {
+    ERRNO_SUCCESS
+}
+
+/// Terminate the plugin.
+fn wasi_proc_exit(_: Caller<StoreData>, code: i32) -> Result<(), wasmi::Error> {
+    Err(wasmi::Error::new(format!("plugin exited with code {code}")))
+}
+
+/// Advance a SplitMix64 generator and return its next output.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_output() {
+        let data = typst_dev_assets::get("plugins/hello.wasm").unwrap();
+        let plugin = Plugin::new(Bytes::from_static(data)).unwrap();
+        let args = vec![Bytes::from_static(b"hey!")];
+        let output = plugin.call("double_it", args).unwrap();
+        assert_eq!(output.bytes, Bytes::from_static(b"hey!.hey!"));
+        assert_eq!(output.printed, "");
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use ecow::{eco_format, EcoString};
+use serde::{Serialize, Serializer};
+
+use crate::diag::{bail, SourceResult, StrResult};
+use crate::foundations::{
+    cast, func, repr, ty, Args, CustomField, Dict, Func, Repr, Str, Value,
+};
+use crate::syntax::Span;
+use crate::utils::LazyHash;
+
+/// Defines a new type.
+///
+/// A type defined this way has named fields with optional types and defaults,
+/// just like the fields of an [element]($element). Calling the resulting
+/// function creates a [record] of that type and checks the given fields.
+///
+/// Methods are functions that take the record as their first argument. They
+/// can be called with dot syntax, just like the methods of built-in types.
+///
+/// ```example
+/// #let point = record(
+///   "point",
+///   fields: (
+///     x: (type: int, default: 0),
+///     y: (type: int, default: 0),
+///   ),
+///   methods: (
+///     norm: self => calc.sqrt(self.x * self.x + self.y * self.y),
+///     scale: (self, k) => type(self)(x: self.x * k, y: self.y * k),
+///   ),
+/// )
+///
+/// #let p = point(x: 3, y: 4)
+/// #p.norm() \
+/// #p.scale(2) \
+/// #type(p) \
+/// #(p == point(x: 3, y: 4))
+/// ```
+#[func(name = "record")]
+pub fn define_record(
+    /// The call site span.
+    span: Span,
+    /// The type's name.
+    name: Str,
+    /// The type's fields, mapping from field names to field descriptions.
+    ///
+    /// The field descriptions are the same as for [elements]($element). If a
+    /// field is not given and not required, it takes its default value.
+    #[named]
+    #[default]
+    fields: Dict,
+    /// The type's methods, mapping from method names to functions.
+    #[named]
+    #[default]
+    methods: Dict,
+) -> StrResult<Func> {
+    if name.is_empty() {
+        bail!("type name must not be empty");
+    }
+
+    let fields = CustomField::parse_all(fields)?;
+    let methods = methods
+        .into_iter()
+        .map(|(name, method)| match method {
+            Value::Func(func) => Ok((EcoString::from(name), func)),
+            v => bail!("expected function for method `{name}`, found {}", v.ty()),
+        })
+        .collect::<StrResult<Vec<_>>>()?;
+
+    for (name, _) in &methods {
+        if fields.iter().any(|field| field.name == *name) {
+            bail!("`{name}` cannot be both a field and a method");
+        }
+    }
+
+    Ok(RecordType(Arc::new(LazyHash::new(RecordTypeData {
+        name: name.into(),
+        fields,
+        methods,
+        span,
+    })))
+    .into())
+}
+
+/// A user-defined type, created with the [`record`] function.
+#[derive(Clone, PartialEq, Hash)]
+pub struct RecordType(Arc<LazyHash<RecordTypeData>>);
+
+/// The definition of a user-defined type.
+#[derive(Debug, PartialEq, Hash)]
+struct RecordTypeData {
+    /// The type's name.
+    name: EcoString,
+    /// The type's fields, in declaration order.
+    fields: Vec<CustomField>,
+    /// The type's methods.
+    methods: Vec<(EcoString, Func)>,
+    /// The span of the definition. Distinguishes otherwise identical types.
+    span: Span,
+}
+
+impl RecordType {
+    /// The type's name.
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The type's fields, in declaration order.
+    pub fn fields(&self) -> &[CustomField] {
+        &self.0.fields
+    }
+
+    /// The method with the given name, if the type has one.
+    pub fn method(&self, name: &str) -> Option<&Func> {
+        self.0
+            .methods
+            .iter()
+            .find(|(method, _)| method == name)
+            .map(|(_, func)| func)
+    }
+
+    /// Get a method of the type by name.
+    pub fn field(&self, field: &str) -> StrResult<Value> {
+        match self.method(field) {
+            Some(method) => Ok(Value::Func(method.clone())),
+            None => bail!("type {} does not contain field `{field}`", self.name()),
+        }
+    }
+
+    /// Construct a record of this type.
+    pub fn construct(&self, args: &mut Args) -> SourceResult<Record> {
+        let mut given = CustomField::take_all(self.fields(), args)?;
+        let fields = self
+            .fields()
+            .iter()
+            .map(|field| {
+                let value =
+                    given.take(&field.name).unwrap_or_else(|_| field.default.clone());
+                (field.name.clone().into(), value)
+            })
+            .collect();
+        Ok(Record { ty: self.clone(), fields })
+    }
+}
+
+impl Debug for RecordType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "RecordType({})", self.name())
+    }
+}
+
+cast! {
+    RecordType,
+    self => Value::Func(self.into()),
+    v: Func => v.record_type().cloned().ok_or("expected user-defined type")?,
+}
+
+/// A value of a user-defined type.
+///
+/// Records are created by calling a type defined with the [`record`]
+/// function. Their fields can be accessed with dot syntax, but not modified.
+/// Two records are equal if they have the same type and equal fields.
+///
+/// ```example
+/// #let book = record("book", fields: (
+///   title: (type: str, required: true),
+///   year: int,
+/// ))
+///
+/// #let b = book(title: "Dune", year: 1965)
+/// #b.title was published in #b.year.
+/// ```
+#[ty(cast)]
+#[derive(Clone, PartialEq, Hash)]
+pub struct Record {
+    /// The record's type.
+    ty: RecordType,
+    /// The values of the record's fields.
+    fields: Dict,
+}
+
+impl Record {
+    /// The record's type.
+    pub fn ty(&self) -> &RecordType {
+        &self.ty
+    }
+
+    /// The values of the record's fields.
+    pub fn fields(&self) -> &Dict {
+        &self.fields
+    }
+
+    /// Access a field of the record.
+    pub fn field(&self, field: &str) -> StrResult<Value> {
+        self.fields.get(field).cloned().map_err(|_| {
+            eco_format!("{} does not have field {}", self.ty.name(), field.repr())
+        })
+    }
+}
+
+impl Debug for Record {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} ", self.ty.name())?;
+        self.fields.fmt(f)
+    }
+}
+
+impl Repr for Record {
+    fn repr(&self) -> EcoString {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, value)| eco_format!("{}: {}", name, value.repr()))
+            .collect::<Vec<_>>();
+        eco_format!("{}{}", self.ty.name(), repr::pretty_array_like(&fields, false))
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.fields.serialize(serializer)
+    }
+}
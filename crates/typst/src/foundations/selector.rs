@@ -7,8 +7,9 @@ use smallvec::SmallVec;
 
 use crate::diag::{bail, HintedStrResult, StrResult};
 use crate::foundations::{
-    cast, func, repr, scope, ty, CastInfo, Content, Context, Dict, Element, FromValue,
-    Func, Label, Reflect, Regex, Repr, Str, StyleChain, Type, Value,
+    cast, func, repr, scope, ty, CastInfo, Content, Context, CustomElem, CustomElement,
+    Dict, Element, FromValue, Func, Label, Reflect, Regex, Repr, Str, StyleChain, Type,
+    Value,
 };
 use crate::introspection::{Introspector, Locatable, Location};
 use crate::symbols::Symbol;
@@ -78,6 +79,11 @@ pub enum Selector {
     /// If there is a dictionary, only elements with the fields from the
     /// dictionary match.
     Elem(Element, Option<SmallVec<[(u8, Value); 1]>>),
+    /// Matches a user-defined element.
+    ///
+    /// If there is a dictionary, only elements with the fields from the
+    /// dictionary match.
+    Custom(CustomElement, Option<Dict>),
     /// Matches the element at the specified location.
     Location(Location),
     /// Matches elements with a specific label.
@@ -130,8 +136,16 @@ impl Selector {
                         target.get(*id, styles).as_ref().ok() == Some(value)
                     })
             }
+            Self::Custom(element, dict) => {
+                target.to_packed::<CustomElem>().is_some_and(|elem| {
+                    elem.element == *element
+                        && dict.iter().flat_map(|dict| dict.iter()).all(
+                            |(name, value)| elem.values.get(name).ok() == Some(value),
+                        )
+                })
+            }
             Self::Label(label) => target.label() == Some(*label),
-            Self::Can(cap) => target.func().can_type_id(*cap),
+            Self::Can(cap) => target.elem().can_type_id(*cap),
             Self::Or(selectors) => {
                 selectors.iter().any(move |sel| sel.matches(target, styles))
             }
@@ -245,6 +259,10 @@ impl Repr for Selector {
                     elem.name().into()
                 }
             }
+            Self::Custom(elem, dict) => match dict {
+                Some(dict) => eco_format!("{}.where{}", elem.name(), dict.repr()),
+                None => elem.name().into(),
+            },
             Self::Label(label) => label.repr(),
             Self::Regex(regex) => regex.repr(),
             Self::Can(cap) => eco_format!("{cap:?}"),
@@ -274,10 +292,13 @@ impl Repr for Selector {
 cast! {
     type Selector,
     text: EcoString => Self::text(&text)?,
-    func: Func => func
-        .element()
-        .ok_or("only element functions can be used as selectors")?
-        .select(),
+    func: Func => match func.custom_element() {
+        Some(custom) => custom.select(),
+        None => func
+            .element()
+            .ok_or("only element functions can be used as selectors")?
+            .select(),
+    },
     label: Label => Self::Label(label),
     regex: Regex => Self::regex(regex)?,
     location: Location => Self::Location(location),
@@ -343,6 +364,7 @@ impl FromValue for LocatableSelector {
                         Err(eco_format!("{} is not locatable", elem.name()))?
                     }
                 }
+                Selector::Custom(_, _) => {}
                 Selector::Location(_) => {}
                 Selector::Label(_) => {}
                 Selector::Regex(_) => bail!("text is not locatable"),
@@ -421,6 +443,7 @@ impl FromValue for ShowableSelector {
         fn validate(selector: &Selector, nested: bool) -> HintedStrResult<()> {
             match selector {
                 Selector::Elem(_, _) => {}
+                Selector::Custom(_, _) => {}
                 Selector::Label(_) => {}
                 Selector::Regex(_) if !nested => {}
                 Selector::Or(list) | Selector::And(list) => {
@@ -503,9 +503,14 @@ impl Recipe {
         let mut content = match &self.transform {
             Transformation::Content(content) => content.clone(),
             Transformation::Func(func) => {
+                let _scope = typst_timing::ProfileScope::new(
+                    "show rule",
+                    self.selector.is_some().then(|| content.elem_name()),
+                    self.span,
+                );
                 let mut result = func.call(engine, context, [content.clone()]);
                 if self.selector.is_some() {
-                    let point = || Tracepoint::Show(content.func().name().into());
+                    let point = || Tracepoint::Show(content.elem_name().into());
                     result = result.trace(engine.world, point, content.span());
                 }
                 result?.display()
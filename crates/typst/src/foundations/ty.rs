@@ -6,7 +6,8 @@ use once_cell::sync::Lazy;
 
 use crate::diag::StrResult;
 use crate::foundations::{
-    cast, func, AutoValue, Func, NativeFuncData, NoneValue, Repr, Scope, Value,
+    cast, func, AutoValue, DeclaredType, Func, NativeFuncData, NoneValue, RecordType,
+    Repr, Scope, Value,
 };
 use crate::utils::Static;
 
@@ -124,6 +125,9 @@ impl Type {
 impl Type {
     /// Determines a value's type.
     ///
+    /// For a value of a user-defined type, this returns the type's constructor
+    /// that was created with the [`record`] function.
+    ///
     /// ```example
     /// #type(12) \
     /// #type(14.7) \
@@ -137,11 +141,33 @@ impl Type {
     pub fn construct(
         /// The value whose type's to determine.
         value: Value,
-    ) -> Type {
-        value.ty()
+    ) -> ValueType {
+        match DeclaredType::of(&value) {
+            DeclaredType::Native(ty) => ValueType::Native(ty),
+            DeclaredType::Record(ty) => ValueType::Record(ty),
+        }
     }
 }
 
+/// The type of a value, as returned by `type()`.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum ValueType {
+    /// A built-in type.
+    Native(Type),
+    /// A user-defined type.
+    Record(RecordType),
+}
+
+cast! {
+    ValueType,
+    self => match self {
+        Self::Native(ty) => ty.into_value(),
+        Self::Record(ty) => ty.into_value(),
+    },
+    v: Type => Self::Native(v),
+    v: RecordType => Self::Record(v),
+}
+
 impl Debug for Type {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "Type({})", self.long_name())
@@ -14,8 +14,8 @@ use crate::eval::ops;
 use crate::foundations::{
     fields, repr, Args, Array, AutoValue, Bytes, CastInfo, Content, Datetime, Decimal,
     Dict, Duration, Fold, FromValue, Func, IntoValue, Label, Module, NativeElement,
-    NativeType, NoneValue, Plugin, Reflect, Repr, Resolve, Scope, Str, Styles, Type,
-    Version,
+    NativeType, NoneValue, Plugin, Record, Reflect, Repr, Resolve, Scope, Str, Styles,
+    Type, Version,
 };
 use crate::layout::{Abs, Angle, Em, Fr, Length, Ratio, Rel};
 use crate::symbols::Symbol;
@@ -88,6 +88,8 @@ pub enum Value {
     Module(Module),
     /// A WebAssembly plugin.
     Plugin(Plugin),
+    /// A value of a user-defined type.
+    Record(Record),
     /// A dynamic value.
     Dyn(Dynamic),
 }
@@ -150,6 +152,7 @@ impl Value {
             Self::Type(_) => Type::of::<Type>(),
             Self::Module(_) => Type::of::<Module>(),
             Self::Plugin(_) => Type::of::<Module>(),
+            Self::Record(_) => Type::of::<Record>(),
             Self::Dyn(v) => v.ty(),
         }
     }
@@ -166,8 +169,12 @@ impl Value {
             Self::Version(version) => version.component(field).map(Self::Int),
             Self::Dict(dict) => dict.get(field).cloned(),
             Self::Content(content) => content.field_by_name(field),
+            Self::Record(record) => record.field(field),
             Self::Type(ty) => ty.field(field).cloned(),
-            Self::Func(func) => func.field(field).cloned(),
+            Self::Func(func) => match func.record_type() {
+                Some(ty) => ty.field(field),
+                None => func.field(field).cloned(),
+            },
             Self::Module(module) => module.field(field).cloned(),
             _ => fields::field(self, field),
         }
@@ -264,6 +271,7 @@ impl Debug for Value {
             Self::Type(v) => Debug::fmt(v, f),
             Self::Module(v) => Debug::fmt(v, f),
             Self::Plugin(v) => Debug::fmt(v, f),
+            Self::Record(v) => Debug::fmt(v, f),
             Self::Dyn(v) => Debug::fmt(v, f),
         }
     }
@@ -302,6 +310,7 @@ impl Repr for Value {
             Self::Type(v) => v.repr(),
             Self::Module(v) => v.repr(),
             Self::Plugin(v) => v.repr(),
+            Self::Record(v) => v.repr(),
             Self::Dyn(v) => v.repr(),
         }
     }
@@ -353,6 +362,7 @@ impl Hash for Value {
             Self::Type(v) => v.hash(state),
             Self::Module(v) => v.hash(state),
             Self::Plugin(v) => v.hash(state),
+            Self::Record(v) => v.hash(state),
             Self::Dyn(v) => v.hash(state),
         }
     }
@@ -374,6 +384,7 @@ impl Serialize for Value {
             Self::Content(v) => v.serialize(serializer),
             Self::Array(v) => v.serialize(serializer),
             Self::Dict(v) => v.serialize(serializer),
+            Self::Record(v) => v.serialize(serializer),
 
             // Fall back to repr() for other things.
             other => serializer.serialize_str(&other.repr()),
@@ -674,6 +685,7 @@ primitive! { Args: "arguments", Args }
 primitive! { Type: "type", Type }
 primitive! { Module: "module", Module }
 primitive! { Plugin: "plugin", Plugin }
+primitive! { Record: "record", Record }
 
 impl<T: Reflect> Reflect for Arc<T> {
     fn input() -> CastInfo {
@@ -10,10 +10,14 @@ use crate::foundations::{
 use crate::introspection::Locator;
 use crate::layout::{
     layout_fragment, layout_frame, Abs, Axes, Corners, Em, Fr, Fragment, Frame,
-    FrameKind, Length, Region, Regions, Rel, Sides, Size, Spacing,
+    FrameItem, FrameKind, GroupMask, Length, Point, Ratio, Region, Regions, Rel, Sides,
+    Size, Spacing, Transform,
 };
+use crate::syntax::Span;
 use crate::utils::Numeric;
-use crate::visualize::{clip_rect, Paint, Stroke};
+use crate::visualize::{
+    clip_rect, BlendMode, Geometry, Mask, Paint, Path, RatioComponent, Shadow, Stroke,
+};
 
 /// An inline-level container that sizes content.
 ///
@@ -121,6 +125,101 @@ pub struct BoxElem {
     #[default(false)]
     pub clip: bool,
 
+    /// A shadow that the box casts.
+    ///
+    /// The shadow takes the shape of the box's background, including its
+    /// outset and corner radii. It is given as a dictionary with the
+    /// following optional keys:
+    ///
+    /// - `offset`: How far the shadow is moved, as an array of a horizontal
+    ///   and a vertical length.
+    /// - `blur`: The radius over which the shadow's edges fade out.
+    /// - `spread`: How much the shadow grows (or shrinks, if negative) on
+    ///   each side.
+    /// - `paint`: The shadow's paint. Defaults to half-transparent black.
+    ///
+    /// ```example
+    /// #box(
+    ///   fill: white,
+    ///   inset: 8pt,
+    ///   radius: 4pt,
+    ///   shadow: (offset: (2pt, 3pt), blur: 6pt),
+    /// )[Floating]
+    /// ```
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
+    /// The radius of a Gaussian blur that is applied to the box and its
+    /// contents.
+    ///
+    /// ```example
+    /// #box(blur: 2pt, inset: 6pt, fill: aqua)[Blurry]
+    /// ```
+    #[resolve]
+    pub blur: Length,
+
+    /// A shape to clip the box to.
+    ///
+    /// The shape is laid out in the box's area, so relative sizes are
+    /// resolved relative to the box's size. Everything outside of the
+    /// shape's geometry is hidden, including the box's fill and stroke.
+    /// Any shape works, for example a `circle`, a `polygon`, or a `path`.
+    ///
+    /// ```example
+    /// #box(
+    ///   clip-shape: circle(width: 100%),
+    ///   image("tiger.jpg", width: 60pt),
+    /// )
+    /// ```
+    pub clip_shape: Option<Content>,
+
+    /// A mask that makes parts of the box and its contents transparent.
+    ///
+    /// The mask's paint covers the box's area. If just a paint is given,
+    /// its luminance determines the opacity: The box stays opaque where
+    /// the paint is white and becomes transparent where it is black. With a
+    /// dictionary like `(paint: .., mode: "alpha")`, the paint's alpha
+    /// channel determines the opacity instead.
+    ///
+    /// ```example
+    /// #box(
+    ///   mask: gradient.linear(white, black),
+    ///   image("tiger.jpg", width: 80pt),
+    /// )
+    /// ```
+    pub mask: Option<Mask>,
+
+    /// The opacity with which the box and its contents are painted as a
+    /// whole.
+    ///
+    /// Unlike a transparent fill, this also applies to text, images and
+    /// shapes inside of the box. Overlapping parts of the contents do not
+    /// shine through each other.
+    ///
+    /// ```example
+    /// #box(
+    ///   opacity: 50%,
+    ///   fill: aqua,
+    ///   inset: 6pt,
+    ///   stack(dir: ltr, spacing: -4pt, circle(fill: red), circle(fill: blue)),
+    /// )
+    /// ```
+    #[default(RatioComponent(Ratio::one()))]
+    pub opacity: RatioComponent,
+
+    /// How the colors of the box and its contents are combined with the
+    /// colors behind it.
+    ///
+    /// ```example
+    /// #box(
+    ///   fill: yellow,
+    ///   inset: 6pt,
+    ///   box(blend: "multiply", fill: aqua, inset: 6pt)[Multiplied],
+    /// )
+    /// ```
+    #[default(BlendMode::Normal)]
+    pub blend: BlendMode,
+
     /// The contents of the box.
     #[positional]
     #[borrowed]
@@ -145,6 +244,9 @@ impl Packed<BoxElem> {
         // Build the pod region.
         let pod = unbreakable_pod(&width, &height.into(), &inset, styles, region);
 
+        // The body and the clip shape are laid out separately.
+        let mut locator = locator.split();
+
         // Layout the body.
         let mut frame = match self.body(styles) {
             // If we have no body, just create an empty frame. If necessary,
@@ -153,7 +255,7 @@ impl Packed<BoxElem> {
 
             // If we have a child, layout it into the body. Boxes are boundaries
             // for gradient relativeness, so we set the `FrameKind` to `Hard`.
-            Some(body) => layout_frame(engine, body, locator, styles, pod)?
+            Some(body) => layout_frame(engine, body, locator.next(&()), styles, pod)?
                 .with_kind(FrameKind::Hard),
         };
 
@@ -188,6 +290,34 @@ impl Packed<BoxElem> {
             frame.fill_and_stroke(fill, &stroke, &outset, &radius, self.span());
         }
 
+        // Clip to the clip shape, if any.
+        if let Some(shape) = self.clip_shape(styles) {
+            let path = layout_clip_shape(
+                engine,
+                &shape,
+                locator.next(&shape.span()),
+                styles,
+                frame.size(),
+            )?;
+            frame.clip(path);
+        }
+
+        // Mask the frame, if requested.
+        if let Some(mask) = self.mask(styles) {
+            frame.mask(area_mask(mask, frame.size(), &outset, &radius, self.span()));
+        }
+
+        // Add a shadow behind the frame, if requested.
+        if let Some(shadow) = self.shadow(styles) {
+            frame.shadow(&shadow, &outset, &radius, self.span());
+        }
+
+        // Blur the frame, if requested.
+        frame.blur(self.blur(styles));
+
+        // Composite the frame as a whole, if requested.
+        frame.composite(self.opacity(styles).0, self.blend(styles));
+
         // Assign label to the frame.
         if let Some(label) = self.label() {
             frame.group(|group| group.label = Some(label))
@@ -416,6 +546,106 @@ pub struct BlockElem {
     #[default(false)]
     pub clip: bool,
 
+    /// A shadow that the block casts.
+    ///
+    /// The shadow takes the shape of the block's background, including its
+    /// outset and corner radii. It is given as a dictionary with the
+    /// following optional keys:
+    ///
+    /// - `offset`: How far the shadow is moved, as an array of a horizontal
+    ///   and a vertical length.
+    /// - `blur`: The radius over which the shadow's edges fade out.
+    /// - `spread`: How much the shadow grows (or shrinks, if negative) on
+    ///   each side.
+    /// - `paint`: The shadow's paint. Defaults to half-transparent black.
+    ///
+    /// ```example
+    /// #block(
+    ///   fill: white,
+    ///   inset: 8pt,
+    ///   radius: 4pt,
+    ///   shadow: (offset: (2pt, 3pt), blur: 6pt),
+    /// )[Floating]
+    /// ```
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
+    /// The radius of a Gaussian blur that is applied to the block and its
+    /// contents.
+    ///
+    /// ```example
+    /// #block(blur: 2pt, inset: 6pt, fill: aqua)[Blurry]
+    /// ```
+    #[resolve]
+    pub blur: Length,
+
+    /// A shape to clip the block to.
+    ///
+    /// The shape is laid out in the block's area, so relative sizes are
+    /// resolved relative to the block's size. Everything outside of the
+    /// shape's geometry is hidden, including the block's fill and stroke.
+    /// Any shape works, for example a `circle`, a `polygon`, or a `path`.
+    ///
+    /// ```example
+    /// #block(
+    ///   clip-shape: polygon(
+    ///     (0%, 0%), (100%, 0%), (100%, 70%), (0%, 100%),
+    ///   ),
+    ///   fill: aqua,
+    ///   inset: (x: 8pt, top: 8pt, bottom: 24pt),
+    ///   width: 100%,
+    ///   [*A slanted header*],
+    /// )
+    /// ```
+    pub clip_shape: Option<Content>,
+
+    /// A mask that makes parts of the block and its contents transparent.
+    ///
+    /// The mask's paint covers the block's area. If just a paint is given,
+    /// its luminance determines the opacity: The block stays opaque where
+    /// the paint is white and becomes transparent where it is black. With a
+    /// dictionary like `(paint: .., mode: "alpha")`, the paint's alpha
+    /// channel determines the opacity instead.
+    ///
+    /// ```example
+    /// #block(
+    ///   mask: gradient.linear(white, black),
+    ///   image("tiger.jpg", width: 80pt),
+    /// )
+    /// ```
+    pub mask: Option<Mask>,
+
+    /// The opacity with which the block and its contents are painted as a
+    /// whole.
+    ///
+    /// Unlike a transparent fill, this also applies to text, images and
+    /// shapes inside of the block. Overlapping parts of the contents do not
+    /// shine through each other.
+    ///
+    /// ```example
+    /// #block(
+    ///   opacity: 50%,
+    ///   fill: aqua,
+    ///   inset: 6pt,
+    ///   stack(dir: ltr, spacing: -4pt, circle(fill: red), circle(fill: blue)),
+    /// )
+    /// ```
+    #[default(RatioComponent(Ratio::one()))]
+    pub opacity: RatioComponent,
+
+    /// How the colors of the block and its contents are combined with the
+    /// colors behind it.
+    ///
+    /// ```example
+    /// #block(
+    ///   fill: yellow,
+    ///   inset: 6pt,
+    ///   block(blend: "multiply", fill: aqua, inset: 6pt)[Multiplied],
+    /// )
+    /// ```
+    #[default(BlendMode::Normal)]
+    pub blend: BlendMode,
+
     /// Whether this block must stick to the following one, with no break in
     /// between.
     ///
@@ -499,6 +729,9 @@ impl Packed<BlockElem> {
         // Build the pod regions.
         let pod = unbreakable_pod(&width.into(), &height, &inset, styles, base);
 
+        // The body and the clip shape are laid out separately.
+        let mut locator = locator.split();
+
         // Layout the body.
         let body = self.body(styles);
         let mut frame = match body {
@@ -508,20 +741,20 @@ impl Packed<BlockElem> {
 
             // If we have content as our body, just layout it.
             Some(BlockBody::Content(body)) => {
-                layout_frame(engine, body, locator.relayout(), styles, pod)?
+                layout_frame(engine, body, locator.next(&()), styles, pod)?
             }
 
             // If we have a child that wants to layout with just access to the
             // base region, give it that.
             Some(BlockBody::SingleLayouter(callback)) => {
-                callback.call(engine, locator, styles, pod)?
+                callback.call(engine, locator.next(&()), styles, pod)?
             }
 
             // If we have a child that wants to layout with full region access,
             // we layout it.
-            Some(BlockBody::MultiLayouter(callback)) => {
-                callback.call(engine, locator, styles, pod.into())?.into_frame()
-            }
+            Some(BlockBody::MultiLayouter(callback)) => callback
+                .call(engine, locator.next(&()), styles, pod.into())?
+                .into_frame(),
         };
 
         // Explicit blocks are boundaries for gradient relativeness.
@@ -560,6 +793,34 @@ impl Packed<BlockElem> {
             frame.fill_and_stroke(fill, &stroke, &outset, &radius, self.span());
         }
 
+        // Clip to the clip shape, if any.
+        if let Some(shape) = self.clip_shape(styles) {
+            let path = layout_clip_shape(
+                engine,
+                &shape,
+                locator.next(&shape.span()),
+                styles,
+                frame.size(),
+            )?;
+            frame.clip(path);
+        }
+
+        // Mask the frame, if requested.
+        if let Some(mask) = self.mask(styles) {
+            frame.mask(area_mask(mask, frame.size(), &outset, &radius, self.span()));
+        }
+
+        // Add a shadow behind the frame, if requested.
+        if let Some(shadow) = self.shadow(styles) {
+            frame.shadow(&shadow, &outset, &radius, self.span());
+        }
+
+        // Blur the frame, if requested.
+        frame.blur(self.blur(styles));
+
+        // Composite the frame as a whole, if requested.
+        frame.composite(self.opacity(styles).0, self.blend(styles));
+
         // Assign label to each frame in the fragment.
         if let Some(label) = self.label() {
             frame.group(|group| group.label = Some(label));
@@ -591,6 +852,10 @@ impl Packed<BlockElem> {
         let pod =
             breakable_pod(&width.into(), &height, &inset, styles, regions, &mut buf);
 
+        // The body and the clip shape are laid out separately.
+        let mut locator = locator.split();
+        let body_locator = locator.next(&());
+
         // Layout the body.
         let body = self.body(styles);
         let mut fragment = match body {
@@ -613,7 +878,7 @@ impl Packed<BlockElem> {
             // If we have content as our body, just layout it.
             Some(BlockBody::Content(body)) => {
                 let mut fragment =
-                    layout_fragment(engine, body, locator.relayout(), styles, pod)?;
+                    layout_fragment(engine, body, body_locator.relayout(), styles, pod)?;
 
                 // If the body is automatically sized and produced more than one
                 // fragment, ensure that the width was consistent across all
@@ -634,7 +899,7 @@ impl Packed<BlockElem> {
                         expand: Axes::new(true, pod.expand.y),
                         ..pod
                     };
-                    fragment = layout_fragment(engine, body, locator, styles, pod)?;
+                    fragment = layout_fragment(engine, body, body_locator, styles, pod)?;
                 }
 
                 fragment
@@ -644,7 +909,9 @@ impl Packed<BlockElem> {
             // base region, give it that.
             Some(BlockBody::SingleLayouter(callback)) => {
                 let pod = Region::new(pod.base(), pod.expand);
-                callback.call(engine, locator, styles, pod).map(Fragment::frame)?
+                callback
+                    .call(engine, body_locator, styles, pod)
+                    .map(Fragment::frame)?
             }
 
             // If we have a child that wants to layout with full region access,
@@ -656,7 +923,7 @@ impl Packed<BlockElem> {
             Some(BlockBody::MultiLayouter(callback)) => {
                 let expand = (pod.expand | regions.expand) & pod.size.map(Abs::is_finite);
                 let pod = Regions { expand, ..pod };
-                callback.call(engine, locator, styles, pod)?
+                callback.call(engine, body_locator, styles, pod)?
             }
         };
 
@@ -673,6 +940,12 @@ impl Packed<BlockElem> {
 
         // Fetch/compute these outside of the loop.
         let clip = self.clip(styles);
+        let clip_shape = self.clip_shape(styles);
+        let mask = self.mask(styles);
+        let opacity = self.opacity(styles).0;
+        let blend = self.blend(styles);
+        let shadow = self.shadow(styles);
+        let blur = self.blur(styles);
         let has_fill_or_stroke = fill.is_some() || stroke.iter().any(Option::is_some);
         let has_inset = !inset.is_zero();
         let is_explicit = matches!(body, None | Some(BlockBody::Content(_)));
@@ -718,6 +991,36 @@ impl Packed<BlockElem> {
                     self.span(),
                 );
             }
+
+            // Clip to the clip shape, if any.
+            if let Some(shape) = &clip_shape {
+                let path = layout_clip_shape(
+                    engine,
+                    shape,
+                    locator.next(&shape.span()),
+                    styles,
+                    frame.size(),
+                )?;
+                frame.clip(path);
+            }
+
+            // Mask the frame, if requested.
+            if let Some(mask) = &mask {
+                let mask =
+                    area_mask(mask.clone(), frame.size(), &outset, &radius, self.span());
+                frame.mask(mask);
+            }
+
+            // Add a shadow behind the frame, if requested.
+            if let Some(shadow) = &shadow {
+                frame.shadow(shadow, &outset, &radius, self.span());
+            }
+
+            // Blur the frame, if requested.
+            frame.blur(blur);
+
+            // Composite the frame as a whole, if requested.
+            frame.composite(opacity, blend);
         }
 
         // Assign label to each frame in the fragment.
@@ -731,6 +1034,70 @@ impl Packed<BlockElem> {
     }
 }
 
+/// Lays out a clip shape in a container's area and combines the geometry of
+/// all shapes in it into one path.
+fn layout_clip_shape(
+    engine: &mut Engine,
+    shape: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    size: Size,
+) -> SourceResult<Path> {
+    // Shapes are blocks themselves, so a clip shape that is set for all
+    // blocks must not apply to the clip shape again.
+    let styled = shape.clone().styled(BlockElem::set_clip_shape(None));
+    let pod = Region::new(size, Axes::splat(false));
+    let frame = layout_frame(engine, &styled, locator, styles, pod)?;
+    let mut path = Path::new();
+    collect_geometry(&mut path, &frame, Transform::identity());
+    if path.0.is_empty() {
+        bail!(shape.span(), "clip shape must contain at least one shape");
+    }
+    Ok(path)
+}
+
+/// Collects the geometry of all shapes in a frame into a path.
+pub(crate) fn collect_geometry(path: &mut Path, frame: &Frame, ts: Transform) {
+    for (pos, item) in frame.items() {
+        let ts = ts.pre_concat(Transform::translate(pos.x, pos.y));
+        match item {
+            FrameItem::Group(group) => {
+                collect_geometry(path, &group.frame, ts.pre_concat(group.transform));
+            }
+            FrameItem::Shape(shape, _) => {
+                // Lines enclose no area, so they don't affect clipping, but
+                // text can still follow them.
+                let mut sub = match &shape.geometry {
+                    Geometry::Rect(size) => Path::rect(*size),
+                    Geometry::Path(sub) => sub.clone(),
+                    Geometry::Line(end) => {
+                        let mut sub = Path::new();
+                        sub.move_to(Point::zero());
+                        sub.line_to(*end);
+                        sub
+                    }
+                };
+                sub.transform(ts);
+                path.0.extend(sub.0);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Creates a mask that covers a container's area, including its outset.
+fn area_mask(
+    mask: Mask,
+    size: Size,
+    outset: &Sides<Rel<Abs>>,
+    radius: &Corners<Rel<Abs>>,
+    span: Span,
+) -> GroupMask {
+    let mut frame = Frame::soft(size);
+    frame.fill_and_stroke(Some(mask.paint), &Sides::splat(None), outset, radius, span);
+    GroupMask { frame, mode: mask.mode }
+}
+
 /// The contents of a block.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum BlockBody {
@@ -1013,6 +1380,11 @@ mod callbacks {
                 pub fn call(&self, $($param: $param_ty),*) -> $ret {
                     (self.f)(&self.captured, $($param),*)
                 }
+
+                /// The element that is laid out by this callback.
+                pub fn captured(&self) -> &Content {
+                    &self.captured
+                }
             }
         };
     }
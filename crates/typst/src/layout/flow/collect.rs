@@ -9,18 +9,23 @@ use once_cell::unsync::Lazy;
 
 use crate::diag::{bail, SourceResult};
 use crate::engine::{Engine, Route, Sink, Traced};
-use crate::foundations::{Packed, Resolve, Smart, StyleChain};
+use crate::foundations::{NativeElement, Packed, Resolve, Smart, StyleChain};
 use crate::introspection::{
     Introspector, Locator, LocatorLink, SplitLocator, Tag, TagElem,
 };
 use crate::layout::{
-    layout_frame, Abs, AlignElem, Alignment, Axes, BlockElem, ColbreakElem,
-    FixedAlignment, FlushElem, Fr, Fragment, Frame, PagebreakElem, PlaceElem,
-    PlacementScope, Ratio, Region, Regions, Rel, Size, Sizing, Spacing, VElem,
+    layout_frame, Abs, AlignElem, Alignment, Angle, Axes, BlockBody, BlockElem,
+    ColbreakElem, Dir, FixedAlignment, FlushElem, Fr, Fragment, Frame, FrameItem,
+    GridElem, GroupItem, PagebreakElem, PlaceElem, PlacementScope, Point, Ratio, Region,
+    Regions, Rel, Size, Sizing, Spacing, Transform, VElem,
 };
-use crate::model::ParElem;
+use crate::model::{ParElem, TableElem};
 use crate::realize::Pair;
-use crate::text::TextElem;
+use crate::text::{TextDir, TextElem};
+use crate::visualize::{
+    ArcElem, CircleElem, CombineElem, EllipseElem, ImageElem, LineElem, PathElem,
+    PolygonElem, RectElem, SectorElem, SquareElem,
+};
 use crate::World;
 
 /// Collects all elements of the flow into prepared children. These are much
@@ -187,13 +192,19 @@ impl<'a> Collector<'a, '_, '_> {
             Smart::Custom(Spacing::Fr(fr)) => Child::Fr(fr),
         };
 
+        // In vertical writing mode, graphics and tables stay upright. They are
+        // laid out in one piece with the page's axes and turned back against
+        // the page.
+        let upright = TextElem::dir_in(styles) == Dir::TTB && is_upright(elem, styles);
+
         self.output.push(spacing(elem.above(styles)));
 
-        if !breakable || sticky || fr.is_some() {
+        if upright || !breakable || sticky || fr.is_some() {
             self.output.push(Child::Single(self.boxed(SingleChild {
                 align,
                 sticky,
                 fr,
+                upright,
                 elem,
                 styles,
                 locator,
@@ -318,6 +329,7 @@ pub struct SingleChild<'a> {
     pub align: Axes<FixedAlignment>,
     pub sticky: bool,
     pub fr: Option<Fr>,
+    upright: bool,
     elem: &'a Packed<BlockElem>,
     styles: StyleChain<'a>,
     locator: Locator<'a>,
@@ -328,7 +340,15 @@ impl SingleChild<'_> {
     /// Build the child's frame given the region's base size.
     pub fn layout(&self, engine: &mut Engine, base: Size) -> SourceResult<Frame> {
         self.cell.get_or_init(base, |base| {
-            layout_single_impl(
+            // Upright blocks are horizontal on their own.
+            let horizontal = TextElem::set_dir(TextDir(Smart::Auto)).wrap();
+            let (styles, base) = if self.upright {
+                (self.styles.chain(&horizontal), Size::new(base.y, base.x))
+            } else {
+                (self.styles, base)
+            };
+
+            let frame = layout_single_impl(
                 engine.world,
                 engine.introspector,
                 engine.traced,
@@ -336,13 +356,55 @@ impl SingleChild<'_> {
                 engine.route.track(),
                 self.elem,
                 self.locator.track(),
-                self.styles,
+                styles,
                 base,
-            )
+            )?;
+
+            Ok(if self.upright { turn_back(frame) } else { frame })
         })
     }
 }
 
+/// Whether a block holds a graphic or a table, which stay upright in vertical
+/// writing mode, rather than text.
+fn is_upright(elem: &Packed<BlockElem>, styles: StyleChain) -> bool {
+    let captured = match elem.body(styles) {
+        Some(BlockBody::SingleLayouter(callback)) => callback.captured(),
+        Some(BlockBody::MultiLayouter(callback)) => callback.captured(),
+        _ => return false,
+    };
+
+    [
+        ImageElem::elem(),
+        RectElem::elem(),
+        SquareElem::elem(),
+        EllipseElem::elem(),
+        CircleElem::elem(),
+        PolygonElem::elem(),
+        PathElem::elem(),
+        LineElem::elem(),
+        ArcElem::elem(),
+        SectorElem::elem(),
+        CombineElem::elem(),
+        GridElem::elem(),
+        TableElem::elem(),
+    ]
+    .contains(&captured.elem())
+}
+
+/// Turns a frame counterclockwise by a quarter, so that it is upright again
+/// once its vertical page is turned clockwise.
+fn turn_back(frame: Frame) -> Frame {
+    let size = frame.size();
+    let mut group = GroupItem::new(frame);
+    group.transform = Transform::translate(Abs::zero(), size.x)
+        .pre_concat(Transform::rotate(Angle::deg(-90.0)));
+
+    let mut turned = Frame::soft(Size::new(size.y, size.x));
+    turned.push(Point::zero(), FrameItem::Group(group));
+    turned
+}
+
 /// The cached, internal implementation of [`SingleChild::layout`].
 #[comemo::memoize]
 #[allow(clippy::too_many_arguments)]
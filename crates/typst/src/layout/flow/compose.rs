@@ -11,7 +11,7 @@ use crate::introspection::{
     TagKind,
 };
 use crate::layout::{
-    layout_fragment, layout_frame, Abs, Axes, Dir, FixedAlignment, Frame, FrameItem,
+    layout_fragment, layout_frame, Abs, Axes, FixedAlignment, Frame, FrameItem,
     OuterHAlignment, PlacementScope, Point, Region, Regions, Rel, Size,
 };
 use crate::model::{
@@ -146,7 +146,7 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
             }
 
             let width = frame.width();
-            let x = if self.config.columns.dir == Dir::LTR {
+            let x = if self.config.columns.dir.is_positive() {
                 offset
             } else {
                 regions.size.x - offset - width
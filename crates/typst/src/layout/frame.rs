@@ -4,12 +4,13 @@ use std::fmt::{self, Debug, Formatter};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
+use ecow::EcoString;
 use smallvec::SmallVec;
 
 use crate::foundations::{cast, dict, Dict, Label, StyleChain, Value};
 use crate::introspection::Tag;
 use crate::layout::{
-    Abs, Axes, Corners, FixedAlignment, HideElem, Length, Point, Rel, Sides, Size,
+    Abs, Axes, Corners, FixedAlignment, HideElem, Length, Point, Ratio, Rel, Sides, Size,
     Transform,
 };
 use crate::model::{Destination, LinkElem};
@@ -17,7 +18,8 @@ use crate::syntax::Span;
 use crate::text::TextItem;
 use crate::utils::{LazyHash, Numeric};
 use crate::visualize::{
-    ellipse, styled_rect, Color, FixedStroke, Geometry, Image, Paint, Path, Shape,
+    clip_rect, ellipse, styled_rect, BlendMode, Color, FixedStroke, Geometry, Image,
+    MaskMode, Paint, Path, Shadow, Shape,
 };
 
 /// A finished layout with items at fixed positions.
@@ -389,6 +391,78 @@ impl Frame {
         );
     }
 
+    /// Add a shadow behind the frame's contents.
+    ///
+    /// The shadow takes the shape of the frame's background (including outset
+    /// and corner radii) and is only visible outside of it, so that it doesn't
+    /// shine through a translucent fill.
+    pub fn shadow(
+        &mut self,
+        shadow: &Shadow<Abs>,
+        outset: &Sides<Rel<Abs>>,
+        radius: &Corners<Rel<Abs>>,
+        span: Span,
+    ) {
+        let outset = outset.relative_to(self.size());
+        let background = self.size() + outset.sum_by_axis();
+        let spread = shadow.spread.max(-background.x.min(background.y) / 2.0);
+        let size = background + Size::splat(2.0 * spread);
+        if !size.is_finite() || size.x <= Abs::zero() || size.y <= Abs::zero() {
+            return;
+        }
+
+        // Rounded corners grow along with a positive spread.
+        let grown = radius.map(|r| {
+            let r = r.relative_to(background.x.min(background.y));
+            Rel::from(if spread > Abs::zero() && r > Abs::zero() {
+                r + spread
+            } else {
+                r
+            })
+        });
+
+        let mut shape = Frame::soft(size);
+        shape.push_multiple(
+            styled_rect(size, &grown, Some(shadow.paint.clone()), &Sides::splat(None))
+                .into_iter()
+                .map(|x| (Point::zero(), FrameItem::Shape(x, span))),
+        );
+        shape.blur(shadow.blur);
+
+        let pos = Point::new(-outset.left, -outset.top);
+        let offset = pos - Point::splat(spread) + shadow.offset.to_point();
+        let mut layer = Frame::soft(self.size());
+        layer.push_frame(offset, shape);
+
+        // Knock out the area covered by the background: An outer rectangle
+        // that is wound counter-clockwise encloses the whole blurred shadow
+        // and the clockwise outline of the background cuts a hole into it.
+        let reach = Point::splat(1.5 * shadow.blur.max(Abs::zero()));
+        let min = (offset - reach).min(pos);
+        let max = (offset + size.to_point() + reach).max(pos + background.to_point());
+        let mut clip = Path::new();
+        clip.move_to(min);
+        clip.line_to(Point::new(min.x, max.y));
+        clip.line_to(max);
+        clip.line_to(Point::new(max.x, min.y));
+        clip.close_path();
+        let mut hole = clip_rect(background, radius, &Sides::splat(None));
+        hole.transform(Transform::translate(pos.x, pos.y));
+        clip.0.extend(hole.0);
+        layer.clip(clip);
+
+        self.prepend_frame(Point::zero(), layer);
+    }
+
+    /// Blur the contents of the frame with a Gaussian blur of the given radius.
+    ///
+    /// The radius is twice the standard deviation of the blur.
+    pub fn blur(&mut self, radius: Abs) {
+        if !self.is_empty() && radius > Abs::zero() && radius.is_finite() {
+            self.group(|g| g.blur = radius);
+        }
+    }
+
     /// Arbitrarily transform the contents of the frame.
     pub fn transform(&mut self, transform: Transform) {
         if !self.is_empty() {
@@ -407,6 +481,29 @@ impl Frame {
         }
     }
 
+    /// Mask the contents of a frame, making them as transparent as the mask
+    /// dictates.
+    pub fn mask(&mut self, mask: GroupMask) {
+        if !self.is_empty() {
+            self.group(|g| g.mask = Some(mask));
+        }
+    }
+
+    /// Composite the contents of a frame as a whole with the given opacity
+    /// and blend mode.
+    ///
+    /// Overlapping items within the frame do not shine through each other,
+    /// only the finished group is blended with what is behind it. The opacity
+    /// must be in the range from 0% to 100%.
+    pub fn composite(&mut self, opacity: Ratio, blend: BlendMode) {
+        if !self.is_empty() && (opacity != Ratio::one() || !blend.is_normal()) {
+            self.group(|g| {
+                g.opacity = opacity;
+                g.blend = blend;
+            });
+        }
+    }
+
     /// Wrap the frame's contents in a group and modify that group with `f`.
     pub fn group<F>(&mut self, f: F)
     where
@@ -518,7 +615,8 @@ impl FrameKind {
 /// The building block frames are composed of.
 #[derive(Clone, Hash)]
 pub enum FrameItem {
-    /// A subframe with optional transformation and clipping.
+    /// A subframe with optional transformation, clipping, masking and
+    /// compositing.
     Group(GroupItem),
     /// A run of shaped text.
     Text(TextItem),
@@ -546,7 +644,7 @@ impl Debug for FrameItem {
     }
 }
 
-/// A subframe with optional transformation and clipping.
+/// A subframe with optional transformation, clipping, masking and compositing.
 #[derive(Clone, Hash)]
 pub struct GroupItem {
     /// The group's frame.
@@ -555,8 +653,20 @@ pub struct GroupItem {
     pub transform: Transform,
     /// Whether the frame should be a clipping boundary.
     pub clip_path: Option<Path>,
+    /// A mask that determines how opaque each point of the group is.
+    pub mask: Option<GroupMask>,
+    /// The opacity with which the group is composited as a whole.
+    pub opacity: Ratio,
+    /// How the group's colors are combined with the backdrop.
+    pub blend: BlendMode,
+    /// The radius of a Gaussian blur that is applied to the group. Zero if the
+    /// group isn't blurred.
+    pub blur: Abs,
     /// The group's label.
     pub label: Option<Label>,
+    /// Text that stands in for the group's content when it is extracted or
+    /// read out, e.g. a ruby annotation along with its base.
+    pub actual_text: Option<EcoString>,
 }
 
 impl GroupItem {
@@ -566,9 +676,23 @@ impl GroupItem {
             frame,
             transform: Transform::identity(),
             clip_path: None,
+            mask: None,
+            opacity: Ratio::one(),
+            blend: BlendMode::Normal,
+            blur: Abs::zero(),
             label: None,
+            actual_text: None,
         }
     }
+
+    /// Whether the group must be composited on its own before being painted
+    /// onto the backdrop.
+    pub fn is_composited(&self) -> bool {
+        self.mask.is_some()
+            || self.opacity != Ratio::one()
+            || !self.blend.is_normal()
+            || self.blur > Abs::zero()
+    }
 }
 
 impl Debug for GroupItem {
@@ -578,6 +702,16 @@ impl Debug for GroupItem {
     }
 }
 
+/// A mask for a group.
+#[derive(Debug, Clone, Hash)]
+pub struct GroupMask {
+    /// The frame that is painted to form the mask, in the group's coordinate
+    /// system.
+    pub frame: Frame,
+    /// Which channel of the painted frame determines the opacity.
+    pub mode: MaskMode,
+}
+
 /// A physical position in a document.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Position {
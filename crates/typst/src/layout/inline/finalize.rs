@@ -18,9 +18,14 @@ pub fn finalize(
     let width = if !region.x.is_finite()
         || (!expand && lines.iter().all(|line| line.fr().is_zero()))
     {
-        region
-            .x
-            .min(p.hang + lines.iter().map(|line| line.width).max().unwrap_or_default())
+        region.x.min(
+            p.hang
+                + lines
+                    .iter()
+                    .map(|line| line.width + line.overhang.0 + line.overhang.1)
+                    .max()
+                    .unwrap_or_default(),
+        )
     } else {
         region.x
     };
@@ -28,8 +28,11 @@ const LINE_SEPARATOR: char = '\u{2028}'; // We use LS to distinguish justified b
 pub struct Line<'a> {
     /// The items the line is made of.
     pub items: Items<'a>,
-    /// The exact natural width of the line.
+    /// The exact natural width of the line, without the parts of its first
+    /// and last glyph that hang into the margin.
     pub width: Abs,
+    /// How far the line hangs into the left and right margin.
+    pub overhang: (Abs, Abs),
     /// Whether the line should be justified.
     pub justify: bool,
     /// Whether the line ends with a hyphen or dash, either naturally or through
@@ -43,6 +46,7 @@ impl<'a> Line<'a> {
         Self {
             items: Items::new(),
             width: Abs::zero(),
+            overhang: (Abs::zero(), Abs::zero()),
             justify: false,
             dash: None,
         }
@@ -170,9 +174,44 @@ pub fn line<'a>(
     adjust_cj_at_line_boundaries(p, full, &mut items);
 
     // Compute the line's width.
-    let width = items.iter().map(Item::natural_width).sum();
+    let overhang = overhang(&items);
+    let width =
+        items.iter().map(Item::natural_width).sum::<Abs>() - overhang.0 - overhang.1;
 
-    Line { items, width, justify, dash }
+    Line { items, width, overhang, justify, dash }
+}
+
+/// Determine how far the glyphs at the edges of a line hang into the left and
+/// right margin.
+fn overhang(items: &Items) -> (Abs, Abs) {
+    let mut overhang = (Abs::zero(), Abs::zero());
+    let single = items.len() == 1;
+
+    // To the left.
+    if let Some(Item::Text(text)) = items.first() {
+        if let Some(glyph) = text.glyphs.first() {
+            if TextElem::overhang_in(text.styles) && (!single || text.glyphs.len() > 1) {
+                let (start, end) =
+                    TextElem::protrusion_in(text.styles).get(&glyph.font, glyph.c);
+                let ratio = if text.dir.is_positive() { start } else { end };
+                overhang.0 = ratio.of(glyph.x_advance.at(text.size));
+            }
+        }
+    }
+
+    // To the right.
+    if let Some(Item::Text(text)) = items.last() {
+        if let Some(glyph) = text.glyphs.last() {
+            if TextElem::overhang_in(text.styles) && (!single || text.glyphs.len() > 1) {
+                let (start, end) =
+                    TextElem::protrusion_in(text.styles).get(&glyph.font, glyph.c);
+                let ratio = if text.dir.is_positive() { end } else { start };
+                overhang.1 = ratio.of(glyph.x_advance.at(text.size));
+            }
+        }
+    }
+
+    overhang
 }
 
 /// Collects / reshapes all items for the line with the given `range`.
@@ -430,32 +469,8 @@ pub fn commit(
         offset += p.hang;
     }
 
-    // Handle hanging punctuation to the left.
-    if let Some(Item::Text(text)) = line.items.first() {
-        if let Some(glyph) = text.glyphs.first() {
-            if !text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
-                && (line.items.len() > 1 || text.glyphs.len() > 1)
-            {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
-                offset -= amount;
-                remaining += amount;
-            }
-        }
-    }
-
-    // Handle hanging punctuation to the right.
-    if let Some(Item::Text(text)) = line.items.last() {
-        if let Some(glyph) = text.glyphs.last() {
-            if text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
-                && (line.items.len() > 1 || text.glyphs.len() > 1)
-            {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
-                remaining += amount;
-            }
-        }
-    }
+    // Let the edges of the line hang into the margin.
+    offset -= line.overhang.0;
 
     // Determine how much additional space is needed. The justification_ratio is
     // for the first step justification, extra_justification is for the last
@@ -609,27 +624,6 @@ fn add_par_line_marker(
     }
 }
 
-/// How much a character should hang into the end margin.
-///
-/// For more discussion, see:
-/// <https://recoveringphysicist.com/21/>
-fn overhang(c: char) -> f64 {
-    match c {
-        // Dashes.
-        '–' | '—' => 0.2,
-        '-' => 0.55,
-
-        // Punctuation.
-        '.' | ',' => 0.8,
-        ':' | ';' => 0.3,
-
-        // Arabic
-        '\u{60C}' | '\u{6D4}' => 0.4,
-
-        _ => 0.0,
-    }
-}
-
 /// A collection of owned or borrowed paragraph items.
 pub struct Items<'a>(Vec<ItemEntry<'a>>);
 
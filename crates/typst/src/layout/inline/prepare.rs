@@ -83,7 +83,12 @@ pub fn prepare<'a>(
     spans: SpanMapper,
     styles: StyleChain<'a>,
 ) -> SourceResult<Preparation<'a>> {
-    let dir = TextElem::dir_in(styles);
+    // Vertical lines are laid out like horizontal ones and rotated as a whole
+    // when the page is laid out.
+    let dir = match TextElem::dir_in(styles) {
+        Dir::TTB => Dir::LTR,
+        dir => dir,
+    };
     let default_level = match dir {
         Dir::RTL => BidiLevel::rtl(),
         _ => BidiLevel::ltr(),
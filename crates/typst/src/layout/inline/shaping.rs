@@ -12,8 +12,11 @@ use unicode_script::{Script, UnicodeScript};
 
 use super::{Item, Range, SpanMapper};
 use crate::engine::Engine;
-use crate::foundations::{Smart, StyleChain};
-use crate::layout::{Abs, Dir, Em, Frame, FrameItem, Point, Size};
+use crate::foundations::{Resolve, Smart, StyleChain};
+use crate::layout::{
+    Abs, Angle, Dir, Em, Frame, FrameItem, GroupItem, Point, Ratio, Size, Transform,
+};
+use crate::model::{Justification, ParElem};
 use crate::text::{
     decorate, families, features, is_default_ignorable, variant, Font, FontVariant,
     Glyph, Lang, Region, TextElem, TextItem,
@@ -82,6 +85,9 @@ pub struct ShapedGlyph {
     pub c: char,
     /// Whether this glyph is justifiable for CJK scripts.
     pub is_justifiable: bool,
+    /// Whether justification elongates the connection to the preceding glyph
+    /// with kashidas instead of widening this glyph.
+    pub kashida: bool,
     /// The script of the glyph.
     pub script: Script,
 }
@@ -106,6 +112,12 @@ impl ShapedGlyph {
         self.is_justifiable
     }
 
+    /// Whether justification may adjust the glyph's width and the spacing
+    /// after it.
+    pub fn is_expandable(&self) -> bool {
+        !self.is_space() && !self.is_cj_script() && !self.is_cjk_punctuation()
+    }
+
     /// Whether the glyph is part of Chinese or Japanese script (i.e. CJ, not CJK).
     pub fn is_cj_script(&self) -> bool {
         is_cj_script(self.c, self.script)
@@ -228,9 +240,21 @@ impl<'a> ShapedText<'a> {
         let fill = TextElem::fill_in(self.styles);
         let stroke = TextElem::stroke_in(self.styles);
         let span_offset = TextElem::span_offset_in(self.styles);
+        let vertical = TextElem::dir_in(self.styles) == Dir::TTB;
+
+        // Glyph scaling is the same for all expandable glyphs of the text.
+        let (scaling_min, scaling_max) =
+            ParElem::justification_limits_in(self.styles).scaling();
+        let scaling = if justification_ratio < 0.0 {
+            1.0 - scaling_min.get()
+        } else {
+            scaling_max.get() - 1.0
+        };
 
-        for ((font, y_offset), group) in
-            self.glyphs.as_ref().group_by_key(|g| (g.font.clone(), g.y_offset))
+        for ((font, y_offset, upright), group) in self
+            .glyphs
+            .as_ref()
+            .group_by_key(|g| (g.font.clone(), g.y_offset, vertical && is_upright(g.c)))
         {
             let mut range = group[0].range.clone();
             for glyph in group {
@@ -239,30 +263,46 @@ impl<'a> ShapedText<'a> {
             }
 
             let pos = Point::new(offset, top + shift - y_offset.at(self.size));
+            let scale = if upright { 1.0 } else { 1.0 + justification_ratio * scaling };
             let glyphs: Vec<Glyph> = group
                 .iter()
-                .map(|shaped: &ShapedGlyph| {
+                .flat_map(|shaped: &ShapedGlyph| {
                     let adjustability_left = if justification_ratio < 0.0 {
                         shaped.shrinkability().0
                     } else {
                         shaped.stretchability().0
                     };
-                    let adjustability_right = if justification_ratio < 0.0 {
+                    let mut adjustability_right = if justification_ratio < 0.0 {
                         shaped.shrinkability().1
                     } else {
                         shaped.stretchability().1
                     };
 
+                    // The part of the adjustability that is due to scaling is
+                    // applied by scaling the whole text item.
+                    let mut expansion = Em::zero();
+                    if !upright && shaped.is_expandable() {
+                        expansion = shaped.x_advance * scaling;
+                        adjustability_right -= expansion;
+                    }
+
                     let justification_left = adjustability_left * justification_ratio;
                     let mut justification_right =
                         adjustability_right * justification_ratio;
+                    let mut kashida = Em::zero();
                     if shaped.is_justifiable() {
-                        justification_right +=
-                            Em::from_length(extra_justification, self.size)
+                        let extra = Em::from_length(extra_justification, self.size);
+                        if shaped.kashida {
+                            kashida = extra;
+                        } else {
+                            justification_right += extra;
+                        }
                     }
 
                     frame.size_mut().x += justification_left.at(self.size)
-                        + justification_right.at(self.size);
+                        + justification_right.at(self.size)
+                        + kashida.at(self.size)
+                        + (expansion * justification_ratio).at(self.size);
 
                     // We may not be able to reach the offset completely if
                     // it exceeds u16, but better to have a roughly correct
@@ -288,16 +328,26 @@ impl<'a> ShapedText<'a> {
                     // D: justification_right
                     // A+B: Glyph's x_offset
                     // A+B+C+D: Glyph's x_advance
-                    Glyph {
+                    //
+                    // If the text item is scaled, everything but the glyph
+                    // itself is scaled back.
+                    let natural = if shaped.is_expandable() { scale } else { 1.0 };
+                    let glyph = Glyph {
                         id: shaped.glyph_id,
-                        x_advance: shaped.x_advance
+                        x_advance: (shaped.x_advance * natural
                             + justification_left
-                            + justification_right,
-                        x_offset: shaped.x_offset + justification_left,
+                            + justification_right)
+                            / scale,
+                        x_offset: (shaped.x_offset * natural + justification_left)
+                            / scale,
                         range: (shaped.range.start - range.start).saturating_as()
                             ..(shaped.range.end - range.start).saturating_as(),
                         span,
-                    }
+                    };
+
+                    let mut glyphs = kashidas(&font, kashida, &glyph);
+                    glyphs.push(glyph);
+                    glyphs
                 })
                 .collect();
 
@@ -312,15 +362,20 @@ impl<'a> ShapedText<'a> {
                 glyphs,
             };
 
-            let width = item.width();
-            if decos.is_empty() {
-                frame.push(pos, FrameItem::Text(item));
+            let width = item.width() * scale;
+            if upright {
+                push_upright(&mut frame, pos, &item);
+            } else if decos.is_empty() {
+                push_scaled(&mut frame, pos, item, scale);
+                offset += width;
+                continue;
             } else {
-                // Apply line decorations.
-                frame.push(pos, FrameItem::Text(item.clone()));
-                for deco in &decos {
-                    decorate(&mut frame, deco, &item, width, shift, pos);
-                }
+                push_scaled(&mut frame, pos, item.clone(), scale);
+            }
+
+            // Apply line decorations.
+            for deco in &decos {
+                decorate(&mut frame, deco, &item, width, shift, pos);
             }
 
             offset += width;
@@ -497,6 +552,7 @@ impl<'a> ShapedText<'a> {
                 safe_to_break: true,
                 c: '-',
                 is_justifiable: false,
+                kashida: false,
                 script: Script::Common,
             };
             match side {
@@ -655,6 +711,58 @@ fn is_compatible(a: Script, b: Script) -> bool {
     is_generic_script(a) || is_generic_script(b) || a == b
 }
 
+/// Push a text item, scaled horizontally by the given factor.
+fn push_scaled(frame: &mut Frame, pos: Point, item: TextItem, scale: f64) {
+    if scale == 1.0 {
+        frame.push(pos, FrameItem::Text(item));
+        return;
+    }
+
+    let mut inner = Frame::soft(Size::zero());
+    inner.push(Point::zero(), FrameItem::Text(item));
+    let mut group = GroupItem::new(inner);
+    group.transform = Transform::scale(Ratio::new(scale), Ratio::one());
+    frame.push(pos, FrameItem::Group(group));
+}
+
+/// Push the glyphs of a text item one by one, each turned counterclockwise by
+/// a quarter around the center of its cell.
+///
+/// Vertical lines are laid out horizontally and turned clockwise as a whole,
+/// so this keeps upright glyphs upright.
+fn push_upright(frame: &mut Frame, pos: Point, item: &TextItem) {
+    let font = &item.font;
+    let metrics = font.metrics();
+    let center = ((metrics.ascender + metrics.descender) / 2.0).at(item.size);
+
+    let mut offset = Abs::zero();
+    for glyph in &item.glyphs {
+        let id = ttf_parser::GlyphId(glyph.id);
+        let width = font.advance(glyph.id).unwrap_or(glyph.x_advance).at(item.size);
+        let height = font
+            .ttf()
+            .glyph_ver_advance(id)
+            .map_or(glyph.x_advance, |advance| font.to_em(advance))
+            .at(item.size);
+
+        let mut single = item.clone();
+        single.glyphs = vec![Glyph {
+            x_advance: Em::from_length(width, item.size),
+            x_offset: Em::zero(),
+            ..glyph.clone()
+        }];
+
+        let mut inner = Frame::soft(Size::zero());
+        inner.push(Point::new(-width / 2.0, center), FrameItem::Text(single));
+
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::rotate(-Angle::deg(90.0));
+        let x = pos.x + offset + glyph.x_offset.at(item.size) + height / 2.0;
+        frame.push(Point::new(x, pos.y - center), FrameItem::Group(group));
+        offset += glyph.x_advance.at(item.size);
+    }
+}
+
 /// Shape text into [`ShapedText`].
 #[allow(clippy::too_many_arguments)]
 fn shape<'a>(
@@ -677,6 +785,7 @@ fn shape<'a>(
         features: features(styles),
         fallback: TextElem::fallback_in(styles),
         dir,
+        vertical: TextElem::dir_in(styles) == Dir::TTB,
     };
 
     if !text.is_empty() {
@@ -685,6 +794,7 @@ fn shape<'a>(
 
     track_and_space(&mut ctx);
     calculate_adjustability(&mut ctx, lang, region);
+    calculate_kashidas(&mut ctx);
 
     #[cfg(debug_assertions)]
     assert_all_glyphs_in_range(&ctx.glyphs, text, base..(base + text.len()));
@@ -716,6 +826,7 @@ struct ShapingContext<'a, 'v> {
     features: Vec<rustybuzz::Feature>,
     fallback: bool,
     dir: Dir,
+    vertical: bool,
 }
 
 /// Shape text with font fallback using the `families` iterator.
@@ -817,7 +928,16 @@ fn shape_segment<'a>(
 
             let c = text[cluster..].chars().next().unwrap();
             let script = c.script();
-            let x_advance = font.to_em(pos[i].x_advance);
+            let mut x_advance = font.to_em(pos[i].x_advance);
+
+            // Upright glyphs in vertical text advance by their height.
+            if ctx.vertical && is_upright(c) {
+                let id = ttf_parser::GlyphId(info.glyph_id as u16);
+                if let Some(advance) = font.ttf().glyph_ver_advance(id) {
+                    x_advance = font.to_em(advance);
+                }
+            }
+
             ctx.glyphs.push(ShapedGlyph {
                 font: font.clone(),
                 glyph_id: info.glyph_id as u16,
@@ -835,6 +955,7 @@ fn shape_segment<'a>(
                     x_advance,
                     Adjustability::default().stretchability,
                 ),
+                kashida: false,
                 script,
             });
         } else {
@@ -925,6 +1046,7 @@ fn shape_tofus(ctx: &mut ShapingContext, base: usize, text: &str, font: Font) {
                 x_advance,
                 Adjustability::default().stretchability,
             ),
+            kashida: false,
             script,
         });
     };
@@ -965,9 +1087,27 @@ fn track_and_space(ctx: &mut ShapingContext) {
 /// and CJK punctuation adjustments according to Chinese Layout Requirements.
 fn calculate_adjustability(ctx: &mut ShapingContext, lang: Lang, region: Option<Region>) {
     let style = cjk_punct_style(lang, region);
+    let limits = ParElem::justification_limits_in(ctx.styles);
+    let (tracking_min, tracking_max) = limits.tracking();
+    let tracking_min = Em::from_length(tracking_min.resolve(ctx.styles), ctx.size);
+    let tracking_max = Em::from_length(tracking_max.resolve(ctx.styles), ctx.size);
+    let (scaling_min, scaling_max) = limits.scaling();
 
     for glyph in &mut ctx.glyphs {
         glyph.adjustability = glyph.base_adjustability(style);
+
+        let width = glyph.x_advance;
+        if let Some((min, max)) = limits.spacing().filter(|_| glyph.is_space()) {
+            glyph.adjustability = Adjustability {
+                stretchability: (Em::zero(), width * (max.get() - 1.0)),
+                shrinkability: (Em::zero(), width * (1.0 - min.get())),
+            };
+        } else if glyph.is_expandable() && !(ctx.vertical && is_upright(glyph.c)) {
+            let Adjustability { stretchability, shrinkability } =
+                &mut glyph.adjustability;
+            stretchability.1 += tracking_max + width * (scaling_max.get() - 1.0);
+            shrinkability.1 += -tracking_min + width * (1.0 - scaling_min.get());
+        }
     }
 
     let mut glyphs = ctx.glyphs.iter_mut().peekable();
@@ -994,6 +1134,165 @@ fn calculate_adjustability(ctx: &mut ShapingContext, lang: Lang, region: Option<
     }
 }
 
+/// Mark where justification may elongate Arabic-script words with kashidas.
+///
+/// Each word gets at most one such position, chosen by the priorities that
+/// are common in Arabic typography. If justification shall not widen spaces,
+/// the spaces of Arabic-script text lose their stretchability.
+fn calculate_kashidas(ctx: &mut ShapingContext) {
+    let justification = ParElem::justification_in(ctx.styles);
+    if justification == Justification::Spacing
+        || ctx.dir.is_positive()
+        || !ctx
+            .glyphs
+            .iter()
+            .any(|g| matches!(g.script, Script::Arabic | Script::Syriac))
+    {
+        return;
+    }
+
+    if justification == Justification::Kashida {
+        for glyph in ctx.glyphs.iter_mut().filter(|g| g.is_space()) {
+            glyph.adjustability.stretchability = (Em::zero(), Em::zero());
+            glyph.is_justifiable = false;
+        }
+    }
+
+    // The clusters in logical order, each with the index of its leftmost
+    // glyph. Since the text is right-to-left, kashidas are inserted to the
+    // left of a cluster.
+    let mut clusters: Vec<(usize, char)> = vec![];
+    for (i, glyph) in ctx.glyphs.iter().enumerate().rev() {
+        match clusters.last_mut() {
+            Some((j, _)) if ctx.glyphs[*j].range == glyph.range => *j = i,
+            _ => clusters.push((i, glyph.c)),
+        }
+    }
+
+    for word in clusters.split(|&(_, c)| !is_joining(c)) {
+        let best = word
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| {
+                let ((i, c), (k, next)) = (pair[0], pair[1]);
+                is_dual_joining(c)
+                    && !(c == 'ل' && matches!(next, 'آ' | 'أ' | 'إ' | 'ا'))
+                    && ctx.glyphs[i].font == ctx.glyphs[k].font
+                    && ctx.glyphs[i].font.ttf().glyph_index(KASHIDA).is_some()
+            })
+            .min_by_key(|&(n, pair)| {
+                (
+                    kashida_priority(pair[0].1, pair[1].1, n + 2 == word.len()),
+                    usize::MAX - n,
+                )
+            });
+
+        if let Some((_, pair)) = best {
+            let glyph = &mut ctx.glyphs[pair[0].0];
+            glyph.kashida = true;
+            glyph.is_justifiable = true;
+        }
+    }
+}
+
+/// The character that elongates the connection between two letters.
+const KASHIDA: char = '\u{0640}';
+
+/// Create the kashidas that elongate the connection to the left of a glyph by
+/// the given amount.
+///
+/// The kashidas may overlap slightly so that they fill the amount exactly.
+fn kashidas(font: &Font, amount: Em, glyph: &Glyph) -> Vec<Glyph> {
+    let Some(id) = font.ttf().glyph_index(KASHIDA) else { return vec![] };
+    let Some(width) = font.advance(id.0).filter(|width| *width > Em::zero()) else {
+        return vec![];
+    };
+
+    if amount <= Em::zero() {
+        return vec![];
+    }
+
+    let count = (amount / width).ceil().max(1.0);
+    let glyph = Glyph {
+        id: id.0,
+        x_advance: amount / count,
+        x_offset: Em::zero(),
+        ..glyph.clone()
+    };
+
+    vec![glyph; count as usize]
+}
+
+/// How suitable the connection between two letters is for a kashida. Lower is
+/// better.
+fn kashida_priority(c: char, next: char, next_is_last: bool) -> u8 {
+    match (c, next) {
+        // After seen, sheen, sad, and dad.
+        ('س' | 'ش' | 'ص' | 'ض', _) => 0,
+        // Before a final teh marbuta, heh, or dal.
+        (_, 'ة' | 'ه' | 'د') if next_is_last => 1,
+        // Before a final alef, tah, lam, kaf, or gaf.
+        (_, 'ا' | 'ط' | 'ل' | 'ك' | 'ک' | 'گ') if next_is_last => 2,
+        // Before a final reh, waw, or yeh.
+        (_, 'ر' | 'و' | 'ي' | 'ى' | 'ی') if next_is_last => 3,
+        _ => 4,
+    }
+}
+
+/// Whether a character is an Arabic or Syriac letter that connects to the
+/// preceding letter.
+fn is_joining(c: char) -> bool {
+    matches!(c,
+        '\u{0620}'..='\u{063F}'
+        | '\u{0641}'..='\u{064A}'
+        | '\u{066E}'..='\u{066F}'
+        | '\u{0671}'..='\u{06D3}'
+        | '\u{06D5}'
+        | '\u{06EE}'..='\u{06EF}'
+        | '\u{06FA}'..='\u{06FC}'
+        | '\u{06FF}'
+        | '\u{0710}'..='\u{072F}'
+        | '\u{074D}'..='\u{077F}'
+    ) && c != '\u{0621}'
+        && c != '\u{0674}'
+}
+
+/// Whether a character is an Arabic or Syriac letter that connects to both the
+/// preceding and the following letter.
+fn is_dual_joining(c: char) -> bool {
+    is_joining(c)
+        && !matches!(c,
+            '\u{0622}'..='\u{0625}'
+            | '\u{0627}'
+            | '\u{0629}'
+            | '\u{062F}'..='\u{0632}'
+            | '\u{0648}'
+            | '\u{0671}'..='\u{0673}'
+            | '\u{0675}'..='\u{0677}'
+            | '\u{0688}'..='\u{0699}'
+            | '\u{06C0}'
+            | '\u{06C3}'..='\u{06CB}'
+            | '\u{06CD}'
+            | '\u{06CF}'
+            | '\u{06D2}'..='\u{06D3}'
+            | '\u{06D5}'
+            | '\u{06EE}'..='\u{06EF}'
+            | '\u{0710}'
+            | '\u{0715}'..='\u{0719}'
+            | '\u{071E}'
+            | '\u{0728}'
+            | '\u{072A}'
+            | '\u{072C}'
+            | '\u{072F}'
+            | '\u{074D}'
+            | '\u{0759}'..='\u{075B}'
+            | '\u{076B}'..='\u{076C}'
+            | '\u{0771}'
+            | '\u{0773}'..='\u{0774}'
+            | '\u{0778}'..='\u{0779}'
+        )
+}
+
 /// Difference between non-breaking and normal space.
 fn nbsp_delta(font: &Font) -> Option<Em> {
     let space = font.ttf().glyph_index(' ')?.0;
@@ -1083,6 +1382,26 @@ fn is_space(c: char) -> bool {
     matches!(c, ' ' | '\u{00A0}' | '　')
 }
 
+/// Whether a character stays upright in vertical text instead of being set
+/// sideways.
+///
+/// This is a simplified version of the `U` and `Tu` classes of
+/// [UAX #50](https://www.unicode.org/reports/tr50/).
+pub(crate) fn is_upright(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'
+        | '\u{2E80}'..='\u{A4CF}'
+        | '\u{A960}'..='\u{A97F}'
+        | '\u{AC00}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE10}'..='\u{FE1F}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF00}'..='\u{FFEF}'
+        | '\u{1F200}'..='\u{1F2FF}'
+        | '\u{20000}'..='\u{3FFFD}'
+    )
+}
+
 /// Whether the glyph is part of Chinese or Japanese script (i.e. CJ, not CJK).
 pub fn is_of_cj_script(c: char) -> bool {
     is_cj_script(c, c.script())
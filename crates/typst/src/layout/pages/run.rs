@@ -7,13 +7,13 @@ use crate::introspection::{
     Counter, CounterDisplayElem, CounterKey, Introspector, Locator, LocatorLink, TagElem,
 };
 use crate::layout::{
-    layout_flow, layout_frame, Abs, AlignElem, Alignment, Axes, Binding, ColumnsElem,
-    Dir, Frame, HAlignment, Length, OuterVAlignment, PageElem, Paper, Region, Regions,
-    Rel, Sides, Size, VAlignment,
+    layout_flow, layout_frame, Abs, AlignElem, Alignment, Angle, Axes, Binding,
+    ColumnsElem, Dir, Frame, FrameItem, GroupItem, HAlignment, Length, OuterVAlignment,
+    PageElem, Paper, Point, Region, Regions, Rel, Sides, Size, Transform, VAlignment,
 };
 use crate::model::Numbering;
 use crate::realize::Pair;
-use crate::text::TextElem;
+use crate::text::{TextDir, TextElem};
 use crate::utils::Numeric;
 use crate::visualize::Paint;
 use crate::World;
@@ -160,19 +160,32 @@ fn layout_page_run_impl(
         (header.as_ref().unwrap_or(&None), footer.as_ref().unwrap_or(&numbering_marginal))
     };
 
-    // Layout the children.
+    // In vertical writing mode, the children are laid out into an area with
+    // swapped axes. Each resulting frame is then turned clockwise by a
+    // quarter so that lines run from top to bottom and follow each other
+    // from right to left.
+    let vertical = TextElem::dir_in(styles) == Dir::TTB;
     let area = size - margin.sum_by_axis();
+    let flow_area = if vertical { Size::new(area.y, area.x) } else { area };
+
+    // Layout the children.
     let fragment = layout_flow(
         &mut engine,
         children,
         &mut locator,
         styles,
-        Regions::repeat(area, area.map(Abs::is_finite)),
+        Regions::repeat(flow_area, flow_area.map(Abs::is_finite)),
         PageElem::columns_in(styles),
         ColumnsElem::gutter_in(styles),
         true,
     )?;
 
+    // Marginals stay horizontal. The direction is reset in the style chain
+    // rather than through a set rule, which would be rejected inside of the
+    // marginal's container.
+    let horizontal = TextElem::set_dir(TextDir(Smart::Auto)).wrap();
+    let marginal_styles = if vertical { styles.chain(&horizontal) } else { styles };
+
     // Layouts a single marginal.
     let mut layout_marginal = |content: &Option<Content>, area, align| {
         let Some(content) = content else { return Ok(None) };
@@ -181,7 +194,7 @@ fn layout_page_run_impl(
             &mut engine,
             &aligned,
             locator.next(&content.span()),
-            styles,
+            marginal_styles,
             Region::new(area, Axes::splat(true)),
         )
         .map(Some)
@@ -190,6 +203,7 @@ fn layout_page_run_impl(
     // Layout marginals.
     let mut layouted = Vec::with_capacity(fragment.len());
     for inner in fragment {
+        let inner = if vertical { turn(inner) } else { inner };
         let header_size = Size::new(inner.width(), margin.top - header_ascent);
         let footer_size = Size::new(inner.width(), margin.bottom - footer_descent);
         let full_size = inner.size() + margin.sum_by_axis();
@@ -211,6 +225,18 @@ fn layout_page_run_impl(
     Ok(layouted)
 }
 
+/// Turns a frame clockwise by a quarter, keeping its top-left corner in place.
+fn turn(frame: Frame) -> Frame {
+    let size = frame.size();
+    let mut group = GroupItem::new(frame);
+    group.transform = Transform::translate(size.y, Abs::zero())
+        .pre_concat(Transform::rotate(Angle::deg(90.0)));
+
+    let mut turned = Frame::soft(Size::new(size.y, size.x));
+    turned.push(Point::zero(), FrameItem::Group(group));
+    turned
+}
+
 /// Determines the styles used for a page run itself and page-level content like
 /// marginals and footnotes.
 ///
@@ -58,15 +58,17 @@ pub use typst_utils as utils;
 
 use std::collections::HashSet;
 use std::ops::{Deref, Range};
+use std::sync::Arc;
 
 use comemo::{Track, Tracked, Validate};
 use ecow::{eco_format, eco_vec, EcoString, EcoVec};
 use typst_timing::{timed, TimingScope};
 
 use crate::diag::{
-    warning, FileError, FileResult, SourceDiagnostic, SourceResult, Warned,
+    warning, FileError, FileResult, Recovered, SourceDiagnostic, SourceResult, Warned,
 };
 use crate::engine::{Engine, Route, Sink, Traced};
+use crate::eval::debug::{Attached, Debugger};
 use crate::foundations::{
     Array, Bytes, Datetime, Dict, Module, Scope, StyleChain, Styles, Value,
 };
@@ -91,6 +93,46 @@ pub fn compile(world: &dyn World) -> Warned<SourceResult<Document>> {
     Warned { output, warnings: sink.warnings() }
 }
 
+/// Compile sources into a fully layouted document, recovering from errors.
+///
+/// Instead of failing compilation, markup expressions and show rules that
+/// produce errors are replaced with a visible placeholder and syntax errors
+/// only skip the erroneous parts. This is useful for previews, which can then
+/// keep showing most of the document while it is being edited.
+///
+/// - Returns `Ok(recovered)` with the document and all errors that were
+///   recovered from.
+/// - Returns `Err(errors)` if there were errors that could not be recovered
+///   from.
+#[typst_macros::time]
+pub fn compile_recovering(
+    world: &dyn World,
+) -> Warned<SourceResult<Recovered<Document>>> {
+    let mut sink = Sink::new();
+    let traced = Traced::default().with_recovery();
+    let output = compile_impl(world.track(), traced.track(), &mut sink)
+        .map(|output| Recovered { output, errors: deduplicate(sink.delayed()) })
+        .map_err(deduplicate);
+    Warned { output, warnings: sink.warnings() }
+}
+
+/// Compile sources into a fully layouted document while a debugger observes
+/// the evaluation.
+///
+/// The debugger is notified before each statement and may block to pause
+/// evaluation. See the [`debug`](crate::eval::debug) module for details.
+#[typst_macros::time]
+pub fn compile_with_debugger(
+    world: &dyn World,
+    debugger: Arc<dyn Debugger>,
+) -> Warned<SourceResult<Document>> {
+    let mut sink = Sink::new();
+    let traced = Traced::default().with_debugger(Attached::new(debugger));
+    let output =
+        compile_impl(world.track(), traced.track(), &mut sink).map_err(deduplicate);
+    Warned { output, warnings: sink.warnings() }
+}
+
 /// Compiles sources and returns all values and styles observed at the given
 /// `span` during compilation.
 #[typst_macros::time]
@@ -170,10 +212,12 @@ fn compile_impl(
 
     sink.extend_from_sink(subsink);
 
-    // Promote delayed errors.
-    let delayed = sink.delayed();
-    if !delayed.is_empty() {
-        return Err(delayed);
+    // Promote delayed errors, unless we are recovering from them.
+    if !traced.recovering() {
+        let delayed = sink.delayed();
+        if !delayed.is_empty() {
+            return Err(delayed);
+        }
     }
 
     Ok(document)
@@ -0,0 +1,43 @@
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, Args, Construct, Content, Packed, Show, ShowSet, StyleChain, Styles,
+};
+use crate::text::TextElem;
+use crate::visualize::Color;
+
+/// A placeholder for content that failed to compile.
+///
+/// Produced in place of failing markup expressions, set rules, and show rules
+/// when compiling for a preview, so that the rest of the document stays
+/// visible while it is being edited. Shows the first error message in red.
+#[elem(Construct, Show, ShowSet)]
+pub struct ErrorElem {
+    /// The message of the first error that occurred.
+    #[required]
+    pub message: EcoString,
+}
+
+impl Construct for ErrorElem {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
+impl Show for Packed<ErrorElem> {
+    #[typst_macros::time(name = "error", span = self.span())]
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(TextElem::packed(eco_format!("error: {}", self.message()))
+            .spanned(self.span()))
+    }
+}
+
+impl ShowSet for Packed<ErrorElem> {
+    fn show_set(&self, _: StyleChain) -> Styles {
+        let mut out = Styles::new();
+        out.set(TextElem::set_fill(Color::RED.into()));
+        out
+    }
+}
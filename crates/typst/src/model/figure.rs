@@ -267,12 +267,9 @@ impl Synthesize for Packed<FigureElem> {
             Smart::Auto => {
                 // Default to the local name for the kind, if available.
                 let name = match &kind {
-                    FigureKind::Elem(func) => func
-                        .local_name(
-                            TextElem::lang_in(styles),
-                            TextElem::region_in(styles),
-                        )
-                        .map(TextElem::packed),
+                    FigureKind::Elem(func) => {
+                        func.local_name(styles).map(TextElem::packed)
+                    }
                     FigureKind::Name(_) => None,
                 };
 
@@ -6,6 +6,7 @@ mod document;
 mod emph;
 #[path = "enum.rs"]
 mod enum_;
+mod error;
 mod figure;
 mod footnote;
 mod heading;
@@ -26,6 +27,7 @@ pub use self::cite::*;
 pub use self::document::*;
 pub use self::emph::*;
 pub use self::enum_::*;
+pub use self::error::*;
 pub use self::figure::*;
 pub use self::footnote::*;
 pub use self::heading::*;
@@ -59,14 +59,23 @@ pub fn numbering(
     context: Tracked<Context>,
     /// Defines how the numbering works.
     ///
-    /// **Counting symbols** are `1`, `a`, `A`, `i`, `I`, `一`, `壹`, `あ`, `い`,
-    /// `ア`, `イ`, `א`, `가`, `ㄱ`, `*`, `①`, and `⓵`. They are replaced by the
-    /// number in the sequence, preserving the original case.
+    /// **Counting symbols** are `1`, `a`, `A`, `i`, `I`, `α`, `Α`, `ա`, `Ա`,
+    /// `ა`, `一`, `壹`, `あ`, `い`, `ア`, `イ`, `א`, `가`, `ㄱ`, `๑`, `௧`, `*`,
+    /// `①`, and `⓵`, as well as the words `one`, `One`, `first`, and `First`.
+    /// They are replaced by the number in the sequence, preserving the original
+    /// case. The words are only recognized when they stand on their own, so
+    /// `Zone 1` counts with `1` and keeps its `one`.
     ///
     /// The `*` character means that symbols should be used to count, in the
     /// order of `*`, `†`, `‡`, `§`, `¶`, `‖`. If there are more than six
     /// items, the number is represented using repeated symbols.
     ///
+    /// The Greek (`α`, `Α`), Armenian (`ա`, `Ա`), and Georgian (`ა`) symbols
+    /// use the traditional additive numeral systems, in which each letter
+    /// stands for a value and the values are added up. The words count in
+    /// spelled-out English cardinal (`one`, `two`) or ordinal (`first`,
+    /// `second`) numbers.
+    ///
     /// **Suffixes** are all characters after the last counting symbol. They are
     /// repeated as-is at the end of any rendered number.
     ///
@@ -142,7 +151,8 @@ cast! {
 /// How to turn a number into text.
 ///
 /// A pattern consists of a prefix, followed by one of `1`, `a`, `A`, `i`, `I`,
-/// `一`, `壹`, `あ`, `い`, `ア`, `イ`, `א`, `가`, `ㄱ`, `*`, `①`, or `⓵`, and then a
+/// `α`, `Α`, `ա`, `Ա`, `ა`, `一`, `壹`, `あ`, `い`, `ア`, `イ`, `א`, `가`, `ㄱ`,
+/// `๑`, `௧`, `*`, `①`, `⓵`, `one`, `One`, `first`, or `First`, and then a
 /// suffix.
 ///
 /// Examples of valid patterns:
@@ -218,13 +228,29 @@ impl FromStr for NumberingPattern {
         let mut handled = 0;
 
         for (i, c) in pattern.char_indices() {
-            let Some(kind) = NumberingKind::from_char(c) else {
+            // Skip the remaining characters of a word.
+            if i < handled {
+                continue;
+            }
+
+            // Words only count when they don't start in the middle of another
+            // word, so that e.g. `Zone 1` keeps its `one`.
+            let word =
+                if pattern[..i].chars().next_back().is_some_and(char::is_alphanumeric) {
+                    None
+                } else {
+                    NumberingKind::from_word(&pattern[i..])
+                };
+
+            let Some((kind, len)) = word
+                .or_else(|| NumberingKind::from_char(c).map(|kind| (kind, c.len_utf8())))
+            else {
                 continue;
             };
 
             let prefix = pattern[handled..i].into();
             pieces.push((prefix, kind));
-            handled = c.len_utf8() + i;
+            handled = len + i;
         }
 
         let suffix = pattern[handled..].into();
@@ -242,7 +268,7 @@ cast! {
         let mut pat = EcoString::new();
         for (prefix, kind) in &self.pieces {
             pat.push_str(prefix);
-            pat.push(kind.to_char());
+            pat.push_str(kind.to_str());
         }
         pat.push_str(&self.suffix);
         pat.into_value()
@@ -311,6 +337,28 @@ pub enum NumberingKind {
     CircledNumber,
     /// Double-circled numbers (⓵, ⓶, ⓷, etc.), up to 10.
     DoubleCircledNumber,
+    /// Lowercase Greek numerals (αʹ, βʹ, γʹ, etc.), up to 9999.
+    LowerGreek,
+    /// Uppercase Greek numerals (Αʹ, Βʹ, Γʹ, etc.), up to 9999.
+    UpperGreek,
+    /// Lowercase Armenian numerals (ա, բ, գ, etc.), up to 9999.
+    LowerArmenian,
+    /// Uppercase Armenian numerals (Ա, Բ, Գ, etc.), up to 9999.
+    UpperArmenian,
+    /// Georgian numerals (ა, ბ, გ, etc.), up to 19999.
+    Georgian,
+    /// Thai numerals.
+    ThaiNumber,
+    /// Tamil numerals.
+    TamilNumber,
+    /// Lowercase English cardinal numbers (one, two, three, etc.).
+    LowerWord,
+    /// Capitalized English cardinal numbers (One, Two, Three, etc.).
+    CapitalWord,
+    /// Lowercase English ordinal numbers (first, second, third, etc.).
+    LowerOrdinal,
+    /// Capitalized English ordinal numbers (First, Second, Third, etc.).
+    CapitalOrdinal,
 }
 
 impl NumberingKind {
@@ -339,35 +387,75 @@ impl NumberingKind {
             '\u{0995}' => NumberingKind::BengaliLetter,
             '①' => NumberingKind::CircledNumber,
             '⓵' => NumberingKind::DoubleCircledNumber,
+            'α' => NumberingKind::LowerGreek,
+            'Α' => NumberingKind::UpperGreek,
+            'ա' => NumberingKind::LowerArmenian,
+            'Ա' => NumberingKind::UpperArmenian,
+            'ა' => NumberingKind::Georgian,
+            '\u{0E51}' => NumberingKind::ThaiNumber,
+            '\u{0BE7}' => NumberingKind::TamilNumber,
             _ => return None,
         })
     }
 
-    /// The representative character for this numbering kind.
-    pub fn to_char(self) -> char {
+    /// Create a numbering kind from a representative word at the start of the
+    /// text, returning it along with the word's length.
+    ///
+    /// The word must not be followed by further letters or digits, so that
+    /// e.g. `Oneness` is not recognized.
+    pub fn from_word(text: &str) -> Option<(Self, usize)> {
+        [
+            NumberingKind::LowerWord,
+            NumberingKind::CapitalWord,
+            NumberingKind::LowerOrdinal,
+            NumberingKind::CapitalOrdinal,
+        ]
+        .into_iter()
+        .map(|kind| (kind, kind.to_str()))
+        .find(|(_, word)| {
+            text.strip_prefix(word).is_some_and(|rest| {
+                !rest.chars().next().is_some_and(char::is_alphanumeric)
+            })
+        })
+        .map(|(kind, word)| (kind, word.len()))
+    }
+
+    /// The representative symbol for this numbering kind.
+    pub fn to_str(self) -> &'static str {
         match self {
-            Self::Arabic => '1',
-            Self::LowerLatin => 'a',
-            Self::UpperLatin => 'A',
-            Self::LowerRoman => 'i',
-            Self::UpperRoman => 'I',
-            Self::Symbol => '*',
-            Self::Hebrew => 'א',
-            Self::LowerSimplifiedChinese | Self::LowerTraditionalChinese => '一',
-            Self::UpperSimplifiedChinese | Self::UpperTraditionalChinese => '壹',
-            Self::HiraganaAiueo => 'あ',
-            Self::HiraganaIroha => 'い',
-            Self::KatakanaAiueo => 'ア',
-            Self::KatakanaIroha => 'イ',
-            Self::KoreanJamo => 'ㄱ',
-            Self::KoreanSyllable => '가',
-            Self::EasternArabic => '\u{0661}',
-            Self::EasternArabicPersian => '\u{06F1}',
-            Self::DevanagariNumber => '\u{0967}',
-            Self::BengaliNumber => '\u{09E7}',
-            Self::BengaliLetter => '\u{0995}',
-            Self::CircledNumber => '①',
-            Self::DoubleCircledNumber => '⓵',
+            Self::Arabic => "1",
+            Self::LowerLatin => "a",
+            Self::UpperLatin => "A",
+            Self::LowerRoman => "i",
+            Self::UpperRoman => "I",
+            Self::Symbol => "*",
+            Self::Hebrew => "א",
+            Self::LowerSimplifiedChinese | Self::LowerTraditionalChinese => "一",
+            Self::UpperSimplifiedChinese | Self::UpperTraditionalChinese => "壹",
+            Self::HiraganaAiueo => "あ",
+            Self::HiraganaIroha => "い",
+            Self::KatakanaAiueo => "ア",
+            Self::KatakanaIroha => "イ",
+            Self::KoreanJamo => "ㄱ",
+            Self::KoreanSyllable => "가",
+            Self::EasternArabic => "\u{0661}",
+            Self::EasternArabicPersian => "\u{06F1}",
+            Self::DevanagariNumber => "\u{0967}",
+            Self::BengaliNumber => "\u{09E7}",
+            Self::BengaliLetter => "\u{0995}",
+            Self::CircledNumber => "①",
+            Self::DoubleCircledNumber => "⓵",
+            Self::LowerGreek => "α",
+            Self::UpperGreek => "Α",
+            Self::LowerArmenian => "ա",
+            Self::UpperArmenian => "Ա",
+            Self::Georgian => "ა",
+            Self::ThaiNumber => "\u{0E51}",
+            Self::TamilNumber => "\u{0BE7}",
+            Self::LowerWord => "one",
+            Self::CapitalWord => "One",
+            Self::LowerOrdinal => "first",
+            Self::CapitalOrdinal => "First",
         }
     }
 
@@ -498,10 +586,210 @@ impl NumberingKind {
             Self::EasternArabicPersian => decimal('\u{06F0}', n),
             Self::DevanagariNumber => decimal('\u{0966}', n),
             Self::BengaliNumber => decimal('\u{09E6}', n),
+            Self::ThaiNumber => decimal('\u{0E50}', n),
+            Self::TamilNumber => decimal('\u{0BE6}', n),
+
+            Self::LowerGreek => greek_numeral(n, Case::Lower),
+            Self::UpperGreek => greek_numeral(n, Case::Upper),
+            Self::LowerArmenian => additive(
+                n,
+                [
+                    ['ա', 'բ', 'գ', 'դ', 'ե', 'զ', 'է', 'ը', 'թ'],
+                    ['ժ', 'ի', 'լ', 'խ', 'ծ', 'կ', 'հ', 'ձ', 'ղ'],
+                    ['ճ', 'մ', 'յ', 'ն', 'շ', 'ո', 'չ', 'պ', 'ջ'],
+                    ['ռ', 'ս', 'վ', 'տ', 'ր', 'ց', 'ւ', 'փ', 'ք'],
+                ],
+            ),
+            Self::UpperArmenian => additive(
+                n,
+                [
+                    ['Ա', 'Բ', 'Գ', 'Դ', 'Ե', 'Զ', 'Է', 'Ը', 'Թ'],
+                    ['Ժ', 'Ի', 'Լ', 'Խ', 'Ծ', 'Կ', 'Հ', 'Ձ', 'Ղ'],
+                    ['Ճ', 'Մ', 'Յ', 'Ն', 'Շ', 'Ո', 'Չ', 'Պ', 'Ջ'],
+                    ['Ռ', 'Ս', 'Վ', 'Տ', 'Ր', 'Ց', 'Ւ', 'Փ', 'Ք'],
+                ],
+            ),
+            Self::Georgian => {
+                // Georgian has an additional letter for ten thousand.
+                let mut fmt = EcoString::new();
+                let mut rest = n;
+                if (10000..20000).contains(&n) {
+                    fmt.push('ჵ');
+                    rest -= 10000;
+                    if rest == 0 {
+                        return fmt;
+                    }
+                }
+                fmt.push_str(&additive(
+                    rest,
+                    [
+                        ['ა', 'ბ', 'გ', 'დ', 'ე', 'ვ', 'ზ', 'ჱ', 'თ'],
+                        ['ი', 'კ', 'ლ', 'მ', 'ნ', 'ჲ', 'ო', 'პ', 'ჟ'],
+                        ['რ', 'ს', 'ტ', 'ჳ', 'ფ', 'ქ', 'ღ', 'ყ', 'შ'],
+                        ['ჩ', 'ც', 'ძ', 'წ', 'ჭ', 'ხ', 'ჴ', 'ჯ', 'ჰ'],
+                    ],
+                ));
+                fmt
+            }
+
+            Self::LowerWord => english_cardinal(n as u64),
+            Self::CapitalWord => capitalize(english_cardinal(n as u64)),
+            Self::LowerOrdinal => english_ordinal(n),
+            Self::CapitalOrdinal => capitalize(english_ordinal(n)),
         }
     }
 }
 
+/// Stringify a number using Greek numerals, followed by a keraia.
+///
+/// Numbers outside of the range of the system are displayed with Arabic
+/// numerals.
+fn greek_numeral(n: usize, case: Case) -> EcoString {
+    if !(1..10000).contains(&n) {
+        return eco_format!("{n}");
+    }
+
+    let mut fmt = EcoString::new();
+    if n >= 1000 {
+        // The lower numeral sign marks thousands.
+        fmt.push('͵');
+    }
+    fmt.push_str(&additive(
+        n,
+        [
+            ['α', 'β', 'γ', 'δ', 'ε', 'ϛ', 'ζ', 'η', 'θ'],
+            ['ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο', 'π', 'ϟ'],
+            ['ρ', 'σ', 'τ', 'υ', 'φ', 'χ', 'ψ', 'ω', 'ϡ'],
+            ['α', 'β', 'γ', 'δ', 'ε', 'ϛ', 'ζ', 'η', 'θ'],
+        ],
+    ));
+    fmt.push('ʹ');
+
+    match case {
+        Case::Lower => fmt,
+        Case::Upper => fmt.to_uppercase(),
+    }
+}
+
+/// Stringify a number using an additive counting system with one symbol for
+/// each of the nine non-zero digits in the ones, tens, hundreds, and thousands
+/// place.
+///
+/// Numbers outside of the range of the system are displayed with Arabic
+/// numerals.
+fn additive(n: usize, places: [[char; 9]; 4]) -> EcoString {
+    if !(1..10000).contains(&n) {
+        return eco_format!("{n}");
+    }
+
+    let mut fmt = EcoString::new();
+    for (place, symbols) in places.iter().enumerate().rev() {
+        let digit = n / 10usize.pow(place as u32) % 10;
+        if digit > 0 {
+            fmt.push(symbols[digit - 1]);
+        }
+    }
+    fmt
+}
+
+/// Spell out a number in English.
+fn english_cardinal(n: u64) -> EcoString {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty",
+        "ninety",
+    ];
+    const SCALES: [(u64, &str); 6] = [
+        (1_000_000_000_000_000_000, "quintillion"),
+        (1_000_000_000_000_000, "quadrillion"),
+        (1_000_000_000_000, "trillion"),
+        (1_000_000_000, "billion"),
+        (1_000_000, "million"),
+        (1_000, "thousand"),
+    ];
+
+    if n < 20 {
+        return ONES[n as usize].into();
+    } else if n < 100 {
+        return match n % 10 {
+            0 => TENS[(n / 10) as usize].into(),
+            ones => eco_format!("{}-{}", TENS[(n / 10) as usize], ONES[ones as usize]),
+        };
+    } else if n < 1000 {
+        return match n % 100 {
+            0 => eco_format!("{} hundred", ONES[(n / 100) as usize]),
+            rest => eco_format!(
+                "{} hundred {}",
+                ONES[(n / 100) as usize],
+                english_cardinal(rest)
+            ),
+        };
+    }
+
+    let (scale, name) = SCALES.iter().copied().find(|&(scale, _)| n >= scale).unwrap();
+    match n % scale {
+        0 => eco_format!("{} {name}", english_cardinal(n / scale)),
+        rest => eco_format!(
+            "{} {name} {}",
+            english_cardinal(n / scale),
+            english_cardinal(rest)
+        ),
+    }
+}
+
+/// Spell out an ordinal number in English.
+fn english_ordinal(n: usize) -> EcoString {
+    let cardinal = english_cardinal(n as u64);
+
+    // Only the last word changes, as in "twenty-first".
+    let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".into(),
+        "two" => "second".into(),
+        "three" => "third".into(),
+        "five" => "fifth".into(),
+        "eight" => "eighth".into(),
+        "nine" => "ninth".into(),
+        "twelve" => "twelfth".into(),
+        word => match word.strip_suffix('y') {
+            Some(stem) => eco_format!("{stem}ieth"),
+            None => eco_format!("{word}th"),
+        },
+    };
+    eco_format!("{head}{last}")
+}
+
+/// Capitalize the first letter of a word.
+fn capitalize(word: EcoString) -> EcoString {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => word,
+    }
+}
+
 fn hebrew_numeral(mut n: usize) -> EcoString {
     if n == 0 {
         return '-'.into();
@@ -1,13 +1,13 @@
 use std::fmt::{self, Debug, Formatter};
 
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, HintedStrResult, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, scope, Args, Cast, Construct, Content, NativeElement, Packed, Set, Smart,
-    StyleVec, Unlabellable,
+    cast, dict, elem, scope, Args, Cast, Construct, Content, Dict, Fold, FromValue,
+    NativeElement, Packed, Set, Smart, StyleVec, Unlabellable, Value,
 };
 use crate::introspection::{Count, CounterUpdate, Locatable};
-use crate::layout::{Em, HAlignment, Length, OuterHAlignment};
+use crate::layout::{Abs, Em, HAlignment, Length, OuterHAlignment, Ratio};
 use crate::model::Numbering;
 use crate::utils::singleton;
 
@@ -84,6 +84,61 @@ pub struct ParElem {
     #[default(false)]
     pub justify: bool,
 
+    /// How to stretch lines of Arabic-script text when justifying.
+    ///
+    /// In Arabic and Syriac, lines are traditionally justified by elongating
+    /// the connections between letters with kashidas (tatweels) rather than by
+    /// widening the spaces between words. Typst inserts at most one kashida
+    /// sequence per word, at the position where it is most commonly placed.
+    /// Text in other scripts is always justified by spacing.
+    ///
+    /// ```example
+    /// #set page(width: 150pt)
+    /// #set text(lang: "ar", font: "Noto Sans Arabic")
+    /// #set par(justify: true, justification: "kashida")
+    /// هذا نص عربي يمتد على عدة أسطر ليظهر كيف تتم محاذاة الأسطر.
+    /// ```
+    #[ghost]
+    #[default(Justification::Spacing)]
+    pub justification: Justification,
+
+    /// How far justification may adjust the text of a line.
+    ///
+    /// This is a dictionary with any of the following keys, each of which
+    /// maps to a dictionary with a `min` and a `max` value:
+    /// - `spacing`: The width of spaces between words relative to their
+    ///   natural width. Defaults to `{(min: 66.67%, max: 150%)}`.
+    /// - `tracking`: Additional spacing between the characters of a word.
+    ///   The minimum must not be positive and the maximum must not be
+    ///   negative. Defaults to `{(min: 0pt, max: 0pt)}`.
+    /// - `scaling`: The width of the glyphs relative to their natural width,
+    ///   also known as font expansion. Defaults to
+    ///   `{(min: 100%, max: 100%)}`.
+    ///
+    /// Within these limits, all adjustments are applied proportionally.
+    /// Optimized line breaking takes them into account when it judges how
+    /// loose or tight a line is. If a line cannot be filled otherwise, the
+    /// spaces between words are stretched beyond the maximum.
+    ///
+    /// Tracking and scaling are not applied to Chinese and Japanese text,
+    /// which is justified by its own rules.
+    ///
+    /// ```example
+    /// #set page(width: 180pt)
+    /// #set par(justify: true)
+    /// #lorem(20)
+    ///
+    /// #set par(justification-limits: (
+    ///   spacing: (min: 80%, max: 120%),
+    ///   tracking: (min: -0.01em, max: 0.02em),
+    ///   scaling: (min: 98%, max: 102%),
+    /// ))
+    /// #lorem(20)
+    /// ```
+    #[fold]
+    #[ghost]
+    pub justification_limits: JustificationLimits,
+
     /// How to determine line breaks.
     ///
     /// When this property is set to `{auto}`, its default value, optimized line
@@ -185,6 +240,109 @@ pub enum Linebreaks {
     Optimized,
 }
 
+/// How to stretch lines of Arabic-script text when justifying.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Justification {
+    /// Widen the spaces between words.
+    Spacing,
+    /// Elongate words with kashidas. Spaces keep their natural width.
+    Kashida,
+    /// First widen the spaces between words as far as is natural and then
+    /// distribute the remaining space between spaces and kashidas.
+    Both,
+}
+
+/// Limits for how far justification may adjust the text of a line.
+///
+/// Limits are updated (prioritizing the later value) when folded.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct JustificationLimits {
+    spacing: Option<(Ratio, Ratio)>,
+    tracking: Option<(Length, Length)>,
+    scaling: Option<(Ratio, Ratio)>,
+}
+
+impl JustificationLimits {
+    /// The minimum and maximum width of spaces relative to their natural
+    /// width, if customized.
+    pub fn spacing(&self) -> Option<(Ratio, Ratio)> {
+        self.spacing
+    }
+
+    /// The minimum and maximum additional spacing between characters.
+    pub fn tracking(&self) -> (Length, Length) {
+        self.tracking.unwrap_or_default()
+    }
+
+    /// The minimum and maximum width of glyphs relative to their natural
+    /// width.
+    pub fn scaling(&self) -> (Ratio, Ratio) {
+        self.scaling.unwrap_or((Ratio::one(), Ratio::one()))
+    }
+}
+
+impl Fold for JustificationLimits {
+    fn fold(self, outer: Self) -> Self {
+        Self {
+            spacing: self.spacing.or(outer.spacing),
+            tracking: self.tracking.or(outer.tracking),
+            scaling: self.scaling.or(outer.scaling),
+        }
+    }
+}
+
+cast! {
+    JustificationLimits,
+    self => {
+        let limits = |(min, max): (Value, Value)| dict! { "min" => min, "max" => max };
+        let spacing = self.spacing.unwrap_or((Ratio::new(2.0 / 3.0), Ratio::new(1.5)));
+        let (tracking, scaling) = (self.tracking(), self.scaling());
+        dict! {
+            "spacing" => limits((spacing.0.into_value(), spacing.1.into_value())),
+            "tracking" => limits((tracking.0.into_value(), tracking.1.into_value())),
+            "scaling" => limits((scaling.0.into_value(), scaling.1.into_value())),
+        }
+        .into_value()
+    },
+    mut v: Dict => {
+        let spacing = take_limits::<Ratio>(&mut v, "spacing")?;
+        let tracking = take_limits::<Length>(&mut v, "tracking")?;
+        let scaling = take_limits::<Ratio>(&mut v, "scaling")?;
+        v.finish(&["spacing", "tracking", "scaling"])?;
+
+        for (min, max) in spacing.iter().chain(&scaling) {
+            if min.get() <= 0.0 || *min > Ratio::one() {
+                bail!("minimum must be larger than 0% and at most 100%");
+            } else if *max < Ratio::one() {
+                bail!("maximum must be at least 100%");
+            }
+        }
+
+        if let Some((min, max)) = tracking {
+            if min.abs > Abs::zero() || min.em > Em::zero() {
+                bail!("minimum tracking must not be positive");
+            } else if max.abs < Abs::zero() || max.em < Em::zero() {
+                bail!("maximum tracking must not be negative");
+            }
+        }
+
+        Self { spacing, tracking, scaling }
+    },
+}
+
+/// Take a dictionary with a `min` and a `max` entry from a dictionary.
+fn take_limits<T: FromValue>(
+    dict: &mut Dict,
+    key: &str,
+) -> HintedStrResult<Option<(T, T)>> {
+    let Ok(value) = dict.take(key) else { return Ok(None) };
+    let mut limits: Dict = value.cast()?;
+    let min = limits.take("min")?.cast()?;
+    let max = limits.take("max")?.cast()?;
+    limits.finish(&["min", "max"])?;
+    Ok(Some((min, max)))
+}
+
 /// A paragraph break.
 ///
 /// This starts a new paragraph. Especially useful when used within code like
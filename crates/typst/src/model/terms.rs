@@ -4,7 +4,7 @@ use crate::foundations::{
     cast, elem, scope, Array, Content, NativeElement, Packed, Show, Smart, StyleChain,
     Styles,
 };
-use crate::layout::{Dir, Em, HElem, Length, Sides, StackChild, StackElem, VElem};
+use crate::layout::{Em, HElem, Length, Sides, StackChild, StackElem, VElem};
 use crate::model::{ListItemLike, ListLike, ParElem};
 use crate::text::TextElem;
 use crate::utils::Numeric;
@@ -138,7 +138,7 @@ impl Show for Packed<TermsElem> {
         }
 
         let mut padding = Sides::default();
-        if TextElem::dir_in(styles) == Dir::LTR {
+        if TextElem::dir_in(styles).is_positive() {
             padding.left = pad.into();
         } else {
             padding.right = pad.into();
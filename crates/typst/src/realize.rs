@@ -14,13 +14,13 @@ use once_cell::unsync::Lazy;
 use crate::diag::{bail, At, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    Content, Context, ContextElem, Element, NativeElement, Recipe, RecipeIndex, Selector,
-    SequenceElem, Show, ShowSet, Style, StyleChain, StyleVec, StyledElem, Styles,
-    Synthesize, Transformation,
+    Content, Context, ContextElem, Element, Fields, NativeElement, Recipe, RecipeIndex,
+    Selector, SequenceElem, Show, ShowSet, Style, StyleChain, StyleVec, StyledElem,
+    Styles, Synthesize, Transformation,
 };
 use crate::introspection::{Locatable, SplitLocator, Tag, TagElem, TagKind};
 use crate::layout::{
-    AlignElem, BoxElem, HElem, InlineElem, PageElem, PagebreakElem, VElem,
+    AlignElem, BoxElem, Dir, HElem, InlineElem, PageElem, PagebreakElem, VElem,
 };
 use crate::math::{EquationElem, LayoutMath};
 use crate::model::{
@@ -360,6 +360,7 @@ fn visit_show_rules<'a>(
     // Apply a show rule step, if there is one.
     if let Some(step) = step {
         let chained = styles.chain(&map);
+        let span = output.span();
         let result = match step {
             // Apply a user-defined show rule.
             ShowStep::Recipe(recipe, guard) => {
@@ -383,7 +384,13 @@ fn visit_show_rules<'a>(
         //
         // This way, we can ignore errors that only occur in earlier iterations
         // and also show more useful errors at once.
-        output = Cow::Owned(s.engine.delay(result));
+        //
+        // When recovering from errors, a visible placeholder takes the place
+        // of the failed show rule.
+        output = Cow::Owned(match result {
+            Err(errors) if s.engine.traced.recovering() => s.engine.recover(errors, span),
+            result => s.engine.delay(result),
+        });
     }
 
     // Lifetime-extend the realized content if necessary.
@@ -597,6 +604,19 @@ fn visit_styled<'a>(
             // When there are page styles, we "break free" from our show rule cage.
             pagebreak = true;
             s.outside = true;
+        } else if is_writing_mode(style) {
+            // Vertical writing turns whole pages, so switching between it and
+            // horizontal writing starts a new page, like page styles do.
+            let vertical = |styles: StyleChain| TextElem::dir_in(styles) == Dir::TTB;
+            if vertical(outer) != vertical(outer.chain(&*local)) {
+                let RealizationKind::Root(_) = s.kind else {
+                    let span = style.span();
+                    bail!(span, "writing mode cannot be changed inside of containers");
+                };
+
+                pagebreak = true;
+                s.outside = true;
+            }
         }
     }
 
@@ -618,9 +638,9 @@ fn visit_styled<'a>(
     // interruptions, not trailing styles that happen to be in the same `Styles`
     // list, so we trim the local styles.
     if pagebreak {
-        let relevant = local
-            .as_slice()
-            .trim_end_matches(|style| style.element() != Some(PageElem::elem()));
+        let relevant = local.as_slice().trim_end_matches(|style| {
+            style.element() != Some(PageElem::elem()) && !is_writing_mode(style)
+        });
         visit(s, PagebreakElem::shared_weak(), outer.chain(relevant))?;
     }
 
@@ -638,6 +658,14 @@ fn visit_styled<'a>(
     Ok(())
 }
 
+/// Whether the style sets the text direction, which determines whether pages
+/// are written vertically.
+fn is_writing_mode(style: &Style) -> bool {
+    style.property().is_some_and(|property| {
+        property.is(TextElem::elem(), <TextElem as Fields>::Enum::Dir as _)
+    })
+}
+
 /// Tries to group the content in an active group or start a new one if any
 /// grouping rule matches. Returns `true` if the element was grouped.
 fn visit_grouping_rules<'a>(
@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::diag::Hint;
+use crate::diag::{bail, Hint, HintedStrResult};
 use ecow::{eco_format, EcoString};
 
-use crate::foundations::{cast, StyleChain};
+use crate::foundations::{cast, Dict, Fold, StyleChain, Value};
 use crate::layout::Dir;
 use crate::text::TextElem;
 
@@ -236,15 +236,129 @@ pub trait LocalName {
         localized_str(lang, region, Self::KEY)
     }
 
-    /// Gets the local name from the style chain.
-    fn local_name_in(styles: StyleChain) -> &'static str
+    /// Gets the local name from the style chain, taking user-provided
+    /// [translations](TextElem::translations) into account.
+    fn local_name_in(styles: StyleChain) -> EcoString
     where
         Self: Sized,
     {
-        Self::local_name(TextElem::lang_in(styles), TextElem::region_in(styles))
+        localized_term(styles, Self::KEY)
     }
 }
 
+/// Retrieves the localized term for a key in the language and region of the
+/// style chain, preferring user-provided [translations](TextElem::translations)
+/// over the built-in ones.
+pub fn localized_term(styles: StyleChain, key: &str) -> EcoString {
+    let lang = TextElem::lang_in(styles);
+    let region = TextElem::region_in(styles);
+    match TextElem::translations_in(styles).get(lang, region, key) {
+        Some(term) => term.clone(),
+        None => localized_str(lang, region, key).into(),
+    }
+}
+
+/// User-provided translations of localized terms.
+///
+/// Later entries take precedence over earlier ones when folded.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct Translations(Vec<Translation>);
+
+/// A single translated term.
+#[derive(Debug, Clone, PartialEq, Hash)]
+struct Translation {
+    lang: Lang,
+    region: Option<Region>,
+    key: EcoString,
+    term: EcoString,
+}
+
+impl Translations {
+    /// Get the term for a key in the given language and (optionally) region,
+    /// if it was overridden.
+    pub fn get(
+        &self,
+        lang: Lang,
+        region: Option<Region>,
+        key: &str,
+    ) -> Option<&EcoString> {
+        let find = |region: Option<Region>| {
+            self.0
+                .iter()
+                .rev()
+                .find(|t| t.lang == lang && t.region == region && t.key == key)
+                .map(|t| &t.term)
+        };
+        region.and_then(|region| find(Some(region))).or_else(|| find(None))
+    }
+
+    /// Parse the terms for a single language and region.
+    fn parse(
+        lang: Lang,
+        region: Option<Region>,
+        terms: Value,
+    ) -> HintedStrResult<Vec<Translation>> {
+        let pairs: Vec<(EcoString, EcoString)> = match terms {
+            Value::Dict(dict) => dict
+                .into_iter()
+                .map(|(key, term)| Ok((key.into(), term.cast::<EcoString>()?)))
+                .collect::<HintedStrResult<_>>()?,
+            Value::Str(text) => parse_bundle(&text)?
+                .into_iter()
+                .map(|(key, term)| (key.into(), term.into()))
+                .collect(),
+            v => bail!("expected dictionary or string, found {}", v.ty()),
+        };
+
+        let known = parse_language_bundle(Lang::ENGLISH, None).unwrap();
+        pairs
+            .into_iter()
+            .map(|(key, term)| {
+                if !known.contains_key(key.as_str()) {
+                    bail!("unknown translation key: {key}");
+                }
+                Ok(Translation { lang, region, key, term })
+            })
+            .collect()
+    }
+}
+
+impl Fold for Translations {
+    fn fold(self, outer: Self) -> Self {
+        Self(self.0.fold(outer.0))
+    }
+}
+
+cast! {
+    Translations,
+    self => {
+        let mut dict = Dict::new();
+        for t in self.0 {
+            let tag = lang_str(t.lang, t.region);
+            let mut terms = match dict.take(&tag) {
+                Ok(Value::Dict(terms)) => terms,
+                _ => Dict::new(),
+            };
+            terms.insert(t.key.into(), t.term.into_value());
+            dict.insert(tag.into(), terms.into_value());
+        }
+        dict.into_value()
+    },
+    dict: Dict => {
+        let mut translations = vec![];
+        for (tag, terms) in dict {
+            let (lang, region) = match tag.split_once('-') {
+                Some((lang, region)) => (lang, Some(region)),
+                None => (tag.as_str(), None),
+            };
+            let lang = Lang::from_str(lang)?;
+            let region = region.map(Region::from_str).transpose()?;
+            translations.extend(Translations::parse(lang, region, terms)?);
+        }
+        Self(translations)
+    },
+}
+
 /// Retrieves the localized string for a given language and region.
 /// Silently falls back to English if no fitting string exists for
 /// the given language + region. Panics if no fitting string exists
@@ -275,10 +389,15 @@ fn parse_language_bundle(
         return Ok(HashMap::new());
     };
 
+    parse_bundle(language_file)
+}
+
+/// Parses the contents of a translation file.
+fn parse_bundle(text: &str) -> Result<HashMap<&str, &str>, &'static str> {
     let mut bundle = HashMap::new();
-    let lines = language_file.trim().lines();
+    let lines = text.trim().lines();
     for line in lines {
-        if line.trim().starts_with('#') {
+        if line.trim().is_empty() || line.trim().starts_with('#') {
             continue;
         }
         let (key, val) = line
@@ -313,4 +432,22 @@ mod tests {
         assert!(option_eq(region, "US"));
         assert!(!option_eq(region, "AB"));
     }
+
+    #[test]
+    fn test_translations_have_date_names() {
+        let keys = (1..=12)
+            .flat_map(|i| [eco_format!("month-{i}"), eco_format!("month-short-{i}")])
+            .chain((1..=7).flat_map(|i| {
+                [eco_format!("weekday-{i}"), eco_format!("weekday-short-{i}")]
+            }))
+            .collect::<Vec<_>>();
+
+        // Regional variants fall back to their language's names.
+        for (name, file) in TRANSLATIONS.iter().filter(|(name, _)| !name.contains('-')) {
+            let bundle = parse_bundle(file).unwrap();
+            for key in &keys {
+                assert!(bundle.contains_key(key.as_str()), "{name} is missing {key}");
+            }
+        }
+    }
 }
@@ -9,6 +9,7 @@ mod linebreak;
 #[path = "lorem.rs"]
 mod lorem_;
 mod raw;
+mod ruby;
 mod shift;
 #[path = "smallcaps.rs"]
 mod smallcaps_;
@@ -23,6 +24,7 @@ pub use self::lang::*;
 pub use self::linebreak::*;
 pub use self::lorem_::*;
 pub use self::raw::*;
+pub use self::ruby::*;
 pub use self::shift::*;
 pub use self::smallcaps_::*;
 pub use self::smartquote::*;
@@ -44,9 +46,9 @@ use crate::engine::Engine;
 use crate::foundations::{
     cast, category, dict, elem, Args, Array, Cast, Category, Construct, Content, Dict,
     Fold, IntoValue, NativeElement, Never, NoneValue, Packed, PlainText, Repr, Resolve,
-    Scope, Set, Smart, StyleChain,
+    Scope, Set, Smart, StyleChain, Value,
 };
-use crate::layout::{Abs, Axis, Dir, Em, Length, Ratio, Rel};
+use crate::layout::{Abs, Dir, Em, Length, Ratio, Rel};
 use crate::model::ParElem;
 use crate::syntax::Spanned;
 use crate::visualize::{Color, Paint, RelativeTo, Stroke};
@@ -72,6 +74,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define_elem::<HighlightElem>();
     global.define_elem::<SmallcapsElem>();
     global.define_elem::<RawElem>();
+    global.define_elem::<RubyElem>();
     global.define_func::<lower>();
     global.define_func::<upper>();
     global.define_func::<lorem>();
@@ -338,6 +341,40 @@ pub struct TextElem {
     #[ghost]
     pub overhang: bool,
 
+    /// How far characters at the edges of a line hang into the margin, for
+    /// each font.
+    ///
+    /// This is a dictionary from font family names to protrusion tables. A
+    /// table maps characters to a dictionary with `start` and `end` ratios
+    /// that define how much of the character's width hangs into the margin at
+    /// the start and the end of a line, respectively. Missing ratios default
+    /// to `{0%}`. Characters that are not listed fall back to the built-in
+    /// table, which lets dashes and some punctuation hang into the margin at
+    /// the end of a line.
+    ///
+    /// Protrusion only applies if [`overhang`]($text.overhang) is enabled.
+    /// Line breaking takes it into account, so that lines can fit slightly
+    /// more text.
+    ///
+    /// ```example
+    /// #set par(justify: true)
+    /// #set text(protrusion: (
+    ///   "Libertinus Serif": (
+    ///     "“": (start: 50%),
+    ///     "”": (end: 50%),
+    ///     "T": (start: 5%, end: 5%),
+    ///     "A": (start: 5%, end: 5%),
+    ///   ),
+    /// ))
+    ///
+    /// “Typesetting is an art,” they
+    /// said. To which I replied: “Typst
+    /// makes it easy. Also: Try it!”
+    /// ```
+    #[fold]
+    #[ghost]
+    pub protrusion: Protrusion,
+
     /// The top end of the conceptual frame around the text used for layout and
     /// positioning. This affects the size of containers that hold text.
     ///
@@ -399,6 +436,35 @@ pub struct TextElem {
     #[ghost]
     pub region: Option<Region>,
 
+    /// Overrides for the terms Typst inserts automatically, such as the
+    /// supplement of a figure or the title of an outline.
+    ///
+    /// Expects a dictionary that maps language codes, optionally followed by a
+    /// region as in `{"de-AT"}`, to the terms for that language. The terms are
+    /// either given as a dictionary or as a string in the format of Typst's
+    /// built-in translation files, with one `key = term` pair per line. This
+    /// makes it possible to keep them in a separate file and [`read`] it.
+    /// Terms for a region take precedence over those for just the language,
+    /// and terms that aren't overridden fall back to the built-in ones.
+    ///
+    /// The available keys are `figure`, `table`, `equation`, `bibliography`,
+    /// `heading`, `outline`, and `raw` for the names of elements, as well as
+    /// `month-1` to `month-12`, `month-short-1` to `month-short-12`,
+    /// `weekday-1` to `weekday-7`, and `weekday-short-1` to `weekday-short-7`
+    /// for the names used when [displaying dates]($datetime.display).
+    ///
+    /// ```example
+    /// #set text(lang: "de", translations: (
+    ///   de: (figure: "Abb.", outline: "Inhalt"),
+    /// ))
+    ///
+    /// #outline()
+    /// #figure(rect(), caption: [Ein Rechteck])
+    /// ```
+    #[fold]
+    #[ghost]
+    pub translations: Translations,
+
     /// The OpenType writing script.
     ///
     /// The combination of `{lang}` and `{script}` determine how font features,
@@ -434,6 +500,7 @@ pub struct TextElem {
     /// - `{auto}`: Automatically infer the direction from the `lang` property.
     /// - `{ltr}`: Layout text from left to right.
     /// - `{rtl}`: Layout text from right to left.
+    /// - `{ttb}`: Layout text from top to bottom (vertical writing).
     ///
     /// When writing in right-to-left scripts like Arabic or Hebrew, you should
     /// set the [text language]($text.lang) or direction. While individual runs
@@ -453,6 +520,19 @@ pub struct TextElem {
     /// #set text(dir: rtl)
     /// هذا عربي.
     /// ```
+    ///
+    /// In vertical writing mode (`{ttb}`), lines run from top to bottom and
+    /// follow each other from right to left. CJK characters stay upright and
+    /// use their vertical forms while other text is set sideways. Images,
+    /// shapes, and tables stay upright. Vertical writing applies to whole
+    /// pages, so switching to or from it starts a new page and is not allowed
+    /// inside of containers. Page headers and footers stay horizontal.
+    ///
+    /// ```example
+    /// #set page(width: 80pt, height: 80pt)
+    /// #set text(dir: ttb, lang: "ja")
+    /// 縦書きの文章。Typst で組む。
+    /// ```
     #[resolve]
     #[ghost]
     pub dir: TextDir,
@@ -1024,8 +1104,8 @@ cast! {
     TextDir,
     self => self.0.into_value(),
     v: Smart<Dir> => {
-        if v.is_custom_and(|dir| dir.axis() == Axis::Y) {
-            bail!("text direction must be horizontal");
+        if v.is_custom_and(|dir| dir == Dir::BTT) {
+            bail!("text direction must be horizontal or top-to-bottom");
         }
         Self(v)
     },
@@ -1042,6 +1122,96 @@ impl Resolve for TextDir {
     }
 }
 
+/// Per-font tables that define how far characters hang into the margin.
+///
+/// Tables are merged (prioritizing the later value) when folded.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct Protrusion(Vec<(EcoString, char, Ratio, Ratio)>);
+
+impl Protrusion {
+    /// How far a character of the given font hangs into the margin at the
+    /// start and end of a line, relative to its width.
+    pub fn get(&self, font: &Font, c: char) -> (Ratio, Ratio) {
+        let family = &font.info().family;
+        self.0
+            .iter()
+            .find(|(f, fc, ..)| *fc == c && f.eq_ignore_ascii_case(family))
+            .map(|&(_, _, start, end)| (start, end))
+            .unwrap_or_else(|| (Ratio::zero(), Ratio::new(default_protrusion(c))))
+    }
+}
+
+/// How far a character hangs into the margin at the end of a line if the font
+/// doesn't define anything else.
+///
+/// For more discussion, see:
+/// <https://recoveringphysicist.com/21/>
+fn default_protrusion(c: char) -> f64 {
+    match c {
+        // Dashes.
+        '–' | '—' => 0.2,
+        '-' => 0.55,
+
+        // Punctuation.
+        '.' | ',' => 0.8,
+        ':' | ';' => 0.3,
+
+        // Arabic
+        '\u{60C}' | '\u{6D4}' => 0.4,
+
+        _ => 0.0,
+    }
+}
+
+impl Fold for Protrusion {
+    fn fold(self, outer: Self) -> Self {
+        let mut entries = self.0;
+        entries.extend(outer.0);
+        Self(entries)
+    }
+}
+
+cast! {
+    Protrusion,
+    self => {
+        let mut fonts = Dict::new();
+        for (family, c, start, end) in self.0 {
+            if !fonts.contains(&family) {
+                fonts.insert(family.clone().into(), Dict::new().into_value());
+            }
+            if let Ok(Value::Dict(table)) = fonts.at_mut(&family) {
+                if !table.contains(c.encode_utf8(&mut [0; 4])) {
+                    let sides = dict! { "start" => start, "end" => end };
+                    table.insert(c.into(), sides.into_value());
+                }
+            }
+        }
+        fonts.into_value()
+    },
+    v: Dict => {
+        let mut entries = vec![];
+        for (family, table) in v {
+            for (key, value) in table.cast::<Dict>()? {
+                let mut chars = key.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else {
+                    bail!("expected a single character, found {}", key.repr());
+                };
+                let mut sides: Dict = value.cast()?;
+                let start = sides.take("start").ok().map(Value::cast).transpose()?;
+                let end = sides.take("end").ok().map(Value::cast).transpose()?;
+                sides.finish(&["start", "end"])?;
+                entries.push((
+                    family.as_str().into(),
+                    c,
+                    start.unwrap_or_default(),
+                    end.unwrap_or_default(),
+                ));
+            }
+        }
+        Self(entries)
+    },
+}
+
 /// Whether to hyphenate text.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Hyphenate(pub Smart<bool>);
@@ -1221,6 +1391,12 @@ pub(crate) fn features(styles: StyleChain) -> Vec<Feature> {
         feat(b"frac", 1);
     }
 
+    // Upright glyphs in vertical text use their vertical forms. Other glyphs
+    // are set sideways by rotating the whole line, so `vrt2` is not applied.
+    if TextElem::dir_in(styles) == Dir::TTB {
+        feat(b"vert", 1);
+    }
+
     for (tag, value) in TextElem::features_in(styles).0 {
         tags.push(Feature::new(tag, value, ..))
     }
@@ -0,0 +1,194 @@
+use ecow::eco_format;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    cast, elem, Array, Content, NativeElement, Packed, Resolve, Show, StyleChain, Value,
+};
+use crate::introspection::Locator;
+use crate::layout::{
+    layout_frame, Abs, Axes, Em, Frame, InlineElem, InlineItem, Length, OuterVAlignment,
+    Point, Region, Size,
+};
+use crate::text::{TextElem, TextSize};
+
+/// Ruby annotations, small text set alongside base text.
+///
+/// Ruby is commonly used in Japanese and Chinese text to indicate the reading
+/// of characters, for example as furigana. The base text and its annotation
+/// form an unbreakable unit in the paragraph.
+///
+/// If the annotation is given as a single piece of content, it is centered on
+/// the whole base (group ruby). If it is given as an array, each annotation
+/// belongs to one character of the base text (mono ruby). A line may then
+/// break between the characters.
+///
+/// # Example
+/// ```example
+/// #ruby[Tokyo][東京] is the capital.
+///
+/// #ruby("ABC", ("a", "b", "c"))
+/// ```
+#[elem(Show)]
+pub struct RubyElem {
+    /// Where to place the annotation relative to the base text.
+    ///
+    /// ```example
+    /// #ruby(position: bottom)[base][annotation]
+    /// ```
+    #[default(OuterVAlignment::Top)]
+    pub position: OuterVAlignment,
+
+    /// The font size of the annotation.
+    #[default(TextSize(Em::new(0.5).into()))]
+    pub size: TextSize,
+
+    /// The gap between the base text and the annotation.
+    #[resolve]
+    pub gap: Length,
+
+    /// Whether an annotation that is wider than its base may extend over
+    /// neighbouring text.
+    ///
+    /// If enabled, the annotation overhangs by up to half of its font size on
+    /// each side instead of pushing the neighbouring text away. At the start
+    /// and end of a line, the annotation may then extend beyond the line.
+    #[default(true)]
+    pub overhang: bool,
+
+    /// The base text.
+    ///
+    /// For mono ruby, this must be plain text.
+    #[required]
+    pub body: Content,
+
+    /// The annotation.
+    ///
+    /// Either a single piece of content for the whole base or an array with
+    /// one annotation for each character of the base text.
+    #[required]
+    pub annotation: RubyAnnotation,
+}
+
+impl Show for Packed<RubyElem> {
+    #[typst_macros::time(name = "ruby", span = self.span())]
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(InlineElem::layouter(self.clone(), layout_ruby)
+            .pack()
+            .spanned(self.span()))
+    }
+}
+
+/// The annotation of a [`RubyElem`].
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum RubyAnnotation {
+    /// One annotation for the whole base.
+    Group(Content),
+    /// One annotation for each character of the base.
+    Mono(Vec<Content>),
+}
+
+cast! {
+    RubyAnnotation,
+    self => match self {
+        Self::Group(content) => content.into_value(),
+        Self::Mono(annotations) => annotations.into_value(),
+    },
+    v: Content => Self::Group(v),
+    v: Array => Self::Mono(v.into_iter().map(Value::display).collect()),
+}
+
+/// Layout ruby into one frame per unbreakable unit.
+fn layout_ruby(
+    elem: &Packed<RubyElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    region: Size,
+) -> SourceResult<Vec<InlineItem>> {
+    let mut locator = locator.split();
+    let pairs = match elem.annotation() {
+        RubyAnnotation::Group(annotation) => {
+            vec![(elem.body().clone(), annotation.clone())]
+        }
+        RubyAnnotation::Mono(annotations) => {
+            let text = elem.body().plain_text();
+            let clusters: Vec<_> = text.graphemes(true).collect();
+            if clusters.len() != annotations.len() {
+                bail!(
+                    elem.span(),
+                    "expected {} annotations, one for each character of the base, found {}",
+                    clusters.len(),
+                    annotations.len();
+                    hint: "to annotate the whole base at once, pass a single annotation"
+                );
+            }
+
+            clusters
+                .into_iter()
+                .zip(annotations.iter().cloned())
+                .map(|(cluster, annotation)| {
+                    (TextElem::packed(cluster).spanned(elem.body().span()), annotation)
+                })
+                .collect()
+        }
+    };
+
+    let pod = Region::new(region, Axes::splat(false));
+    let mut items = vec![];
+    for (base, annotation) in pairs {
+        let actual = eco_format!("{}({})", base.plain_text(), annotation.plain_text());
+        let annotation = annotation.styled(TextElem::set_size(elem.size(styles)));
+        let base = layout_frame(engine, &base, locator.next(&()), styles, pod)?;
+        let annotation =
+            layout_frame(engine, &annotation, locator.next(&()), styles, pod)?;
+        let mut frame = combine(elem, styles, base, annotation);
+        frame.group(|group| group.actual_text = Some(actual));
+        items.push(InlineItem::Frame(frame));
+    }
+
+    Ok(items)
+}
+
+/// Place an annotation next to its base.
+fn combine(
+    elem: &Packed<RubyElem>,
+    styles: StyleChain,
+    base: Frame,
+    annotation: Frame,
+) -> Frame {
+    let gap = elem.gap(styles);
+    let (bw, aw) = (base.width(), annotation.width());
+
+    // How far the annotation may overhang on each side.
+    let overhang = if elem.overhang(styles) {
+        let limit = elem.size(styles).0.resolve(styles) / 2.0;
+        ((aw - bw) / 2.0).max(Abs::zero()).min(limit)
+    } else {
+        Abs::zero()
+    };
+
+    let width = bw.max(aw - 2.0 * overhang);
+    let height = base.height() + gap + annotation.height();
+    let base_x = (width - bw) / 2.0;
+    let annotation_x = (width - aw) / 2.0;
+
+    let mut frame = Frame::soft(Size::new(width, height));
+    match elem.position(styles) {
+        OuterVAlignment::Top => {
+            let base_y = annotation.height() + gap;
+            frame.set_baseline(base_y + base.baseline());
+            frame.push_frame(Point::new(annotation_x, Abs::zero()), annotation);
+            frame.push_frame(Point::new(base_x, base_y), base);
+        }
+        OuterVAlignment::Bottom => {
+            let annotation_y = base.height() + gap;
+            frame.set_baseline(base.baseline());
+            frame.push_frame(Point::new(base_x, Abs::zero()), base);
+            frame.push_frame(Point::new(annotation_x, annotation_y), annotation);
+        }
+    }
+
+    frame
+}
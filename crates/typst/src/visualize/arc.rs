@@ -0,0 +1,245 @@
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{elem, Content, NativeElement, Packed, Show, Smart, StyleChain};
+use crate::introspection::Locator;
+use crate::layout::{
+    Abs, Angle, BlockElem, Frame, FrameItem, Length, Point, Region, Size,
+};
+use crate::utils::Numeric;
+use crate::visualize::{
+    FillRule, FixedStroke, Geometry, Paint, Path, PathExt, Shape, Stroke,
+};
+
+/// A circular arc.
+///
+/// Angles are measured clockwise, starting from the positive x-axis, just
+/// like for a [`line`]. The arc is laid out in the square that the full
+/// circle would occupy, so that arcs of the same circle line up when they are
+/// [placed]($place) on top of each other.
+///
+/// # Example
+/// ```example
+/// #arc(radius: 20pt, start: 180deg, end: 360deg)
+/// #arc(
+///   radius: 20pt,
+///   start: -90deg,
+///   end: 135deg,
+///   stroke: 3pt + eastern,
+/// )
+/// ```
+#[elem(Show)]
+pub struct ArcElem {
+    /// The radius of the arc's circle.
+    #[resolve]
+    #[default(Abs::pt(15.0).into())]
+    pub radius: Length,
+
+    /// The angle at which the arc starts.
+    pub start: Angle,
+
+    /// The angle at which the arc ends. If it is smaller than the start
+    /// angle, the arc runs counter-clockwise. Arcs sweep at most a full
+    /// circle.
+    #[default(Angle::deg(90.0))]
+    pub end: Angle,
+
+    /// How to [stroke] the arc.
+    ///
+    /// ```example
+    /// #arc(stroke: (paint: blue, thickness: 4pt, cap: "round"))
+    /// ```
+    #[resolve]
+    #[fold]
+    pub stroke: Stroke,
+}
+
+impl Show for Packed<ArcElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::single_layouter(self.clone(), layout_arc)
+            .pack()
+            .spanned(self.span()))
+    }
+}
+
+/// Layout the arc.
+#[typst_macros::time(span = elem.span())]
+fn layout_arc(
+    elem: &Packed<ArcElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    _: Region,
+) -> SourceResult<Frame> {
+    let radius = elem.radius(styles);
+    if !radius.is_finite() {
+        bail!(elem.span(), "cannot create arc with infinite radius");
+    }
+
+    let radius = radius.max(Abs::zero());
+    let start = elem.start(styles);
+    let sweep = sweep(start, elem.end(styles));
+    let center = Point::splat(radius);
+
+    let mut path = Path::new();
+    path.move_to(point_on_circle(center, radius, start));
+    push_arc(&mut path, center, radius, start, sweep);
+
+    let stroke = elem.stroke(styles).unwrap_or_default();
+    let mut frame = Frame::soft(Size::splat(2.0 * radius));
+    let shape = Geometry::Path(path).stroked(stroke);
+    frame.push(Point::zero(), FrameItem::Shape(shape, elem.span()));
+    Ok(frame)
+}
+
+/// A circular sector, i.e. a slice of a pie.
+///
+/// With an inner radius, the sector becomes a slice of a ring instead, which
+/// is useful for donut charts. Just like [arcs]($arc), sectors are laid out in
+/// the square that their full circle would occupy.
+///
+/// # Example
+/// ```example
+/// #let data = ((0.3, red), (0.45, green), (0.25, blue))
+/// #let start = -90deg
+/// #for (share, color) in data {
+///   let end = start + share * 360deg
+///   place(sector(radius: 25pt, start: start, end: end, fill: color))
+///   start = end
+/// }
+/// #v(50pt)
+/// ```
+#[elem(Show)]
+pub struct SectorElem {
+    /// The radius of the sector's circle.
+    #[resolve]
+    #[default(Abs::pt(15.0).into())]
+    pub radius: Length,
+
+    /// The radius of the hole in the middle of the sector.
+    ///
+    /// ```example
+    /// #sector(inner-radius: 8pt, start: 0deg, end: 270deg, fill: aqua)
+    /// ```
+    #[resolve]
+    pub inner_radius: Length,
+
+    /// The angle at which the sector starts.
+    pub start: Angle,
+
+    /// The angle at which the sector ends. If it is smaller than the start
+    /// angle, the sector runs counter-clockwise. Sectors cover at most a full
+    /// circle.
+    #[default(Angle::deg(90.0))]
+    pub end: Angle,
+
+    /// How to fill the sector. See the [rectangle's documentation]($rect.fill)
+    /// for more details.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the sector. See the
+    /// [rectangle's documentation]($rect.stroke) for more details.
+    #[resolve]
+    #[fold]
+    #[default(Smart::Auto)]
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+impl Show for Packed<SectorElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::single_layouter(self.clone(), layout_sector)
+            .pack()
+            .spanned(self.span()))
+    }
+}
+
+/// Layout the sector.
+#[typst_macros::time(span = elem.span())]
+fn layout_sector(
+    elem: &Packed<SectorElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    _: Region,
+) -> SourceResult<Frame> {
+    let radius = elem.radius(styles);
+    let inner = elem.inner_radius(styles);
+    if !radius.is_finite() || !inner.is_finite() {
+        bail!(elem.span(), "cannot create sector with infinite radius");
+    }
+
+    let radius = radius.max(Abs::zero());
+    let inner = inner.max(Abs::zero()).min(radius);
+    let start = elem.start(styles);
+    let sweep = sweep(start, elem.end(styles));
+    let center = Point::splat(radius);
+
+    let mut path = Path::new();
+    if sweep.abs() >= Angle::deg(360.0) {
+        // A full circle, with a hole that is wound the other way round.
+        path.move_to(point_on_circle(center, radius, start));
+        push_arc(&mut path, center, radius, start, sweep);
+        path.close_path();
+        if inner > Abs::zero() {
+            path.move_to(point_on_circle(center, inner, start));
+            push_arc(&mut path, center, inner, start, -sweep);
+            path.close_path();
+        }
+    } else {
+        let end = start + sweep;
+        path.move_to(point_on_circle(center, radius, start));
+        push_arc(&mut path, center, radius, start, sweep);
+        if inner > Abs::zero() {
+            path.line_to(point_on_circle(center, inner, end));
+            push_arc(&mut path, center, inner, end, -sweep);
+        } else {
+            path.line_to(center);
+        }
+        path.close_path();
+    }
+
+    // Prepare fill and stroke.
+    let fill = elem.fill(styles);
+    let stroke = match elem.stroke(styles) {
+        Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
+        Smart::Auto => None,
+        Smart::Custom(stroke) => stroke.map(Stroke::unwrap_or_default),
+    };
+
+    let mut frame = Frame::soft(Size::splat(2.0 * radius));
+    let shape = Shape {
+        geometry: Geometry::Path(path),
+        stroke,
+        fill,
+        fill_rule: FillRule::default(),
+    };
+    frame.push(Point::zero(), FrameItem::Shape(shape, elem.span()));
+    Ok(frame)
+}
+
+/// The signed angle swept from `start` to `end`, limited to a full circle.
+fn sweep(start: Angle, end: Angle) -> Angle {
+    Angle::deg((end - start).to_deg().clamp(-360.0, 360.0))
+}
+
+/// The point on a circle at the given angle.
+fn point_on_circle(center: Point, radius: Abs, angle: Angle) -> Point {
+    center + Point::new(radius * angle.cos(), radius * angle.sin())
+}
+
+/// Adds a circular arc to the path, which must already be at the arc's start.
+///
+/// The arc is split into pieces of at most a quarter circle, each of which is
+/// approximated with a cubic Bézier curve.
+fn push_arc(path: &mut Path, center: Point, radius: Abs, start: Angle, sweep: Angle) {
+    if sweep.is_zero() || radius.is_zero() {
+        return;
+    }
+
+    let pieces = (sweep.abs().to_deg() / 90.0).ceil().max(1.0) as usize;
+    let step = sweep / pieces as f64;
+    for i in 0..pieces {
+        let from = point_on_circle(center, radius, start + step * i as f64);
+        let to = point_on_circle(center, radius, start + step * (i + 1) as f64);
+        path.arc(from, center, to);
+    }
+}
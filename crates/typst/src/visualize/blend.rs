@@ -0,0 +1,55 @@
+use crate::foundations::Cast;
+
+/// How the colors of a group are combined with the colors behind it.
+///
+/// The separable modes (from `multiply` to `exclusion`) combine each color
+/// channel on its own, while the non-separable ones (from `hue` to
+/// `luminosity`) mix hue, saturation and luminosity of the two colors.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum BlendMode {
+    /// Paints the group on top of the backdrop.
+    #[default]
+    Normal,
+    /// Multiplies the colors, which always results in a darker color.
+    Multiply,
+    /// Multiplies the complements of the colors, which always results in a
+    /// lighter color.
+    Screen,
+    /// Multiplies or screens the colors depending on the backdrop color.
+    Overlay,
+    /// Keeps the darker of the two colors.
+    Darken,
+    /// Keeps the lighter of the two colors.
+    Lighten,
+    /// Brightens the backdrop to reflect the group's color.
+    ColorDodge,
+    /// Darkens the backdrop to reflect the group's color.
+    ColorBurn,
+    /// Multiplies or screens the colors depending on the group's color.
+    HardLight,
+    /// Darkens or lightens the colors depending on the group's color.
+    SoftLight,
+    /// Subtracts the darker of the two colors from the lighter one.
+    Difference,
+    /// Like `difference`, but with lower contrast.
+    Exclusion,
+    /// Uses the hue of the group's color with the saturation and luminosity
+    /// of the backdrop.
+    Hue,
+    /// Uses the saturation of the group's color with the hue and luminosity
+    /// of the backdrop.
+    Saturation,
+    /// Uses the hue and saturation of the group's color with the luminosity
+    /// of the backdrop.
+    Color,
+    /// Uses the luminosity of the group's color with the hue and saturation
+    /// of the backdrop.
+    Luminosity,
+}
+
+impl BlendMode {
+    /// Whether this is the normal blend mode.
+    pub fn is_normal(self) -> bool {
+        self == Self::Normal
+    }
+}
@@ -0,0 +1,489 @@
+use std::collections::{HashMap, HashSet};
+
+use kurbo::{PathEl, Vec2};
+
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, Cast, Content, NativeElement, Packed, Show, Smart, StyleChain,
+};
+use crate::introspection::Locator;
+use crate::layout::{
+    collect_geometry, layout_frame, Abs, Axes, BlockElem, Frame, FrameItem, Point,
+    Region, Size, Transform,
+};
+use crate::visualize::{FillRule, FixedStroke, Geometry, Paint, Path, Shape, Stroke};
+
+/// Combines the areas of multiple shapes into one shape.
+///
+/// Each shape is laid out in the available space, so that shapes overlap at
+/// their top-left corners. Use [`move`] or [`place`] to position them
+/// relative to each other. Only the outlines of the shapes are combined, their
+/// fills and strokes are replaced by the ones of the combined shape. Curved
+/// outlines are approximated by many short straight lines.
+///
+/// # Example
+/// ```example
+/// #let a = circle(radius: 20pt)
+/// #let b = move(dx: 20pt, circle(radius: 20pt))
+///
+/// #combine(a, b, fill: aqua)
+/// #combine(operation: "intersection", a, b, fill: aqua)
+/// #combine(operation: "difference", a, b, fill: aqua)
+/// #combine(operation: "xor", a, b, fill: aqua)
+/// ```
+#[elem(title = "Combined Shape", Show)]
+pub struct CombineElem {
+    /// How to combine the shapes.
+    #[default(BooleanOp::Union)]
+    pub operation: BooleanOp,
+
+    /// How to fill the combined shape. See the
+    /// [rectangle's documentation]($rect.fill) for more details.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the combined shape. See the
+    /// [rectangle's documentation]($rect.stroke) for more details.
+    #[resolve]
+    #[fold]
+    #[default(Smart::Auto)]
+    pub stroke: Smart<Option<Stroke>>,
+
+    /// The shapes to combine.
+    #[variadic]
+    pub shapes: Vec<Content>,
+}
+
+impl Show for Packed<CombineElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::single_layouter(self.clone(), layout_combine)
+            .pack()
+            .spanned(self.span()))
+    }
+}
+
+/// Layout the combined shape.
+#[typst_macros::time(span = elem.span())]
+fn layout_combine(
+    elem: &Packed<CombineElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let mut locator = locator.split();
+    let pod = Region::new(region.size, Axes::splat(false));
+    let mut size = Size::zero();
+    let mut operands = vec![];
+    for shape in elem.shapes() {
+        let frame =
+            layout_frame(engine, shape, locator.next(&shape.span()), styles, pod)?;
+        size = size.max(frame.size());
+        let mut path = Path::new();
+        collect_geometry(&mut path, &frame, Transform::identity());
+        operands.push(path);
+    }
+
+    let path = elem.operation(styles).apply(&operands);
+
+    // Prepare fill and stroke.
+    let fill = elem.fill(styles);
+    let stroke = match elem.stroke(styles) {
+        Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
+        Smart::Auto => None,
+        Smart::Custom(stroke) => stroke.map(Stroke::unwrap_or_default),
+    };
+
+    let mut frame = Frame::soft(size);
+    if !path.0.is_empty() {
+        let shape = Shape {
+            geometry: Geometry::Path(path),
+            stroke,
+            fill,
+            fill_rule: FillRule::default(),
+        };
+        frame.push(Point::zero(), FrameItem::Shape(shape, elem.span()));
+    }
+    Ok(frame)
+}
+
+/// A boolean operation on the areas of paths.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum BooleanOp {
+    /// The area covered by any of the paths.
+    #[default]
+    Union,
+    /// The area covered by all of the paths.
+    Intersection,
+    /// The area covered by the first path, but none of the others.
+    Difference,
+    /// The area covered by an odd number of the paths.
+    Xor,
+}
+
+impl BooleanOp {
+    /// Combine the areas of the paths.
+    ///
+    /// The paths are filled with the non-zero rule. Curves are flattened, so
+    /// the resulting path consists of straight lines only. Its outlines are
+    /// wound such that it can be filled with either fill rule.
+    pub fn apply(self, operands: &[Path]) -> Path {
+        let polygons: Vec<Vec<Vec<Vec2>>> = operands.iter().map(flatten).collect();
+        let edges = split_edges(&polygons);
+
+        // Keep exactly those edges that separate the inside of the result from
+        // its outside, oriented such that the inside is to their right.
+        let mut kept = vec![];
+        for (a, b) in edges {
+            let dir = b - a;
+            let normal = Vec2::new(-dir.y, dir.x).normalize() * PROBE;
+            let mid = a.lerp(b, 0.5);
+            let left = self.contains(&polygons, mid + normal);
+            let right = self.contains(&polygons, mid - normal);
+            match (left, right) {
+                (false, true) => kept.push((a, b)),
+                (true, false) => kept.push((b, a)),
+                _ => {}
+            }
+        }
+
+        link_edges(kept)
+    }
+
+    /// Whether a point lies within the combined area.
+    fn contains(self, polygons: &[Vec<Vec<Vec2>>], point: Vec2) -> bool {
+        let mut inside = polygons.iter().map(|polys| winding(polys, point) != 0);
+        match self {
+            Self::Union => inside.any(|b| b),
+            Self::Intersection => !polygons.is_empty() && inside.all(|b| b),
+            Self::Difference => inside.next().unwrap_or(false) && !inside.any(|b| b),
+            Self::Xor => inside.filter(|&b| b).count() % 2 == 1,
+        }
+    }
+}
+
+/// How much curves may deviate from their flattened version, in points.
+const TOLERANCE: f64 = 0.05;
+
+/// Points within this distance of each other are considered the same, in
+/// points.
+const SNAP: f64 = 1e-4;
+
+/// How far to the side of an edge to probe for the inside, in points.
+const PROBE: f64 = 1e-3;
+
+/// Flattens a path into closed polygons.
+fn flatten(path: &Path) -> Vec<Vec<Vec2>> {
+    let mut polygons: Vec<Vec<Vec2>> = vec![];
+    kurbo::flatten(path.to_kurbo(), TOLERANCE, |el| match el {
+        PathEl::MoveTo(p) => polygons.push(vec![p.to_vec2()]),
+        PathEl::LineTo(p) => match polygons.last_mut() {
+            Some(polygon) => polygon.push(p.to_vec2()),
+            None => polygons.push(vec![p.to_vec2()]),
+        },
+        PathEl::ClosePath => {}
+        PathEl::QuadTo(..) | PathEl::CurveTo(..) => unreachable!(),
+    });
+    polygons.retain(|polygon| polygon.len() > 2);
+    polygons
+}
+
+/// The winding number of polygons around a point.
+fn winding(polygons: &[Vec<Vec2>], point: Vec2) -> i32 {
+    let mut winding = 0;
+    for polygon in polygons {
+        for (i, &a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            let side = (b - a).cross(point - a);
+            if a.y <= point.y {
+                if b.y > point.y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= point.y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+    winding
+}
+
+/// Collects the edges of all polygons, split at all points where they
+/// intersect or touch each other, without duplicates.
+fn split_edges(polygons: &[Vec<Vec<Vec2>>]) -> Vec<(Vec2, Vec2)> {
+    let mut edges = vec![];
+    for polygon in polygons.iter().flatten() {
+        for (i, &a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            if (b - a).hypot() > SNAP {
+                edges.push((a, b));
+            }
+        }
+    }
+
+    // Find the parameters at which each edge must be split. Only edges whose
+    // bounding boxes overlap can meet, so the edges are swept from left to
+    // right and each one is only compared with those that start before it
+    // ends.
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&i, &j| {
+        let (a, b) = (edges[i], edges[j]);
+        a.0.x.min(a.1.x).total_cmp(&b.0.x.min(b.1.x))
+    });
+
+    let mut splits: Vec<Vec<f64>> = vec![vec![0.0, 1.0]; edges.len()];
+    for (k, &i) in order.iter().enumerate() {
+        let (a1, b1) = edges[i];
+        let right = a1.x.max(b1.x) + SNAP;
+        let (top, bottom) = (a1.y.min(b1.y) - SNAP, a1.y.max(b1.y) + SNAP);
+        for &j in &order[k + 1..] {
+            let (a2, b2) = edges[j];
+            if a2.x.min(b2.x) > right {
+                break;
+            }
+            if a2.y.max(b2.y) < top || a2.y.min(b2.y) > bottom {
+                continue;
+            }
+
+            let (ts, us) = intersect(edges[i], edges[j]);
+            splits[i].extend(ts);
+            splits[j].extend(us);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut result = vec![];
+    for (&(a, b), mut ts) in edges.iter().zip(splits) {
+        ts.sort_by(f64::total_cmp);
+        for pair in ts.windows(2) {
+            let (from, to) = (a.lerp(b, pair[0]), a.lerp(b, pair[1]));
+            let (kf, kt) = (key(from), key(to));
+            if kf != kt && seen.insert(if kf < kt { (kf, kt) } else { (kt, kf) }) {
+                result.push((from, to));
+            }
+        }
+    }
+    result
+}
+
+/// Finds the parameters at which two edges intersect or touch each other,
+/// for both edges.
+fn intersect((a1, b1): (Vec2, Vec2), (a2, b2): (Vec2, Vec2)) -> (Vec<f64>, Vec<f64>) {
+    let (d1, d2) = (b1 - a1, b2 - a2);
+    let (l1, l2) = (d1.hypot(), d2.hypot());
+    let denom = d1.cross(d2);
+    let offset = a2 - a1;
+    let (mut ts, mut us) = (vec![], vec![]);
+
+    if denom.abs() > 1e-9 * l1 * l2 {
+        let t = offset.cross(d2) / denom;
+        let u = offset.cross(d1) / denom;
+        let (et, eu) = (SNAP / l1, SNAP / l2);
+        if (-et..=1.0 + et).contains(&t) && (-eu..=1.0 + eu).contains(&u) {
+            ts.push(t.clamp(0.0, 1.0));
+            us.push(u.clamp(0.0, 1.0));
+        }
+    } else if offset.cross(d1).abs() <= SNAP * l1 {
+        // The edges are collinear, so each one is split where the other one
+        // starts and ends.
+        let project = |p: Vec2, a: Vec2, d: Vec2, l: f64| (p - a).dot(d) / (l * l);
+        for p in [a2, b2] {
+            let t = project(p, a1, d1, l1);
+            if t > 0.0 && t < 1.0 {
+                ts.push(t);
+            }
+        }
+        for p in [a1, b1] {
+            let u = project(p, a2, d2, l2);
+            if u > 0.0 && u < 1.0 {
+                us.push(u);
+            }
+        }
+    }
+
+    (ts, us)
+}
+
+/// Snaps a point to a grid, so that nearby points can be identified.
+fn key(p: Vec2) -> (i64, i64) {
+    ((p.x / SNAP).round() as i64, (p.y / SNAP).round() as i64)
+}
+
+/// Links oriented edges into closed outlines.
+fn link_edges(edges: Vec<(Vec2, Vec2)>) -> Path {
+    let mut outgoing: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, &(a, _)) in edges.iter().enumerate() {
+        outgoing.entry(key(a)).or_default().push(i);
+    }
+
+    let point = |p: Vec2| Point::new(Abs::raw(p.x), Abs::raw(p.y));
+    let mut used = vec![false; edges.len()];
+    let mut path = Path::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+
+        let start = key(edges[first].0);
+        let mut outline = vec![edges[first].0];
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let end = edges[current].1;
+            if key(end) == start {
+                break;
+            }
+
+            let next = outgoing
+                .get(&key(end))
+                .and_then(|candidates| candidates.iter().find(|&&i| !used[i]));
+            match next {
+                Some(&next) => {
+                    outline.push(end);
+                    current = next;
+                }
+                // Due to numerical issues, an outline might not close up. It
+                // is then closed with a straight line.
+                None => break,
+            }
+        }
+
+        simplify(&mut outline);
+        if outline.len() > 2 {
+            path.move_to(point(outline[0]));
+            for &p in &outline[1..] {
+                path.line_to(point(p));
+            }
+            path.close_path();
+        }
+    }
+
+    path
+}
+
+/// Removes points of a closed outline that lie on a straight line between
+/// their neighbours.
+fn simplify(outline: &mut Vec<Vec2>) {
+    let mut i = 0;
+    while i < outline.len() && outline.len() > 2 {
+        let len = outline.len();
+        let prev = outline[(i + len - 1) % len];
+        let next = outline[(i + 1) % len];
+        let (d1, d2) = (outline[i] - prev, next - outline[i]);
+        if d1.cross(d2).abs() <= SNAP * (d1.hypot() + d2.hypot()) && d1.dot(d2) > 0.0 {
+            outline.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A closed square path with the given top-left corner and side length.
+    fn square(x: f64, y: f64, size: f64) -> Path {
+        let mut path = Path::new();
+        path.move_to(Point::new(Abs::pt(x), Abs::pt(y)));
+        path.line_to(Point::new(Abs::pt(x + size), Abs::pt(y)));
+        path.line_to(Point::new(Abs::pt(x + size), Abs::pt(y + size)));
+        path.line_to(Point::new(Abs::pt(x), Abs::pt(y + size)));
+        path.close_path();
+        path
+    }
+
+    /// The area enclosed by a path's outlines, with holes subtracted.
+    fn area(path: &Path) -> f64 {
+        let signed: f64 = flatten(path)
+            .iter()
+            .map(|polygon| {
+                let n = polygon.len();
+                (0..n).map(|i| polygon[i].cross(polygon[(i + 1) % n])).sum::<f64>() / 2.0
+            })
+            .sum();
+        signed.abs()
+    }
+
+    /// Check the area and the number of outlines of a path.
+    #[track_caller]
+    fn check(path: &Path, expected_area: f64, outlines: usize) {
+        assert!(
+            (area(path) - expected_area).abs() < 1e-6,
+            "expected area {expected_area}, found {}",
+            area(path),
+        );
+        assert_eq!(flatten(path).len(), outlines);
+    }
+
+    #[test]
+    fn test_boolean_overlapping_squares() {
+        let operands = [square(0.0, 0.0, 10.0), square(5.0, 5.0, 10.0)];
+        check(&BooleanOp::Union.apply(&operands), 175.0, 1);
+        check(&BooleanOp::Intersection.apply(&operands), 25.0, 1);
+        check(&BooleanOp::Difference.apply(&operands), 75.0, 1);
+        check(&BooleanOp::Xor.apply(&operands), 150.0, 2);
+    }
+
+    #[test]
+    fn test_boolean_disjoint_squares() {
+        let operands = [square(0.0, 0.0, 10.0), square(20.0, 0.0, 10.0)];
+        check(&BooleanOp::Union.apply(&operands), 200.0, 2);
+        check(&BooleanOp::Intersection.apply(&operands), 0.0, 0);
+        check(&BooleanOp::Difference.apply(&operands), 100.0, 1);
+        check(&BooleanOp::Xor.apply(&operands), 200.0, 2);
+    }
+
+    #[test]
+    fn test_boolean_touching_squares() {
+        // A fully shared edge merges into a single square.
+        let operands = [square(0.0, 0.0, 10.0), square(10.0, 0.0, 10.0)];
+        let union = BooleanOp::Union.apply(&operands);
+        check(&union, 200.0, 1);
+        assert_eq!(flatten(&union)[0].len(), 4);
+        check(&BooleanOp::Intersection.apply(&operands), 0.0, 0);
+
+        // A partially shared, collinear edge is split where the other square
+        // starts and ends.
+        let operands = [square(0.0, 0.0, 10.0), square(10.0, 5.0, 10.0)];
+        let union = BooleanOp::Union.apply(&operands);
+        check(&union, 200.0, 1);
+        assert_eq!(flatten(&union)[0].len(), 8);
+        check(&BooleanOp::Difference.apply(&operands), 100.0, 1);
+    }
+
+    #[test]
+    fn test_boolean_nested_holes() {
+        let outer = square(0.0, 0.0, 30.0);
+        let hole = square(10.0, 10.0, 10.0);
+        let ring = BooleanOp::Difference.apply(&[outer.clone(), hole.clone()]);
+        check(&ring, 800.0, 2);
+
+        // An island within the hole stays separate from the ring.
+        let island = square(12.0, 12.0, 6.0);
+        check(&BooleanOp::Union.apply(&[ring.clone(), island.clone()]), 836.0, 3);
+        check(&BooleanOp::Intersection.apply(&[ring.clone(), island]), 0.0, 0);
+
+        // Filling the hole again yields the outer square.
+        check(&BooleanOp::Union.apply(&[ring, hole]), 900.0, 1);
+    }
+
+    #[test]
+    fn test_boolean_empty_operands() {
+        for op in [
+            BooleanOp::Union,
+            BooleanOp::Intersection,
+            BooleanOp::Difference,
+            BooleanOp::Xor,
+        ] {
+            check(&op.apply(&[]), 0.0, 0);
+            check(&op.apply(&[Path::new(), Path::new()]), 0.0, 0);
+            check(&op.apply(&[square(0.0, 0.0, 10.0)]), 100.0, 1);
+        }
+
+        let operands = [Path::new(), square(0.0, 0.0, 10.0)];
+        check(&BooleanOp::Union.apply(&operands), 100.0, 1);
+        check(&BooleanOp::Intersection.apply(&operands), 0.0, 0);
+        check(&BooleanOp::Difference.apply(&operands), 0.0, 0);
+    }
+}
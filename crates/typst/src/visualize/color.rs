@@ -1888,7 +1888,8 @@ cast! {
 }
 
 /// A component that must be a ratio.
-pub struct RatioComponent(Ratio);
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct RatioComponent(pub Ratio);
 
 cast! {
     RatioComponent,
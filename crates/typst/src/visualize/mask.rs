@@ -0,0 +1,44 @@
+use crate::foundations::{cast, dict, Cast, Dict};
+use crate::visualize::Paint;
+
+/// A mask that makes parts of content transparent.
+///
+/// Can be given as just a paint, which is then used as a luminance mask, or as
+/// a dictionary with the keys `paint` and `mode`.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Mask {
+    /// The paint whose luminance or alpha channel determines the opacity.
+    pub paint: Paint,
+    /// Which channel of the paint determines the opacity.
+    pub mode: MaskMode,
+}
+
+cast! {
+    Mask,
+    self => match self.mode {
+        MaskMode::Luminance => self.paint.into_value(),
+        MaskMode::Alpha => dict! {
+            "paint" => self.paint,
+            "mode" => self.mode,
+        }.into_value(),
+    },
+    paint: Paint => Self { paint, mode: MaskMode::Luminance },
+    mut dict: Dict => {
+        let paint = dict.take("paint")?.cast()?;
+        let mode = dict.take("mode").ok().map(MaskMode::from_value)
+            .transpose()?.unwrap_or_default();
+        dict.finish(&["paint", "mode"])?;
+        Self { paint, mode }
+    },
+}
+
+/// Which channel of a mask determines the opacity of the masked content.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum MaskMode {
+    /// The content is opaque where the mask is white and transparent where
+    /// it is black.
+    #[default]
+    Luminance,
+    /// The content is as opaque as the mask.
+    Alpha,
+}
@@ -1,26 +1,38 @@
 //! Drawing and visualization.
 
+mod arc;
+mod blend;
+mod boolean;
 mod color;
 mod gradient;
 mod image;
 mod line;
+mod mask;
 mod paint;
 mod path;
 mod pattern;
 mod polygon;
+mod shadow;
 mod shape;
 mod stroke;
+mod text_path;
 
+pub use self::arc::*;
+pub use self::blend::*;
+pub use self::boolean::*;
 pub use self::color::*;
 pub use self::gradient::*;
 pub use self::image::*;
 pub use self::line::*;
+pub use self::mask::*;
 pub use self::paint::*;
 pub use self::path::*;
 pub use self::pattern::*;
 pub use self::polygon::*;
+pub use self::shadow::*;
 pub use self::shape::*;
 pub use self::stroke::*;
+pub use self::text_path::*;
 
 use crate::foundations::{category, Category, Scope};
 
@@ -47,4 +59,8 @@ pub(super) fn define(global: &mut Scope) {
     global.define_elem::<CircleElem>();
     global.define_elem::<PolygonElem>();
     global.define_elem::<PathElem>();
+    global.define_elem::<ArcElem>();
+    global.define_elem::<SectorElem>();
+    global.define_elem::<CombineElem>();
+    global.define_elem::<TextPathElem>();
 }
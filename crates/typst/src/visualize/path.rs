@@ -8,7 +8,7 @@ use crate::foundations::{
 };
 use crate::introspection::Locator;
 use crate::layout::{
-    Abs, Axes, BlockElem, Frame, FrameItem, Length, Point, Region, Rel, Size,
+    Abs, Axes, BlockElem, Frame, FrameItem, Length, Point, Region, Rel, Size, Transform,
 };
 use crate::visualize::{FillRule, FixedStroke, Geometry, Paint, Shape, Stroke};
 
@@ -298,6 +298,36 @@ impl Path {
         self.0.push(PathItem::ClosePath);
     }
 
+    /// Convert the path into a `kurbo` path for geometric computations.
+    pub(crate) fn to_kurbo(&self) -> kurbo::BezPath {
+        let point = |p: Point| kurbo::Point::new(p.x.to_raw(), p.y.to_raw());
+        let mut bez = kurbo::BezPath::new();
+        for item in &self.0 {
+            match *item {
+                PathItem::MoveTo(p) => bez.move_to(point(p)),
+                PathItem::LineTo(p) => bez.line_to(point(p)),
+                PathItem::CubicTo(a, b, c) => bez.curve_to(point(a), point(b), point(c)),
+                PathItem::ClosePath => bez.close_path(),
+            }
+        }
+        bez
+    }
+
+    /// Transform all points of the path.
+    pub fn transform(&mut self, ts: Transform) {
+        for item in &mut self.0 {
+            match item {
+                PathItem::MoveTo(p) | PathItem::LineTo(p) => *p = p.transform(ts),
+                PathItem::CubicTo(p1, p2, p3) => {
+                    *p1 = p1.transform(ts);
+                    *p2 = p2.transform(ts);
+                    *p3 = p3.transform(ts);
+                }
+                PathItem::ClosePath => {}
+            }
+        }
+    }
+
     /// Computes the size of bounding box of this path.
     pub fn bbox_size(&self) -> Size {
         let mut min_x = Abs::inf();
@@ -0,0 +1,57 @@
+use crate::foundations::{cast, dict, Dict, Resolve, StyleChain};
+use crate::layout::{Abs, Axes, Length};
+use crate::utils::Numeric;
+use crate::visualize::{Color, Paint};
+
+/// A shadow that is cast by a shape or container.
+///
+/// Given as a dictionary with the optional keys `offset` (an array of two
+/// lengths), `blur`, `spread`, and `paint`. The shadow is half-transparent
+/// black and sits right behind its caster by default.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Shadow<T: Numeric + 'static = Length> {
+    /// How far the shadow is moved away from its caster.
+    pub offset: Axes<T>,
+    /// The radius over which the shadow's edges fade out.
+    pub blur: T,
+    /// How much larger than its caster the shadow is on each side. Negative
+    /// values shrink the shadow.
+    pub spread: T,
+    /// The shadow's paint.
+    pub paint: Paint,
+}
+
+impl Resolve for Shadow {
+    type Output = Shadow<Abs>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        Shadow {
+            offset: self.offset.resolve(styles),
+            blur: self.blur.resolve(styles),
+            spread: self.spread.resolve(styles),
+            paint: self.paint,
+        }
+    }
+}
+
+cast! {
+    Shadow,
+    self => dict! {
+        "offset" => self.offset,
+        "blur" => self.blur,
+        "spread" => self.spread,
+        "paint" => self.paint,
+    }.into_value(),
+    mut dict: Dict => {
+        let offset = dict.take("offset").ok().map(Axes::<Length>::from_value)
+            .transpose()?.unwrap_or_else(|| Axes::splat(Length::zero()));
+        let blur = dict.take("blur").ok().map(Length::from_value)
+            .transpose()?.unwrap_or_default();
+        let spread = dict.take("spread").ok().map(Length::from_value)
+            .transpose()?.unwrap_or_default();
+        let paint = dict.take("paint").ok().map(Paint::from_value)
+            .transpose()?.unwrap_or_else(|| Color::BLACK.with_alpha(0.5).into());
+        dict.finish(&["offset", "blur", "spread", "paint"])?;
+        Self { offset, blur, spread, paint }
+    },
+}
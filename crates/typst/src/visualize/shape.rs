@@ -12,7 +12,7 @@ use crate::layout::{
 };
 use crate::syntax::Span;
 use crate::utils::Get;
-use crate::visualize::{FixedStroke, Paint, Path, Stroke};
+use crate::visualize::{FixedStroke, Paint, Path, Shadow, Stroke};
 
 /// A rectangle with optional content.
 ///
@@ -126,6 +126,19 @@ pub struct RectElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A shadow that the rectangle casts.
+    /// See the [box's documentation]($box.shadow) for more details.
+    ///
+    /// ```example
+    /// #rect(
+    ///   fill: white,
+    ///   radius: 4pt,
+    ///   shadow: (offset: (3pt, 3pt), blur: 4pt, paint: blue.transparentize(50%)),
+    /// )
+    /// ```
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the rectangle.
     ///
     /// When this is omitted, the rectangle takes on a default size of at most
@@ -140,7 +153,7 @@ impl Show for Packed<RectElem> {
         Ok(BlockElem::single_layouter(
             self.clone(),
             |elem, engine, locator, styles, region| {
-                layout_shape(
+                let mut frame = layout_shape(
                     engine,
                     locator,
                     styles,
@@ -153,7 +166,13 @@ impl Show for Packed<RectElem> {
                     elem.outset(styles),
                     elem.radius(styles),
                     elem.span(),
-                )
+                )?;
+                if let Some(shadow) = elem.shadow(styles) {
+                    let outset = elem.outset(styles).unwrap_or_default();
+                    let radius = elem.radius(styles).unwrap_or_default();
+                    frame.shadow(&shadow, &outset, &radius, elem.span());
+                }
+                Ok(frame)
             },
         )
         .with_width(self.width(styles))
@@ -1230,7 +1249,7 @@ impl ControlPoints {
 }
 
 /// Helper to draw arcs with bezier curves.
-trait PathExt {
+pub(crate) trait PathExt {
     fn arc(&mut self, start: Point, center: Point, end: Point);
     fn arc_move(&mut self, start: Point, center: Point, end: Point);
     fn arc_line(&mut self, start: Point, center: Point, end: Point);
@@ -0,0 +1,276 @@
+use kurbo::{ParamCurve, ParamCurveArclen, PathSeg, Vec2};
+
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{elem, Content, NativeElement, Packed, Show, StyleChain};
+use crate::introspection::Locator;
+use crate::layout::{
+    collect_geometry, layout_frame, Abs, Angle, Axes, BlockElem, FixedAlignment, Frame,
+    FrameItem, HAlignment, Length, Point, Region, Rel, Size, Transform,
+};
+use crate::text::{Glyph, TextItem};
+use crate::visualize::{Geometry, Path, PathItem};
+
+/// Text that follows the outline of a shape.
+///
+/// The text is shaped just like normal text, so kerning and ligatures are
+/// preserved. Afterwards, each glyph is placed upright on the outline of the
+/// shape, with its baseline touching the outline. Any shape works, for example
+/// a [`circle`], a [`line`], or a [`path`]. The shape itself is not drawn.
+///
+/// The text is laid out on a single line. Content other than text, like
+/// images or boxes, is placed on the outline as a whole.
+///
+/// # Example
+/// ```example
+/// #text-path(
+///   circle(radius: 30pt),
+///   align: center,
+///   offset: -25%,
+/// )[Quality Approved]
+/// ```
+#[elem(title = "Text on Path", Show)]
+pub struct TextPathElem {
+    /// The shape whose outline the text follows.
+    ///
+    /// The shape is laid out in the available space, so relative sizes are
+    /// resolved relative to it. The text runs in the direction in which the
+    /// outline is drawn. Circles and ellipses are drawn clockwise, starting
+    /// at their rightmost point.
+    #[required]
+    pub path: Content,
+
+    /// How to align the text along the outline.
+    ///
+    /// ```example
+    /// #let arc = path((0pt, 30pt), ((100pt, 30pt), (-20pt, -40pt)))
+    /// #stack(
+    ///   spacing: 8pt,
+    ///   text-path(arc, align: start)[Start],
+    ///   text-path(arc, align: center)[Center],
+    ///   text-path(arc, align: end)[End],
+    /// )
+    /// ```
+    #[resolve]
+    #[default(HAlignment::Start)]
+    pub align: HAlignment,
+
+    /// How far to move the text along the outline, relative to the outline's
+    /// length.
+    ///
+    /// On a closed outline, text that moves past its end continues at its
+    /// start. On an open outline, it continues in a straight line.
+    ///
+    /// ```example
+    /// #text-path(
+    ///   line(length: 100pt),
+    ///   offset: 20pt,
+    /// )[Offset]
+    /// ```
+    #[resolve]
+    pub offset: Rel<Length>,
+
+    /// The text to place along the outline.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<TextPathElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::single_layouter(self.clone(), layout_text_path)
+            .pack()
+            .spanned(self.span()))
+    }
+}
+
+/// Layout the text along the path.
+#[typst_macros::time(span = elem.span())]
+fn layout_text_path(
+    elem: &Packed<TextPathElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let mut locator = locator.split();
+
+    // Lay out the outline and take its geometry.
+    let pod = Region::new(region.size, Axes::splat(false));
+    let shape = layout_frame(engine, elem.path(), locator.next(&()), styles, pod)?;
+    let mut path = Path::new();
+    collect_geometry(&mut path, &shape, Transform::identity());
+
+    // Lay out the text on a single line.
+    let pod = Region::new(Size::splat(Abs::inf()), Axes::splat(false));
+    let text = layout_frame(engine, elem.body(), locator.next(&()), styles, pod)?;
+
+    let curve = Curve::new(&path);
+    if curve.segments.is_empty() {
+        return Ok(text);
+    }
+
+    let length = Abs::raw(curve.length);
+    let align: FixedAlignment = elem.align(styles);
+    let start =
+        align.position(length - text.width()) + elem.offset(styles).relative_to(length);
+    let baseline = first_baseline(&text, Abs::zero()).unwrap_or(text.baseline());
+
+    let mut frame = Frame::soft(shape.size());
+    place_frame(&mut frame, &curve, &text, Point::new(start, -baseline));
+    Ok(frame)
+}
+
+/// Finds the baseline of the first text run in a frame.
+fn first_baseline(frame: &Frame, y: Abs) -> Option<Abs> {
+    frame.items().find_map(|(pos, item)| match item {
+        FrameItem::Text(_) => Some(y + pos.y),
+        FrameItem::Group(group) if group.transform.is_identity() => {
+            first_baseline(&group.frame, y + pos.y)
+        }
+        _ => None,
+    })
+}
+
+/// Places the items of a frame along the curve.
+///
+/// The horizontal coordinate of an item is mapped to a distance along the
+/// curve and the vertical one to a distance from it. The origin is added to
+/// the item positions beforehand.
+fn place_frame(out: &mut Frame, curve: &Curve, frame: &Frame, origin: Point) {
+    for (pos, item) in frame.items() {
+        let pos = origin + *pos;
+        match item {
+            FrameItem::Group(group) if group.transform.is_identity() => {
+                place_frame(out, curve, &group.frame, pos);
+            }
+            FrameItem::Text(text) => place_text(out, curve, text, pos),
+            FrameItem::Group(group) => {
+                let width = group.frame.width();
+                place_item(out, curve, pos, width, item.clone());
+            }
+            FrameItem::Image(_, size, _) | FrameItem::Link(_, size) => {
+                place_item(out, curve, pos, size.x, item.clone());
+            }
+            FrameItem::Shape(shape, _) => {
+                let width = match shape.geometry {
+                    Geometry::Line(end) => end.x.max(Abs::zero()),
+                    Geometry::Rect(size) => size.x,
+                    Geometry::Path(_) => Abs::zero(),
+                };
+                place_item(out, curve, pos, width, item.clone());
+            }
+            FrameItem::Tag(_) => place_item(out, curve, pos, Abs::zero(), item.clone()),
+        }
+    }
+}
+
+/// Places each glyph of a text run on its own along the curve.
+fn place_text(out: &mut Frame, curve: &Curve, text: &TextItem, pos: Point) {
+    let mut x = pos.x;
+    for glyph in &text.glyphs {
+        let range = glyph.range();
+        let item = TextItem {
+            font: text.font.clone(),
+            size: text.size,
+            fill: text.fill.clone(),
+            stroke: text.stroke.clone(),
+            lang: text.lang,
+            region: text.region,
+            text: text.text[range.clone()].into(),
+            glyphs: vec![Glyph { range: 0..range.len() as u16, ..*glyph }],
+        };
+
+        let advance = glyph.x_advance.at(text.size);
+        place_item(out, curve, Point::new(x, pos.y), advance, FrameItem::Text(item));
+        x += advance;
+    }
+}
+
+/// Places an item of the given width such that its horizontal center sits on
+/// the curve and it is rotated along with the curve.
+fn place_item(out: &mut Frame, curve: &Curve, pos: Point, width: Abs, item: FrameItem) {
+    let Some((point, angle)) = curve.at((pos.x + width / 2.0).to_raw()) else {
+        return;
+    };
+
+    let mut frame = Frame::soft(Size::zero());
+    frame.push(Point::new(-width / 2.0, pos.y), item);
+    frame.transform(
+        Transform::translate(point.x, point.y).pre_concat(Transform::rotate(angle)),
+    );
+    out.push_frame(Point::zero(), frame);
+}
+
+/// The accuracy with which lengths along a curve are computed, in points.
+const ACCURACY: f64 = 1e-3;
+
+/// Measures distances along a path.
+struct Curve {
+    /// The path's segments along with the distance at which they start and
+    /// their length.
+    segments: Vec<(f64, f64, PathSeg)>,
+    /// The length of the whole path.
+    length: f64,
+    /// Whether the path ends with returning to its start.
+    closed: bool,
+}
+
+impl Curve {
+    /// Prepare a path for measuring.
+    fn new(path: &Path) -> Self {
+        let closed = matches!(path.0.last(), Some(PathItem::ClosePath));
+        let bez = path.to_kurbo();
+
+        let mut segments = vec![];
+        let mut length = 0.0;
+        for seg in bez.segments() {
+            let len = seg.arclen(ACCURACY);
+            if len > 0.0 {
+                segments.push((length, len, seg));
+                length += len;
+            }
+        }
+
+        Self { segments, length, closed }
+    }
+
+    /// The point at the given distance along the curve and the curve's
+    /// direction there.
+    ///
+    /// A closed curve wraps around, while an open one is extended in a
+    /// straight line beyond its ends.
+    fn at(&self, distance: f64) -> Option<(Point, Angle)> {
+        let distance = if self.closed && self.length > 0.0 {
+            distance.rem_euclid(self.length)
+        } else {
+            distance
+        };
+
+        let index = self
+            .segments
+            .partition_point(|&(start, _, _)| start <= distance)
+            .saturating_sub(1);
+        let &(start, len, seg) = self.segments.get(index)?;
+
+        let local = distance - start;
+        let (t, beyond) = if local < 0.0 {
+            (0.0, local)
+        } else if local > len {
+            (1.0, local - len)
+        } else {
+            (seg.inv_arclen(local, ACCURACY), 0.0)
+        };
+
+        let dir = direction(seg, t);
+        let p = seg.eval(t) + dir * beyond;
+        Some((Point::new(Abs::raw(p.x), Abs::raw(p.y)), Angle::rad(dir.atan2())))
+    }
+}
+
+/// The normalized direction of a segment at the given parameter.
+fn direction(seg: PathSeg, t: f64) -> Vec2 {
+    const DELTA: f64 = 1e-3;
+    let from = seg.eval((t - DELTA).max(0.0));
+    let to = seg.eval((t + DELTA).min(1.0));
+    (to - from).normalize()
+}
@@ -0,0 +1,75 @@
+;; A plugin that exercises the WASI functions provided to plugins.
+;;
+;; This is the source of `wasi.wasm`. To rebuild it, run
+;; `wat2wasm wasi.wat -o wasi.wasm`.
+(module
+  (import "typst_env" "wasm_minimal_protocol_write_args_to_buffer"
+    (func $write_args (param i32)))
+  (import "typst_env" "wasm_minimal_protocol_send_result_to_host"
+    (func $send_result (param i32 i32)))
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_read"
+    (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "random_get"
+    (func $random_get (param i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "clock_time_get"
+    (func $clock_time_get (param i32 i64 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "path_open"
+    (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "proc_exit"
+    (func $proc_exit (param i32)))
+
+  ;; Arguments are stored at 1024, the I/O vector at 16, and results at 64.
+  (memory (export "memory") 1)
+
+  ;; Write the argument to stdout and return nothing.
+  (func (export "print") (param $len i32) (result i32)
+    (call $write_args (i32.const 1024))
+    (i32.store (i32.const 16) (i32.const 1024))
+    (i32.store offset=4 (i32.const 16) (local.get $len))
+    (drop (call $fd_write (i32.const 1) (i32.const 16) (i32.const 1) (i32.const 32)))
+    (call $send_result (i32.const 1024) (i32.const 0))
+    (i32.const 0))
+
+  ;; Return 16 random bytes.
+  (func (export "random") (result i32)
+    (drop (call $random_get (i32.const 64) (i32.const 16)))
+    (call $send_result (i32.const 64) (i32.const 16))
+    (i32.const 0))
+
+  ;; Return the realtime clock as 8 little-endian bytes.
+  (func (export "time") (result i32)
+    (drop (call $clock_time_get (i32.const 0) (i64.const 1) (i32.const 64)))
+    (call $send_result (i32.const 64) (i32.const 8))
+    (i32.const 0))
+
+  ;; Read from stdin and return the number of read bytes and the errno.
+  (func (export "read") (result i32)
+    (i32.store (i32.const 16) (i32.const 1024))
+    (i32.store offset=4 (i32.const 16) (i32.const 16))
+    (i32.store offset=4 (i32.const 64)
+      (call $fd_read (i32.const 0) (i32.const 16) (i32.const 1) (i32.const 64)))
+    (call $send_result (i32.const 64) (i32.const 8))
+    (i32.const 0))
+
+  ;; Return the errnos of opening a file, writing a buffer that exceeds the
+  ;; memory, and writing to a file descriptor that isn't open.
+  (func (export "errors") (result i32)
+    (i32.store8 (i32.const 64)
+      (call $path_open
+        (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 0)
+        (i64.const 0) (i64.const 0) (i32.const 0) (i32.const 32)))
+    (i32.store (i32.const 16) (i32.const 1024))
+    (i32.store offset=4 (i32.const 16) (i32.const -16))
+    (i32.store8 offset=1 (i32.const 64)
+      (call $fd_write (i32.const 1) (i32.const 16) (i32.const 1) (i32.const 32)))
+    (i32.store8 offset=2 (i32.const 64)
+      (call $fd_write (i32.const 5) (i32.const 16) (i32.const 1) (i32.const 32)))
+    (call $send_result (i32.const 64) (i32.const 3))
+    (i32.const 0))
+
+  ;; Exit with code 3.
+  (func (export "exit") (result i32)
+    (call $proc_exit (i32.const 3))
+    (unreachable)))
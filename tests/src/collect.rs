@@ -26,6 +26,7 @@ pub struct Test {
     pub source: Source,
     pub notes: Vec<Note>,
     pub large: bool,
+    pub recover: bool,
 }
 
 impl Display for Test {
@@ -262,6 +263,8 @@ impl<'a> Parser<'a> {
                 self.collector.large.insert(name.clone());
             }
 
+            let recover = text.starts_with("// RECOVER");
+
             if !selected(&name, self.path.canonicalize().unwrap()) {
                 self.collector.skipped += 1;
                 continue;
@@ -285,7 +288,8 @@ impl<'a> Parser<'a> {
                 }
             }
 
-            self.collector.tests.push(Test { pos, name, source, notes, large });
+            let test = Test { pos, name, source, notes, large, recover };
+            self.collector.tests.push(test);
         }
     }
 
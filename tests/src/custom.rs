@@ -1,8 +1,10 @@
 use std::fmt::Write;
 
 use typst::foundations::Smart;
+use typst::layout::{Abs, Frame, FrameItem};
 use typst::model::{Document, DocumentInfo};
 use typst::World;
+use typst_pdf::PdfOptions;
 
 use crate::collect::Test;
 use crate::world::TestWorld;
@@ -35,11 +37,89 @@ pub fn check(test: &Test, world: &TestWorld, doc: Option<&Document>) -> String {
             test_eq!(sink, info.author, ["Changed"]);
             test_eq!(sink, info.title.as_deref(), Some("Alternative"));
         }
+        "container-opacity-blend" => {
+            let svg = ["opacity=\"0.5\"", "mix-blend-mode: multiply"];
+            let pdf = ["/ca ", "/BM /Multiply", "/BM /Difference"];
+            check_exports(&mut sink, doc, &svg, &pdf);
+        }
+        "container-clip-shape-mask" => {
+            let svg = ["<clipPath", "mask-type=\"luminance\"", "mask-type=\"alpha\""];
+            let pdf = ["/SMask", "/S /Luminosity", "/S /Alpha"];
+            check_exports(&mut sink, doc, &svg, &pdf);
+        }
+        "container-shadow-blur" => {
+            let svg = ["feGaussianBlur", "<clipPath"];
+            let pdf = ["/SMask", "/S /Luminosity"];
+            check_exports(&mut sink, doc, &svg, &pdf);
+        }
+        "recover-show-rule" => {
+            let text = text(doc);
+            test_eq!(sink, text.contains(r#"error:headingdoesnothavefield"page""#), true);
+            test_eq!(sink, text.contains("Therestofthedocumentstaysvisible."), true);
+        }
+        "recover-set-rule" => {
+            let text = text(doc);
+            test_eq!(sink, text.contains("error:unexpectedargument"), true);
+            test_eq!(sink, text.contains("Thetextafterthefailingsetrule"), true);
+        }
+        "recover-syntax-error" => {
+            let text = text(doc);
+            test_eq!(sink, text.contains("error:"), false);
+            test_eq!(sink, text.contains("evaluated:3"), true);
+        }
         _ => {}
     }
     sink
 }
 
+/// Extract the text of all pages, without any whitespace.
+fn text(doc: Option<&Document>) -> String {
+    fn visit(text: &mut String, frame: &Frame) {
+        for (_, item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => visit(text, &group.frame),
+                FrameItem::Text(item) => {
+                    text.extend(item.text.chars().filter(|c| !c.is_whitespace()))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut text = String::new();
+    for page in doc.iter().flat_map(|doc| &doc.pages) {
+        visit(&mut text, &page.frame);
+    }
+    text
+}
+
+/// Check that the SVG and PDF exports of the document contain the given
+/// snippets. This covers effects that the exporters implement separately and
+/// that the reference image only shows for the renderer.
+fn check_exports(sink: &mut String, doc: Option<&Document>, svg: &[&str], pdf: &[&str]) {
+    let Some(doc) = doc else { return };
+
+    let output = typst_svg::svg_merged(doc, Abs::pt(5.0));
+    for snippet in svg {
+        if !output.contains(snippet) {
+            writeln!(sink, "SVG export does not contain {snippet:?}").unwrap();
+        }
+    }
+
+    let output = match typst_pdf::pdf(doc, &PdfOptions::default()) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(_) => {
+            writeln!(sink, "PDF export failed").unwrap();
+            return;
+        }
+    };
+    for snippet in pdf {
+        if !output.contains(snippet) {
+            writeln!(sink, "PDF export does not contain {snippet:?}").unwrap();
+        }
+    }
+}
+
 /// Extract the document information.
 fn info(doc: Option<&Document>) -> DocumentInfo {
     doc.map(|doc| doc.info.clone()).unwrap_or_default()
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use ecow::eco_vec;
 use tiny_skia as sk;
-use typst::diag::{SourceDiagnostic, Warned};
+use typst::diag::{Recovered, SourceDiagnostic, Warned};
 use typst::layout::{Abs, Frame, FrameItem, Page, Transform};
 use typst::model::Document;
 use typst::visualize::Color;
@@ -79,9 +79,16 @@ impl<'a> Runner<'a> {
             log!(into: self.result.infos, "tree: {:#?}", self.test.source.root());
         }
 
-        let Warned { output, warnings } = typst::compile(&self.world);
+        let Warned { output, warnings } = if self.test.recover {
+            typst::compile_recovering(&self.world)
+        } else {
+            let Warned { output, warnings } = typst::compile(&self.world);
+            let output = output.map(|output| Recovered { output, errors: eco_vec![] });
+            Warned { output, warnings }
+        };
+
         let (doc, errors) = match output {
-            Ok(doc) => (Some(doc), eco_vec![]),
+            Ok(Recovered { output, errors }) => (Some(output), errors),
             Err(errors) => (None, errors),
         };
 
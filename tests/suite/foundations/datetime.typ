@@ -91,3 +91,86 @@
 --- datetime-display-insufficient-information ---
 // Error: 2-36 failed to format datetime (insufficient information)
 #datetime.today().display("[hour]")
+
+--- datetime-display-localized ---
+#let date = datetime(year: 2026, month: 10, day: 18)
+#set text(lang: "de")
+#context test(date.display("[day padding:none]. [month repr:long] [year]"), "18. Oktober 2026")
+#context test(date.display("[weekday repr:short]"), "So.")
+#context test(date.display(style: "long"), "18. Oktober 2026")
+#context test(date.display(style: "short"), "18.10.2026")
+#test(date.display("[month repr:long]"), "October")
+
+--- datetime-display-localized-genitive ---
+#let date = datetime(year: 2026, month: 10, day: 18)
+#set text(lang: "ru")
+#context test(date.display(style: "long"), "18 октября 2026")
+#context test(date.display("[weekday]"), "воскресенье")
+
+--- datetime-display-localized-fallback ---
+// Languages without built-in names use the English ones.
+#let date = datetime(year: 2026, month: 10, day: 18)
+#set text(lang: "ko")
+#context test(date.display("[weekday], [month repr:long]"), "Sunday, October")
+
+--- datetime-display-localized-cjk ---
+#let date = datetime(year: 2026, month: 10, day: 18)
+#set text(lang: "ja")
+#context test(date.display(style: "long"), "2026年10月18日")
+#context test(date.display("[weekday]"), "日曜日")
+
+--- datetime-display-style-english ---
+#let date = datetime(year: 2026, month: 10, day: 18, hour: 14, minute: 5, second: 0)
+#test(date.display(style: "long"), "October 18, 2026, 2:05:00 PM")
+#test(date.display(style: "medium"), "Oct 18, 2026, 2:05:00 PM")
+#test(date.display(style: "short"), "10/18/26, 2:05 PM")
+#set text(region: "gb")
+#context test(date.display(style: "long"), "18 October 2026, 14:05:00")
+
+--- datetime-display-translations ---
+#set text(lang: "de", translations: (de: ("month-short-10": "Okt")))
+#context test(datetime(year: 2026, month: 10, day: 18).display(style: "medium"), "18. Okt 2026")
+
+--- datetime-display-pattern-and-style ---
+// Error: 2-51 cannot specify both a pattern and a style
+#datetime.today().display("[year]", style: "long")
+
+--- datetime-parse ---
+#test(datetime.parse("2026-10-18"), datetime(year: 2026, month: 10, day: 18))
+#test(datetime.parse("14:30:15"), datetime(hour: 14, minute: 30, second: 15))
+#test(
+  datetime.parse("2026-10-18T14:30:15"),
+  datetime(year: 2026, month: 10, day: 18, hour: 14, minute: 30, second: 15),
+)
+#test(
+  datetime.parse("2026-10-18 14:30:15"),
+  datetime(year: 2026, month: 10, day: 18, hour: 14, minute: 30, second: 15),
+)
+#test(
+  datetime.parse("18.10.2026", "[day].[month].[year]"),
+  datetime(year: 2026, month: 10, day: 18),
+)
+
+--- datetime-parse-offset ---
+#let s = "2026-10-18T14:30:00+02:00"
+#test(datetime.parse(s).hour(), 14)
+#test(datetime.parse(s, offset: 0).hour(), 12)
+#test(datetime.parse("2026-10-18T23:30:00Z", offset: 2).day(), 19)
+
+--- datetime-parse-invalid ---
+// Error: 2-30 failed to parse datetime ("18 October" is not in ISO 8601 format)
+#datetime.parse("18 October")
+
+--- datetime-parse-offset-duration ---
+#let d = datetime.parse("2026-10-18T12:00:00Z", offset: duration(hours: 5, minutes: 30))
+#test((d.hour(), d.minute()), (17, 30))
+#let d = datetime.parse("2026-10-18T12:00:00Z", offset: -duration(hours: 3, minutes: 30))
+#test((d.hour(), d.minute()), (8, 30))
+
+--- datetime-parse-invalid-offset ---
+// Error: 54-57 offset must be less than 24 hours
+#datetime.parse("2026-10-18T14:30:00+02:00", offset: 100)
+
+--- datetime-parse-invalid-offset-duration ---
+// Error: 49-66 offset must be less than 24 hours
+#datetime.parse("2026-10-18T14:30:00Z", offset: duration(days: 1))
@@ -77,3 +77,11 @@
 --- decimal-expected-integer-error ---
 // Error: 11-25 expected integer, found decimal
 #calc.odd(decimal("1.1"))
+
+--- decimal-format ---
+#let amount = decimal("1234567.895")
+#test(amount.format(currency: "USD"), "$1,234,567.90")
+#test(amount.format(currency: "CHF"), "CHF\u{a0}1,234,567.90")
+#test(decimal("-0.5").format(digits: 0), "−1")
+#set text(lang: "de")
+#context test(amount.format(currency: "EUR"), "1.234.567,90\u{a0}€")
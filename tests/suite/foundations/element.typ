@@ -0,0 +1,140 @@
+// Test user-defined elements.
+
+--- element-construct ---
+#let note = element("note", fields: (
+  body: (type: content, positional: true, required: true),
+  urgent: (type: bool, default: false),
+))
+
+#let n = note[Hi]
+#test(type(n), content)
+#test(n.func(), note)
+#test(n.body, [Hi])
+#test(n.has("body"), true)
+#test(n.has("urgent"), false)
+#test(note(urgent: true)[Hi].urgent, true)
+#test(note("Hi").body, [Hi])
+#test(repr(note), "note")
+#test(repr(note[Hi]), "note(body: [Hi])")
+
+--- element-fields-resolved ---
+#let note = element("note", fields: (
+  body: (type: content, positional: true, required: true),
+  urgent: (type: bool, default: false),
+))
+
+#show note: it => {
+  test(it.urgent, false)
+  test(it.fields(), (body: [Hi], urgent: false))
+}
+
+#note[Hi]
+
+--- element-set ---
+#let note = element("note", fields: (
+  body: (positional: true, required: true),
+  level: (type: int, default: 1),
+))
+
+#show note: it => test(it.level, 3)
+#set note(level: 2)
+#set note(level: 3)
+#note[A]
+
+--- element-set-given-wins ---
+#let note = element("note", fields: (level: (type: int, default: 1)))
+
+#show note: it => test(it.level, 5)
+#set note(level: 2)
+#note(level: 5)
+
+--- element-set-scoped ---
+#let note = element("note", fields: (level: int))
+#let other = element("note", fields: (level: int))
+
+#show note: it => test(it.level, 2)
+#show other: it => test(it.level, none)
+#set note(level: 2)
+#note()
+#other()
+
+--- element-show-where ---
+#let note = element("note", fields: (kind: (type: str, default: "info")))
+
+#show note: it => test(it.kind, "info")
+#show note.where(kind: "warning"): it => test(it.kind, "warning")
+#note()
+#note(kind: "warning")
+
+--- element-display ---
+#let note = element(
+  "note",
+  fields: (body: (type: content, positional: true, required: true)),
+  display: it => test(it.body, [A]),
+)
+
+#note[A]
+
+--- element-query ---
+#let note = element("note", fields: (kind: (type: str, default: "info")))
+
+#note()
+#note(kind: "warning")
+#note() <third>
+
+#context {
+  test(query(note).len(), 3)
+  test(query(note.where(kind: "warning")).len(), 1)
+  test(query(<third>).first().func(), note)
+  test(query(selector(note).before(here())).len(), 3)
+}
+
+--- element-counter ---
+#let note = element("note", fields: (numbering: (default: "1")))
+
+#note()
+#note(numbering: none)
+#note()
+#context test(counter(note).get(), (2,))
+
+--- element-locatable ---
+#let note = element("note")
+
+#show note: it => test(type(it.location()), location)
+#note()
+
+--- element-where-unknown-field ---
+#let note = element("note")
+// Error: 2-21 element `note` does not have field `kind`
+#note.where(kind: 1)
+
+--- element-wrong-type ---
+#let note = element("note", fields: (level: int))
+// Error: 14-17 expected integer, found string
+#note(level: "a")
+
+--- element-missing-required ---
+#let note = element("note", fields: (title: (required: true)))
+// Error: 2-8 missing argument: title
+#note()
+
+--- element-required-with-default ---
+// Error: 2-57 required field `a` cannot have a default value
+#element("x", fields: (a: (required: true, default: 1)))
+
+--- element-invalid-default ---
+// Error: 2-54 invalid default for `a`: expected integer, found string
+#element("x", fields: (a: (type: int, default: "b")))
+
+--- element-bad-field-key ---
+// Error: 2-40 unexpected key "kind", valid keys are "type", "default", "positional", and "required"
+#element("x", fields: (a: (kind: int)))
+
+--- element-reserved-field ---
+// Error: 2-36 field name `label` is reserved
+#element("x", fields: (label: int))
+
+--- element-set-required ---
+#let note = element("note", fields: (title: (required: true)))
+// Error: 11-21 unexpected argument: title
+#set note(title: [A])
@@ -90,3 +90,19 @@
 #float.inf \
 #(-float.inf) \
 #float.nan
+
+--- float-format ---
+#test((1234567.891).format(digits: 2), "1,234,567.89")
+#test((0.125).format(percent: true), "12.5%")
+#test((1234567.891).format(notation: "scientific", significant: 3), "1.23×10⁶")
+#test((9.96).format(notation: "scientific", digits: 1), "1.0×10¹")
+#test((0.0001234).format(notation: "scientific"), "1.234×10⁻⁴")
+#test((-0.001).format(digits: 1), "0.0")
+#test((-float.inf).format(), "−∞")
+#set text(lang: "de")
+#context test((1234567.891).format(digits: 2), "1.234.567,89")
+#context test((0.125).format(percent: true), "12,5\u{a0}%")
+
+--- float-format-unexpected-argument ---
+// Error: 15-24 unexpected argument: unit
+#(1.5).format(unit: "m")
@@ -108,3 +108,34 @@
 --- number-invalid-suffix ---
 // Error: 2-4 invalid number suffix: u
 #1u
+
+--- int-format ---
+#test((1234567).format(), "1,234,567")
+#test((-5).format(percent: true), "−500%")
+#test((1234567).format(significant: 2), "1,200,000")
+#test((1234567).format(notation: "engineering"), "1.234567×10⁶")
+#test((1234).format(grouping: false, digits: 2), "1234.00")
+#set text(lang: "de")
+#context test((1234567).format(), "1.234.567")
+#context test((1234567).format(currency: "EUR"), "1.234.567,00\u{a0}€")
+#set text(lang: "es")
+#context test((1234).format(), "1234")
+#context test((12345).format(), "12.345")
+
+--- int-format-digits-and-significant ---
+// Error: 2-39 cannot specify both the digits and the significant digits
+#(1).format(digits: 2, significant: 2)
+
+--- int-format-lang ---
+#set text(lang: "el")
+#context test((1234567).format(digits: 1), "1.234.567,0")
+#set text(lang: "uk")
+#context test((1234567).format(digits: 1), "1\u{a0}234\u{a0}567,0")
+
+--- int-format-too-many-digits ---
+// Error: 2-24 number of digits must be at most 28
+#(1).format(digits: 29)
+
+--- int-format-too-many-significant-digits ---
+// Error: 2-29 number of significant digits must be at most 28
+#(1).format(significant: 29)
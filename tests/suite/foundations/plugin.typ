@@ -45,3 +45,42 @@
 
 // Error: 2-27 plugin tried to write out of bounds: pointer 0x40000000 is out of bounds for write of length 3
 #p.write_oob(bytes("xyz"))
+
+--- plugin-wasi-print ---
+#let p = plugin("/tests/plugins/wasi.wasm")
+
+// Warning: 7-33 plugin printed: hi there
+#test(p.print(bytes("hi there")), bytes(()))
+
+// What was printed by a previous call does not leak into the next one.
+#test(p.time(), bytes((0,) * 8))
+
+--- plugin-wasi-random ---
+#let p = plugin("/tests/plugins/wasi.wasm")
+#let expected = bytes((
+  179, 200, 155, 142, 107, 104, 66, 191,
+  38, 172, 40, 108, 32, 30, 251, 34,
+))
+#test(p.random(), expected)
+#test(p.random(), expected)
+
+--- plugin-wasi-time ---
+#let p = plugin("/tests/plugins/wasi.wasm")
+#test(p.time(), bytes((0,) * 8))
+
+--- plugin-wasi-read ---
+// Reading from stdin succeeds, but never yields any bytes.
+#let p = plugin("/tests/plugins/wasi.wasm")
+#test(p.read(), bytes((0,) * 8))
+
+--- plugin-wasi-errors ---
+// Opening a file is not permitted, writing a buffer that exceeds the memory
+// faults, and only the standard streams exist.
+#let p = plugin("/tests/plugins/wasi.wasm")
+#test(p.errors(), bytes((76, 21, 8)))
+
+--- plugin-wasi-exit ---
+#let p = plugin("/tests/plugins/wasi.wasm")
+
+// Error: 2-10 plugin panicked: plugin exited with code 3
+#p.exit()
@@ -0,0 +1,96 @@
+// Test user-defined types.
+
+--- record-construct ---
+#let point = record("point", fields: (
+  x: (type: int, default: 0),
+  y: (type: int, default: 0),
+))
+
+#let p = point(x: 3)
+#test(p.x, 3)
+#test(p.y, 0)
+#test(type(p), point)
+#test(repr(p), "point(x: 3, y: 0)")
+#test(repr(point), "point")
+#test(p, point(x: 3, y: 0))
+#test(p == point(x: 3, y: 1), false)
+
+--- record-methods ---
+#let point = record(
+  "point",
+  fields: (x: (type: int, default: 0), y: (type: int, default: 0)),
+  methods: (
+    sum: self => self.x + self.y,
+    scale: (self, k) => type(self)(x: self.x * k, y: self.y * k),
+  ),
+)
+
+#let p = point(x: 1, y: 2)
+#test(p.sum(), 3)
+#test(p.scale(2), point(x: 2, y: 4))
+#test((point.sum)(p), 3)
+
+--- record-distinct-types ---
+#let a = record("thing")
+#let b = record("thing")
+#test(a() == b(), false)
+#test(type(a()) == a, true)
+#test(type(a()) == b, false)
+
+--- record-field-of-record-type ---
+#let point = record("point", fields: (x: int, y: int))
+#let line = record("line", fields: (
+  start: (type: point, required: true),
+  end: (type: point, required: true),
+))
+
+#let l = line(start: point(x: 0, y: 0), end: point(x: 1, y: 1))
+#test(l.end.x, 1)
+
+--- record-field-wrong-record-type ---
+#let point = record("point")
+#let size = record("size")
+#let line = record("line", fields: (start: point))
+// Error: 14-20 expected point, found size
+#line(start: size())
+
+--- record-wrong-type ---
+#let point = record("point", fields: (x: int))
+// Error: 11-14 expected integer, found string
+#point(x: "a")
+
+--- record-missing-required ---
+#let point = record("point", fields: (x: (type: int, required: true)))
+// Error: 2-9 missing argument: x
+#point()
+
+--- record-unknown-field ---
+#let point = record("point", fields: (x: int))
+// Error: 10-11 point does not have field "z"
+#point().z
+
+--- record-unknown-method ---
+#let point = record("point")
+// Error: 10-13 type point has no method `foo`
+#point().foo()
+
+--- record-method-conflict ---
+// Error: 2-65 `x` cannot be both a field and a method
+#record("point", fields: (x: int), methods: (x: self => self.x))
+
+--- record-method-not-function ---
+// Error: 2-34 expected function for method `x`, found integer
+#record("point", methods: (x: 1))
+
+--- record-type-agrees ---
+// The type of a record is the same everywhere it shows up.
+#let point = record("point")
+#let p = point()
+#test(type(p), point)
+#test(repr(type(p)), "point")
+#test(type(p) == type(point()), true)
+
+--- record-type-in-cast-error ---
+#let point = record("point")
+// Error: 10-17 expected string, found point
+#str.len(point())
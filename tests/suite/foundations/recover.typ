@@ -0,0 +1,29 @@
+// Test recovering from errors when compiling for a preview.
+
+--- recover-show-rule ---
+// RECOVER
+// Error: 25-29 heading does not have field "page"
+#show heading: it => it.page
+= Heading
+The rest of the document stays visible.
+
+--- recover-set-rule ---
+// RECOVER
+// Error: 23-27 unexpected argument
+#set text(size: 10pt, 12pt)
+The text after the failing set rule is not styled by it.
+
+--- recover-syntax-error ---
+// RECOVER
+// Error: 1-2 unclosed delimiter
+_Hello
+
+The rest of the document is still evaluated: #(1 + 2)
+
+--- recover-placeholder-not-public ---
+// RECOVER
+// The placeholder cannot be named, so it cannot be restyled.
+// Error: 7-12 unknown variable: error
+// Error: 2:2-2:9 unknown variable: missing
+#show error: set text(blue)
+#missing and more text.
@@ -245,6 +245,76 @@ First!
   image("/assets/images/rhino.png", width: 30pt)
 )
 
+--- container-clip-shape-mask ---
+// Test clipping to shapes and masking with luminance and alpha masks.
+#box(
+  clip-shape: circle(width: 100%),
+  stroke: 4pt + red,
+  square(size: 30pt, fill: gradient.conic(..color.map.rainbow)),
+)
+#box(
+  clip-shape: polygon((0%, 0%), (100%, 0%), (50%, 100%)),
+  fill: aqua,
+  width: 30pt,
+  height: 30pt,
+)
+#box(
+  mask: gradient.linear(white, black),
+  square(size: 30pt, fill: gradient.conic(..color.map.rainbow)),
+)
+#block(
+  mask: (paint: gradient.radial(black, black.transparentize(100%)), mode: "alpha"),
+  fill: green,
+  width: 100%,
+  height: 20pt,
+)
+
+--- box-clip-shape-without-shapes ---
+// Error: 19-23 clip shape must contain at least one shape
+#box(clip-shape: [text])[A]
+
+--- box-mask-missing-paint ---
+// Error: 12-27 dictionary does not contain key "paint"
+#box(mask: (mode: "alpha"))[A]
+
+--- container-shadow-blur ---
+// Test drop shadows with offset, blur and spread, and blurred containers.
+#box(
+  fill: white,
+  inset: 6pt,
+  radius: 4pt,
+  shadow: (offset: (2pt, 3pt), blur: 4pt),
+)[Shadow]
+#box(
+  fill: aqua,
+  inset: 6pt,
+  shadow: (blur: 0pt, spread: 2pt, paint: red),
+)[Spread]
+
+#block(blur: 2pt, inset: 6pt, fill: aqua)[Blurry]
+#rect(width: 40pt, height: 15pt, fill: yellow, shadow: (offset: (3pt, 3pt)))
+
+--- container-opacity-blend ---
+// Test that overlapping contents of a transparent group don't show through
+// each other and that blend modes apply to the whole group.
+#let circles = stack(
+  dir: ltr,
+  spacing: -8pt,
+  circle(radius: 10pt, fill: red),
+  circle(radius: 10pt, fill: blue),
+)
+#box(opacity: 50%, circles)
+#box(fill: yellow, inset: 4pt, box(blend: "multiply", circles))
+#box(fill: yellow, inset: 4pt, box(blend: "difference", opacity: 80%, circles))
+
+--- box-opacity-invalid ---
+// Error: 15-19 ratio must be between 0% and 100%
+#box(opacity: 150%)[A]
+
+--- block-blend-invalid ---
+// Error: 15-20 expected "normal", "multiply", "screen", "overlay", "darken", "lighten", "color-dodge", "color-burn", "hard-light", "soft-light", "difference", "exclusion", "hue", "saturation", "color", or "luminosity"
+#block(blend: "mix")[A]
+
 --- container-layoutable-child ---
 // Test box/block sizing with directly layoutable child.
 //
@@ -65,9 +65,38 @@ Lריווח #h(1cm) R
 לתכנת בעברית `אם א == ב:`
 
 --- bidi-vertical ---
-// Test setting a vertical direction.
-// Error: 16-19 text direction must be horizontal
+// Test vertical writing. Lines run from top to bottom and follow each other
+// from right to left, with Latin text set sideways.
+#set page(width: 80pt, height: 80pt)
 #set text(dir: ttb)
+Vertical lines of text follow each other.
+
+--- bidi-vertical-mixed ---
+// Test that graphics and tables stay upright in vertical writing and that
+// headers stay horizontal.
+#set page(width: 180pt, height: 100pt, header: [Header])
+#set text(dir: ttb)
+Text before.
+#rect(width: 40pt, height: 15pt, fill: aqua)[Upright]
+#table(columns: 2, [A], [B])
+Text after.
+
+--- bidi-vertical-pagebreak ---
+// Test that switching to vertical writing starts a new page.
+#set page(width: 60pt, height: 60pt)
+Horizontal
+#set text(dir: ttb)
+Vertical
+
+--- bidi-vertical-in-container ---
+#box[
+  // Error: 4-22 writing mode cannot be changed inside of containers
+  #set text(dir: ttb)
+]
+
+--- bidi-bottom-to-top ---
+// Error: 16-19 text direction must be horizontal or top-to-bottom
+#set text(dir: btt)
 
 --- issue-1373-bidi-tofus ---
 // Test that shaping missing characters in both left-to-right and
@@ -162,6 +162,57 @@ int main() {
 #set par(justify: true)
 #block(width: 1cm, fill: aqua, lorem(2))
 
+--- justify-kashida ---
+// Test that Arabic lines are stretched with kashidas instead of wider spaces.
+#set page(width: 150pt)
+#set text(font: "DejaVu Sans Mono", lang: "ar")
+#set par(justify: true, justification: "kashida")
+السلام عليكم ورحمة الله وبركاته، كيف حالكم اليوم؟ نتمنى لكم يوما سعيدا.
+
+--- justify-kashida-both ---
+// Test distributing the extra width between kashidas and spaces.
+#set page(width: 150pt)
+#set text(font: "DejaVu Sans Mono", lang: "ar")
+#set par(justify: true, justification: "both")
+السلام عليكم ورحمة الله وبركاته، كيف حالكم اليوم؟ نتمنى لكم يوما سعيدا.
+
+--- justify-kashida-invalid ---
+// Error: 25-34 expected "spacing", "kashida", or "both"
+#set par(justification: "tatweel")
+
+--- justify-limits-scaling ---
+// Test that glyphs are slightly expanded and condensed to even out the
+// spacing of justified lines.
+#set par(
+  justify: true,
+  justification-limits: (
+    spacing: (min: 80%, max: 120%),
+    scaling: (min: 97%, max: 103%),
+  ),
+)
+#lorem(30)
+
+--- justify-limits-tracking ---
+// Test that tracking takes part of the stretch and shrink before the spacing
+// exceeds its limits.
+#set par(
+  justify: true,
+  justification-limits: (tracking: (min: -0.02em, max: 0.05em)),
+)
+#lorem(30)
+
+--- justify-limits-bad-minimum ---
+// Error: 32-65 minimum must be larger than 0% and at most 100%
+#set par(justification-limits: (spacing: (min: 110%, max: 120%)))
+
+--- justify-limits-positive-tracking ---
+// Error: 32-64 minimum tracking must not be positive
+#set par(justification-limits: (tracking: (min: 1pt, max: 2pt)))
+
+--- justify-limits-missing-maximum ---
+// Error: 32-53 dictionary does not contain key "max"
+#set par(justification-limits: (scaling: (min: 95%)))
+
 --- issue-2419-justify-hanging-indent ---
 // Test that combination of justification and hanging indent doesn't result in
 // an underfull first line.
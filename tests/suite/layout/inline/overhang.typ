@@ -22,3 +22,36 @@
 #set align(end)
 #set text(dir: rtl)
 :
+
+--- overhang-protrusion ---
+// Test that custom protrusion tables move characters into the margin. The
+// rectangle marks the text area.
+#set page(width: 130pt, margin: 15pt)
+#set par(justify: true)
+#set text(protrusion: (
+  "Libertinus Serif": (
+    "“": (start: 50%),
+    "”": (end: 50%),
+    "T": (start: 10%, end: 10%),
+    "-": (end: 60%),
+  ),
+))
+#rect(inset: 0pt, stroke: 0.5pt + red, width: 100%)[
+  “Typesetting is an art,” they said. To which I replied: “Typst makes it
+  easy. Try it with long ex-amples!”
+]
+
+--- overhang-protrusion-other-font ---
+// Test that protrusion tables only apply to the font they are given for.
+#set page(width: 130pt, margin: 15pt)
+#set par(justify: true)
+#set text(font: "New Computer Modern", protrusion: (
+  "Libertinus Serif": ("T": (start: 50%, end: 50%)),
+))
+#rect(inset: 0pt, stroke: 0.5pt + red, width: 100%)[
+  To Typst, To Typst, To Typst, To Typst.
+]
+
+--- overhang-protrusion-not-a-character ---
+// Error: 23-64 expected a single character, found "Th"
+#set text(protrusion: ("Libertinus Serif": ("Th": (start: 5%))))
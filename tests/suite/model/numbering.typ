@@ -63,6 +63,37 @@
 #t(pat: "⓵", 1, "⓵")
 #t(pat: "⓵", 10, "⓾")
 
+// Greek.
+#t(pat: "α", 1, "αʹ", "βʹ", 6, "ϛʹ", 11, "ιαʹ", 999, "ϡϟθʹ", 1821, "͵αωκαʹ")
+#t(pat: "Α", 24, "ΚΔʹ")
+#t(pat: "α", 0, "0", 10000, "10000")
+
+// Armenian.
+#t(pat: "Ա", 1, "Ա", 19, "ԺԹ", 1988, "ՌՋՁԸ")
+#t(pat: "ա", 2024, "սիդ")
+
+// Georgian.
+#t(pat: "ა", 1, "ა", 11, "ია", 2024, "ცკდ", 10000, "ჵ", 10001, "ჵა")
+
+// Thai.
+#t(pat: "\u{0E51}", 2567, "๒๕๖๗")
+
+// Tamil.
+#t(pat: "\u{0BE7}", 105, "௧௦௫")
+
+// Words.
+#t(pat: "one", 0, "zero", "one", "two", 21, "twenty-one", 100, "one hundred")
+#t(pat: "One", 1234, "One thousand two hundred thirty-four")
+#t(pat: "first", 1, "first", "second", "third", "fourth", 12, "twelfth", 20, "twentieth", "twenty-first")
+#t(pat: "First", 103, "One hundred third")
+#test(numbering("Book One:", 3), "Book Three:")
+#test(numbering("first.a)", 2, 3), "second.c)")
+
+// Words inside other words are left alone.
+#test(numbering("Zone 1", 5), "Zone 5")
+#test(numbering("Phone 1.a", 2, 3), "Phone 2.c")
+#test(numbering("Oneness 1", 2), "Oneness 2")
+
 --- numbering-negative ---
 // Error: 17-19 number must be at least zero
 #numbering("1", -1)
@@ -67,3 +67,53 @@
 --- issue-1351-parameter-dictionary ---
 // Error: 17-22 expected pattern, found string
 #let foo((test: "bar")) = {}
+
+--- params-type-annotations ---
+#let f(x as int, y as length | auto: auto) -> str = repr(x) + " " + repr(y)
+#test(f(1), "1 auto")
+#test(f(1, y: 2pt), "1 2pt")
+#test(f.with(3)(), "3 auto")
+
+--- params-type-annotations-anonymous ---
+#let f = (x as int | float) -> float => x * 1.0
+#test(f(2), 2.0)
+#test(((x as str) => x)("a"), "a")
+
+--- params-type-annotation-content ---
+#let f(body as content) = body
+#test(f("hi"), [hi])
+
+--- params-type-annotation-destructuring ---
+#let f((a, b) as array) = a + b
+#test(f((1, 2)), 3)
+
+--- params-type-annotation-record ---
+#let point = record("point", fields: (x: int))
+#let first(p as point) -> int = p.x
+#test(first(point(x: 2)), 2)
+
+--- params-type-annotation-wrong-positional ---
+#let f(x as int) = x
+// Error: 4-7 expected integer, found string
+#f("a")
+
+--- params-type-annotation-wrong-named ---
+#let f(size as length | auto: auto) = size
+// Error: 10-13 expected length or auto, found string
+#f(size: "a")
+
+--- params-type-annotation-wrong-default ---
+// Error: 18-21 expected integer, found string
+#let f(x as int: "a") = x
+
+--- params-type-annotation-not-a-type ---
+// Error: 13-14 expected type, function, none, or auto, found integer
+#let f(x as 1) = x
+
+--- params-type-annotation-wrong-return ---
+// Error: 22-28 expected integer, found string
+#{ let f(x) -> int = str(x); f(1) }
+
+--- params-type-annotation-wrong-explicit-return ---
+// Error: 23-33 expected integer, found string
+#{ let f() -> int = { return "a" }; f() }
@@ -83,3 +83,27 @@
 // Error: 17-24 expected two or three letter language code (ISO 639-1/2/3)
 // Hint: 17-24 you should leave only "en" in the `lang` parameter and specify "gb" in the `region` parameter
 #set text(lang: "en-gb")
+
+--- text-translations ---
+#set text(lang: "de", translations: (de: (figure: "Abb.")))
+#show figure: it => test(it.supplement, [Abb.])
+#figure(rect())
+
+--- text-translations-region ---
+#set text(lang: "de", region: "at")
+#set text(translations: (de: (figure: "Abb."), "de-AT": "figure = Bild"))
+#show figure: it => test(it.supplement, [Bild])
+#figure(rect())
+
+--- text-translations-fallback ---
+#set text(lang: "de", translations: (de: (table: "Tab.")))
+#show figure: it => test(it.supplement, [Abbildung])
+#figure(rect())
+
+--- text-translations-unknown-key ---
+// Error: 25-48 unknown translation key: chapter
+#set text(translations: (en: (chapter: "Part")))
+
+--- text-translations-bad-lang ---
+// Error: 25-39 expected two or three letter language code (ISO 639-1/2/3)
+#set text(translations: (english: (:)))
@@ -0,0 +1,20 @@
+// Test ruby annotations.
+
+--- ruby-group ---
+#ruby[Tōkyō][east capital] is the capital of #ruby[Nihon][sun origin].
+
+--- ruby-mono ---
+#set page(width: 80pt)
+Spell #ruby("ABC", ("a", "b", "c")) and #ruby("DEF", ("d", "e", "f")) aloud.
+
+--- ruby-position-bottom ---
+#ruby(position: bottom, gap: 2pt)[Pinyin][pīn yīn]
+
+--- ruby-overhang ---
+I#ruby[X][annotation]I \
+I#ruby(overhang: false)[X][annotation]I
+
+--- ruby-mono-count-mismatch ---
+// Error: 2-29 expected 2 annotations, one for each character of the base, found 3
+// Hint: 2-29 to annotate the whole base at once, pass a single annotation
+#ruby("ab", ("x", "y", "z"))
@@ -0,0 +1,28 @@
+// Test arcs and sectors.
+
+--- arc ---
+// Test arcs in both directions and arcs that exceed a full circle.
+#set page(width: auto)
+#arc(radius: 10pt, start: 180deg, end: 360deg)
+#arc(radius: 10pt, start: -90deg, end: 135deg, stroke: 2pt + eastern)
+#arc(radius: 10pt, start: 90deg, end: -90deg, stroke: (paint: red, cap: "round"))
+#arc(radius: 10pt, end: 500deg, stroke: 1pt + blue)
+
+--- sector ---
+// Test sectors, ring slices, and full circles with and without holes.
+#set page(width: auto)
+#sector(radius: 12pt, start: -90deg, end: 45deg, fill: aqua)
+#sector(radius: 12pt, inner-radius: 6pt, end: 270deg, fill: red, stroke: 1pt)
+#sector(radius: 12pt, inner-radius: 6pt, end: 400deg, fill: green)
+#sector(radius: 12pt, start: 30deg, end: -30deg)
+
+--- sector-pie-chart ---
+// Test that sectors of the same circle line up.
+#let data = ((0.3, red), (0.45, green), (0.25, blue))
+#let start = -90deg
+#for (share, color) in data {
+  let end = start + share * 360deg
+  place(sector(radius: 25pt, start: start, end: end, fill: color))
+  start = end
+}
+#v(50pt)
@@ -0,0 +1,37 @@
+// Test combining shapes.
+
+--- combine-operations ---
+// Test each operation on two overlapping circles.
+#set page(width: auto)
+#let a = circle(radius: 12pt)
+#let b = move(dx: 12pt, circle(radius: 12pt))
+#combine(a, b, fill: aqua)
+#combine(operation: "intersection", a, b, fill: aqua)
+#combine(operation: "difference", a, b, fill: aqua)
+#combine(operation: "xor", a, b, fill: aqua, stroke: 1pt)
+
+--- combine-holes ---
+// Test that holes are kept and that stroking the result outlines them.
+#let frame = rect(width: 40pt, height: 30pt)
+#let hole = move(dx: 10pt, dy: 8pt, rect(width: 20pt, height: 14pt))
+#let bar = move(dx: 18pt, dy: -4pt, rect(width: 4pt, height: 38pt))
+#combine(
+  combine(operation: "difference", frame, hole),
+  bar,
+  fill: yellow,
+  stroke: 1pt + red,
+)
+
+--- combine-empty ---
+// Test that combining nothing or disjoint shapes under intersection yields no
+// shape.
+#combine(operation: "intersection")
+#combine(
+  operation: "intersection",
+  rect(width: 10pt, height: 10pt),
+  move(dx: 20pt, rect(width: 10pt, height: 10pt)),
+)
+
+--- combine-operation-invalid ---
+// Error: 21-28 expected "union", "intersection", "difference", or "xor"
+#combine(operation: "merge", rect())
@@ -105,3 +105,7 @@
 #align(right, rect(width: -1cm, fill: gradient.linear(red, blue))[Reverse right])
 
 #align(right, rect(width: 1cm, fill: gradient.linear(red, blue))[Right])
+
+--- rect-shadow-unknown-key ---
+// Error: 15-38 unexpected key "color", valid keys are "offset", "blur", "spread", and "paint"
+#rect(shadow: (blur: 2pt, color: red))
@@ -0,0 +1,30 @@
+// Test text on a path.
+
+--- text-path-circle ---
+// Test text around a closed shape, wrapping past the end of the outline.
+#text-path(
+  circle(radius: 30pt),
+  align: center,
+  offset: -25%,
+)[Round and round it goes, where it stops]
+
+--- text-path-curve ---
+// Test alignment along an open Bézier curve.
+#let arc = path((0pt, 30pt), ((100pt, 30pt), (-20pt, -40pt)))
+#stack(
+  spacing: 8pt,
+  text-path(arc, align: start)[Start],
+  text-path(arc, align: center)[Center],
+  text-path(arc, align: end)[End],
+)
+
+--- text-path-beyond-end ---
+// Test that text continues in a straight line past the end of an open path and
+// that inline content is placed as a whole.
+#text-path(line(length: 40pt, angle: 20deg), offset: 10pt)[
+  Longer than the line #box(fill: red, width: 5pt, height: 5pt)
+]
+
+--- text-path-align-vertical ---
+// Error: 39-42 expected `start`, `left`, `center`, `right`, or `end`, found top
+#text-path(line(length: 10pt), align: top)[A]